@@ -82,8 +82,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         let material = MaterialLibrary::get_material(&material_name)?;
         println!("  {}: ρ = {:.0} kg/m³, ε = {:.1}, melting point = {:?} K", 
                  material.name, 
-                 material.density, 
-                 material.emissivity,
+                 material.get_density(298.15, Some(&mut formula_engine))?, 
+                 material.get_emissivity(298.15, Some(&mut formula_engine))?,
                  material.melting_point);
     }
     println!();
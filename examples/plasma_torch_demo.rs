@@ -91,7 +91,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("5. Boundary heat loss analysis:");
     
     let temperatures = [400.0, 600.0, 800.0, 1000.0, 1200.0];
-    let emissivity = 0.8;
+    let emissivity = Some(0.8);
     
     println!("   Temperature (K) | Convection (W/m²) | Radiation (W/m²) | Total (W/m²)");
     println!("   ----------------|-------------------|------------------|-------------");
@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use tauri::State;
+use plasma_simulation::errors::FormulaLimit;
 use plasma_simulation::formula::{FormulaEngine, FormulaManager};
 
 /// Formula validation result
@@ -29,6 +30,8 @@ pub struct FormulaEvaluationResult {
     pub success: bool,
     pub value: Option<f64>,
     pub error: Option<String>,
+    /// Resource limit ("timeout", "operations" or "memory") that aborted the evaluation
+    pub limit: Option<FormulaLimit>,
 }
 
 /// Formula information for UI display
@@ -132,11 +135,13 @@ pub async fn evaluate_formula(
             success: true,
             value: Some(value),
             error: None,
+            limit: None,
         }),
         Err(e) => Ok(FormulaEvaluationResult {
             success: false,
             value: None,
             error: Some(e.to_string()),
+            limit: e.formula_limit(),
         }),
     }
 }
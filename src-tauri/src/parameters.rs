@@ -10,6 +10,7 @@
  */
 
 use serde::{Deserialize, Serialize};
+use plasma_simulation::units;
 use log::info;
 use chrono;
 
@@ -23,6 +24,9 @@ pub struct ParameterTemplate {
 }
 
 /// Complete simulation parameters structure
+///
+/// Dimensional fields accept plain numbers in the unit noted on the field or
+/// quantity strings such as "25 degC" or "150 kW", converted on load.
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct SimulationParameters {
     // Furnace geometry
@@ -53,7 +57,9 @@ pub struct SimulationParameters {
 /// Furnace geometry parameters
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct GeometryParameters {
+    #[serde(deserialize_with = "units::meters")]
     pub cylinder_height: f64,      // Height of the furnace cylinder (m)
+    #[serde(deserialize_with = "units::meters")]
     pub cylinder_radius: f64,      // Radius of the furnace cylinder (m)
 }
 
@@ -70,8 +76,10 @@ pub struct MeshParameters {
 pub struct TorchConfig {
     pub id: i32,                   // Torch ID
     pub position: TorchPosition,   // Position in furnace
+    #[serde(deserialize_with = "units::kilowatts")]
     pub power: f64,                // Power (kW)
     pub efficiency: f64,           // Efficiency (0.0-1.0)
+    #[serde(deserialize_with = "units::meters")]
     pub sigma: f64,                // Gaussian spread parameter (m)
 }
 
@@ -96,15 +104,21 @@ pub struct MaterialParameters {
     pub thermal_conductivity: f64, // Thermal conductivity (W/(m·K))
     pub specific_heat: f64,        // Specific heat capacity (J/(kg·K))
     pub emissivity: f64,           // Surface emissivity (0.0-1.0)
+    #[serde(deserialize_with = "units::kelvin")]
     pub melting_point: f64,        // Melting point (K)
 }
 
 /// Boundary conditions for the simulation
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct BoundaryParameters {
+    #[serde(deserialize_with = "units::kelvin")]
     pub initial_temperature: f64,   // Initial temperature (K)
+    #[serde(deserialize_with = "units::kelvin")]
     pub ambient_temperature: f64,   // Ambient temperature (K)
     pub wall_boundary_type: String, // "mixed", "adiabatic", "fixed-temperature"
+    #[serde(default, deserialize_with = "units::optional_kelvin")]
+    pub wall_temperature: Option<f64>, // Wall temperature (K), required for "fixed-temperature"
+    #[serde(deserialize_with = "units::heat_transfer_coefficient")]
     pub convection_coefficient: f64, // Heat transfer coefficient (W/(m²·K))
     pub surface_emissivity: f64,    // Surface emissivity for radiation (0.0-1.0)
 }
@@ -112,7 +126,9 @@ pub struct BoundaryParameters {
 /// Simulation settings
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct SimulationSettings {
+    #[serde(deserialize_with = "units::seconds")]
     pub total_time: f64,            // Total simulation time (s)
+    #[serde(deserialize_with = "units::seconds")]
     pub output_interval: f64,       // Output interval (s)
     pub solver_method: String,      // "forward-euler", "crank-nicolson"
     pub cfl_factor: f64,            // CFL safety factor (0.0-1.0)
@@ -154,6 +170,7 @@ pub fn get_parameters() -> Result<SimulationParameters, String> {
             initial_temperature: 298.0,
             ambient_temperature: 298.0,
             wall_boundary_type: "mixed".to_string(),
+            wall_temperature: None,
             convection_coefficient: 10.0,
             surface_emissivity: 0.8,
         },
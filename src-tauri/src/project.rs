@@ -125,6 +125,13 @@ impl Project {
         if self.parameters.boundary.ambient_temperature < 273.0 {
             return Err("Ambient temperature must be above absolute zero (273K)".to_string());
         }
+        if self.parameters.boundary.wall_boundary_type == "fixed-temperature" {
+            match self.parameters.boundary.wall_temperature {
+                Some(temperature) if temperature > 0.0 => {}
+                Some(_) => return Err("Wall temperature must be above absolute zero".to_string()),
+                None => return Err("Wall temperature is required for a fixed-temperature wall".to_string()),
+            }
+        }
 
         // Validate material properties
         if self.parameters.materials.density <= 0.0 {
@@ -412,6 +419,7 @@ fn create_small_furnace_params() -> SimulationParameters {
             initial_temperature: 298.0,
             ambient_temperature: 298.0,
             wall_boundary_type: "mixed".to_string(),
+            wall_temperature: None,
             convection_coefficient: 10.0,
             surface_emissivity: 0.8,
         },
@@ -468,6 +476,7 @@ fn create_industrial_furnace_params() -> SimulationParameters {
             initial_temperature: 298.0,
             ambient_temperature: 298.0,
             wall_boundary_type: "mixed".to_string(),
+            wall_temperature: None,
             convection_coefficient: 15.0,
             surface_emissivity: 0.7,
         },
@@ -517,6 +526,7 @@ fn create_high_power_params() -> SimulationParameters {
             initial_temperature: 298.0,
             ambient_temperature: 298.0,
             wall_boundary_type: "mixed".to_string(),
+            wall_temperature: None,
             convection_coefficient: 20.0,
             surface_emissivity: 0.9,
         },
@@ -573,6 +583,7 @@ fn create_medical_waste_params() -> SimulationParameters {
             initial_temperature: 298.0,
             ambient_temperature: 298.0,
             wall_boundary_type: "mixed".to_string(),
+            wall_temperature: None,
             convection_coefficient: 12.0,
             surface_emissivity: 0.85,
         },
@@ -232,6 +232,28 @@ fn convert_parameters_to_config(parameters: SimulationParameters) -> Result<Simu
         }
     }).collect();
     
+    // Set boundary conditions
+    config.boundaries.convection_coefficient = parameters.boundary.convection_coefficient;
+    config.boundaries.outer_wall = match parameters.boundary.wall_boundary_type.as_str() {
+        "adiabatic" => Some(BoundarySpec::uniform(BoundaryCondition::Adiabatic)),
+        "fixed-temperature" => match parameters.boundary.wall_temperature {
+            Some(temperature) if temperature > 0.0 => {
+                Some(BoundarySpec::uniform(BoundaryCondition::FixedTemperature { temperature }))
+            }
+            Some(temperature) => return Err(format!("Wall temperature must be above absolute zero, got {} K", temperature)),
+            None => return Err("Wall temperature is required for a fixed-temperature wall".to_string()),
+        },
+        "mixed" => Some(BoundarySpec::uniform(BoundaryCondition::Combined {
+            heat_transfer_coefficient: parameters.boundary.convection_coefficient,
+            emissivity: parameters.boundary.surface_emissivity,
+            ambient_temperature: parameters.boundary.ambient_temperature,
+        })),
+        other => {
+            warn!("Unknown wall boundary type '{}', using material defaults", other);
+            None
+        }
+    };
+    
     // Set material
     config.material.material_name = parameters.materials.material_type.clone();
     
@@ -24,6 +24,9 @@ pub enum SimulationError {
     #[error("Formula evaluation error: {formula} - {error}")]
     FormulaError { formula: String, error: String },
     
+    #[error("Formula {limit} limit exceeded: {formula} - {details}")]
+    FormulaLimitExceeded { formula: String, limit: FormulaLimit, details: String },
+    
     #[error("Physics calculation error: {operation} - {details}")]
     PhysicsError { operation: String, details: String },
     
@@ -50,6 +53,38 @@ pub enum SimulationError {
     VisualizationError { operation: String, details: String },
 }
 
+/// Resource limit that aborted a formula evaluation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FormulaLimit {
+    /// Wall-clock execution time
+    Timeout,
+    /// Number of script operations
+    Operations,
+    /// Size of a string, array or map built by the formula
+    Memory,
+}
+
+impl std::fmt::Display for FormulaLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Timeout => write!(f, "time"),
+            Self::Operations => write!(f, "operation"),
+            Self::Memory => write!(f, "memory"),
+        }
+    }
+}
+
+impl SimulationError {
+    /// Resource limit behind a formula error, if one was exceeded
+    pub fn formula_limit(&self) -> Option<FormulaLimit> {
+        match self {
+            Self::FormulaLimitExceeded { limit, .. } => Some(*limit),
+            _ => None,
+        }
+    }
+}
+
 /// Result type alias for simulation operations
 pub type Result<T> = std::result::Result<T, SimulationError>;
 
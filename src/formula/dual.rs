@@ -0,0 +1,252 @@
+//! Dual numbers for derivatives of formulas
+//!
+//! Forward-mode automatic differentiation: a dual number carries a value and
+//! its derivative with respect to one variable, and every arithmetic
+//! operation and math function propagates both by the chain rule. Binding
+//! `T` to the dual number (T, 1) and evaluating an unchanged formula yields
+//! f(T) and df/dT exactly, without a symbolic rewrite of the expression.
+//!
+//! The operators and functions are registered on the Rhai engine for every
+//! combination of dual numbers with floats and integers, so literals and
+//! the other (constant) standard variables mix freely with `T`. Comparisons
+//! use the value only; a conditional therefore differentiates the branch it
+//! takes.
+
+use rhai::{Engine, FLOAT, INT};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Value with its derivative with respect to the differentiation variable
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dual {
+    pub value: f64,
+    pub derivative: f64,
+}
+
+impl Dual {
+    /// Constant with zero derivative
+    pub fn constant(value: f64) -> Self {
+        Self { value, derivative: 0.0 }
+    }
+
+    /// Differentiation variable (derivative one)
+    pub fn variable(value: f64) -> Self {
+        Self { value, derivative: 1.0 }
+    }
+
+    /// Apply a function with known derivative f'(value) by the chain rule
+    fn chain(self, value: f64, slope: f64) -> Self {
+        Self { value, derivative: slope * self.derivative }
+    }
+
+    fn sqrt(self) -> Self {
+        let root = self.value.sqrt();
+        self.chain(root, 0.5 / root)
+    }
+
+    fn exp(self) -> Self {
+        let value = self.value.exp();
+        self.chain(value, value)
+    }
+
+    fn ln(self) -> Self {
+        self.chain(self.value.ln(), 1.0 / self.value)
+    }
+
+    fn log10(self) -> Self {
+        self.chain(self.value.log10(), 1.0 / (self.value * std::f64::consts::LN_10))
+    }
+
+    /// Logarithm to a base, ln(a) / ln(base)
+    fn log(self, base: Self) -> Self {
+        self.ln() / base.ln()
+    }
+
+    fn asin(self) -> Self {
+        self.chain(self.value.asin(), 1.0 / (1.0 - self.value * self.value).sqrt())
+    }
+
+    fn acos(self) -> Self {
+        self.chain(self.value.acos(), -1.0 / (1.0 - self.value * self.value).sqrt())
+    }
+
+    fn powf(self, exponent: Self) -> Self {
+        let value = self.value.powf(exponent.value);
+        // d(a^b) = a^b (b' ln a + b a' / a); the ln term only applies to variable exponents
+        let mut derivative = exponent.value * self.value.powf(exponent.value - 1.0) * self.derivative;
+        if exponent.derivative != 0.0 {
+            derivative += value * self.value.ln() * exponent.derivative;
+        }
+        Self { value, derivative }
+    }
+
+    fn min(self, other: Self) -> Self {
+        if other.value < self.value { other } else { self }
+    }
+
+    fn max(self, other: Self) -> Self {
+        if other.value > self.value { other } else { self }
+    }
+}
+
+impl From<f64> for Dual {
+    fn from(value: f64) -> Self {
+        Self::constant(value)
+    }
+}
+
+impl Add for Dual {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self { value: self.value + other.value, derivative: self.derivative + other.derivative }
+    }
+}
+
+impl Sub for Dual {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self { value: self.value - other.value, derivative: self.derivative - other.derivative }
+    }
+}
+
+impl Mul for Dual {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Self {
+            value: self.value * other.value,
+            derivative: self.derivative * other.value + self.value * other.derivative,
+        }
+    }
+}
+
+impl Div for Dual {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        Self {
+            value: self.value / other.value,
+            derivative: (self.derivative * other.value - self.value * other.derivative) / (other.value * other.value),
+        }
+    }
+}
+
+impl Neg for Dual {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self { value: -self.value, derivative: -self.derivative }
+    }
+}
+
+/// Register a binary function for dual numbers mixed with floats and integers
+macro_rules! register_binary {
+    ($engine:expr, $name:expr, $op:expr) => {{
+        let op: fn(Dual, Dual) -> _ = $op;
+        $engine.register_fn($name, move |a: Dual, b: Dual| op(a, b));
+        $engine.register_fn($name, move |a: Dual, b: FLOAT| op(a, Dual::constant(b)));
+        $engine.register_fn($name, move |a: FLOAT, b: Dual| op(Dual::constant(a), b));
+        $engine.register_fn($name, move |a: Dual, b: INT| op(a, Dual::constant(b as f64)));
+        $engine.register_fn($name, move |a: INT, b: Dual| op(Dual::constant(a as f64), b));
+    }};
+}
+
+/// Register dual-number arithmetic, comparisons and math functions on an engine
+pub fn register(engine: &mut Engine) {
+    engine.register_type_with_name::<Dual>("Dual");
+
+    register_binary!(engine, "+", |a, b| a + b);
+    register_binary!(engine, "-", |a, b| a - b);
+    register_binary!(engine, "*", |a, b| a * b);
+    register_binary!(engine, "/", |a, b| a / b);
+    register_binary!(engine, "**", |a: Dual, b: Dual| a.powf(b));
+    register_binary!(engine, "min", |a: Dual, b: Dual| a.min(b));
+    register_binary!(engine, "max", |a: Dual, b: Dual| a.max(b));
+    register_binary!(engine, "log", |a: Dual, b: Dual| a.log(b));
+
+    register_binary!(engine, "<", |a: Dual, b: Dual| a.value < b.value);
+    register_binary!(engine, "<=", |a: Dual, b: Dual| a.value <= b.value);
+    register_binary!(engine, ">", |a: Dual, b: Dual| a.value > b.value);
+    register_binary!(engine, ">=", |a: Dual, b: Dual| a.value >= b.value);
+    register_binary!(engine, "==", |a: Dual, b: Dual| a.value == b.value);
+    register_binary!(engine, "!=", |a: Dual, b: Dual| a.value != b.value);
+
+    engine.register_fn("-", |a: Dual| -a);
+    engine.register_fn("+", |a: Dual| a);
+
+    type Unary = fn(Dual) -> Dual;
+    let unary: [(&str, Unary); 17] = [
+        ("sqrt", Dual::sqrt),
+        ("exp", Dual::exp),
+        ("ln", Dual::ln),
+        ("log", Dual::log10),
+        ("sin", |a| a.chain(a.value.sin(), a.value.cos())),
+        ("cos", |a| a.chain(a.value.cos(), -a.value.sin())),
+        ("tan", |a| a.chain(a.value.tan(), 1.0 / (a.value.cos() * a.value.cos()))),
+        ("sinh", |a| a.chain(a.value.sinh(), a.value.cosh())),
+        ("cosh", |a| a.chain(a.value.cosh(), a.value.sinh())),
+        ("tanh", |a| a.chain(a.value.tanh(), 1.0 - a.value.tanh() * a.value.tanh())),
+        ("asin", Dual::asin),
+        ("acos", Dual::acos),
+        ("atan", |a| a.chain(a.value.atan(), 1.0 / (1.0 + a.value * a.value))),
+        ("abs", |a| a.chain(a.value.abs(), a.value.signum())),
+        ("floor", |a| Dual::constant(a.value.floor())),
+        ("ceiling", |a| Dual::constant(a.value.ceil())),
+        ("round", |a| Dual::constant(a.value.round())),
+    ];
+    for (name, function) in unary {
+        engine.register_fn(name, function);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(formula: &str, t: f64) -> Dual {
+        let mut engine = Engine::new();
+        register(&mut engine);
+        let mut scope = rhai::Scope::new();
+        scope.push("T", Dual::variable(t));
+        let result: rhai::Dynamic = engine.eval_with_scope(&mut scope, formula).unwrap();
+        result.try_cast::<Dual>().unwrap()
+    }
+
+    #[test]
+    fn test_dual_arithmetic_and_functions() {
+        let d = evaluate("3.0 * T * T - 2 * T + 1.0", 2.0);
+        assert_eq!((d.value, d.derivative), (9.0, 10.0));
+
+        let d = evaluate("1.0 / T", 4.0);
+        assert!((d.derivative + 1.0 / 16.0).abs() < 1e-15);
+
+        let d = evaluate("exp(-1000.0 / T) + sqrt(T) + ln(T)", 500.0);
+        let expected = (-2.0f64).exp() * 1000.0 / 250000.0 + 0.5 / 500.0f64.sqrt() + 1.0 / 500.0;
+        assert!((d.derivative - expected).abs() < 1e-15);
+
+        let d = evaluate("T ** 1.5", 4.0);
+        assert!((d.value - 8.0).abs() < 1e-12 && (d.derivative - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_dual_inverse_trigonometry_and_logarithms() {
+        let d = evaluate("asin(T)", 0.6);
+        assert!((d.value - 0.6f64.asin()).abs() < 1e-15 && (d.derivative - 1.25).abs() < 1e-12);
+        let d = evaluate("acos(T)", 0.6);
+        assert!((d.value - 0.6f64.acos()).abs() < 1e-15 && (d.derivative + 1.25).abs() < 1e-12);
+
+        // Two-argument log takes the base second, as in value mode
+        let d = evaluate("log(T, 2.0)", 8.0);
+        assert!((d.value - 3.0).abs() < 1e-12);
+        assert!((d.derivative - 1.0 / (8.0 * std::f64::consts::LN_2)).abs() < 1e-15);
+        let d = evaluate("log(T, 10)", 100.0);
+        assert!((d.value - 2.0).abs() < 1e-12);
+        let d = evaluate("log(1024.0, T)", 2.0);
+        assert!((d.value - 10.0).abs() < 1e-12);
+        assert!((d.derivative + 10.0 / (2.0 * std::f64::consts::LN_2)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_dual_conditionals_follow_value() {
+        let formula = "if T < 500 { 2.0 * T } else { max(T * T, 1000.0) }";
+        assert_eq!(evaluate(formula, 100.0).derivative, 2.0);
+        assert_eq!(evaluate(formula, 600.0).derivative, 1200.0);
+        assert_eq!(evaluate("-T.abs()", -3.0).derivative, 1.0);
+    }
+}
@@ -3,14 +3,114 @@
 //! This module provides safe evaluation of mathematical formulas for
 //! material properties, heat sources, and boundary conditions.
 
-use crate::errors::Result;
+use crate::errors::{FormulaLimit, Result, SimulationError};
+use super::dual::{self, Dual};
+use crate::units::{self, Unit};
+use ndarray::Array2;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+/// Maximum number of compiled formulas kept by one engine
+const MAX_CACHED_FORMULAS: usize = 1024;
+
+/// Maximum number of script operations in one evaluation
+const MAX_OPERATIONS: u64 = 10_000;
+
+/// Operations between two checks of the execution time
+const PROGRESS_INTERVAL: u64 = 64;
+
+/// Offset between K and °C for the `TC` variable
+const CELSIUS_OFFSET: f64 = 273.15;
+
+/// Standard variables bound for every simulation formula, with their meaning
+pub const STANDARD_VARIABLES: [(&str, &str); 7] = [
+    ("T", "temperature in K"),
+    ("TC", "temperature in °C"),
+    ("r", "radial position in m"),
+    ("z", "axial position in m"),
+    ("t", "simulation time in s"),
+    ("torch", "index of the dominant torch (-1 without torches)"),
+    ("phi", "liquid fraction of the local material (0 solid, 1 liquid)"),
+];
+
+/// Values of the standard formula variables at one evaluation point
+///
+/// Variables that do not apply where a formula is evaluated (position for a
+/// material property, time at initialization) keep their zero defaults.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FormulaContext {
+    pub temperature: f64,
+    pub r: f64,
+    pub z: f64,
+    pub time: f64,
+    pub torch: Option<usize>,
+    pub liquid_fraction: f64,
+}
+
+impl FormulaContext {
+    /// Context with only the temperature set
+    pub fn new(temperature: f64) -> Self {
+        Self { temperature, r: 0.0, z: 0.0, time: 0.0, torch: None, liquid_fraction: 0.0 }
+    }
+    
+    /// Set the position (r, z) in meters
+    pub fn at(mut self, r: f64, z: f64) -> Self {
+        self.r = r;
+        self.z = z;
+        self
+    }
+    
+    /// Set the simulation time in seconds
+    pub fn with_time(mut self, time: f64) -> Self {
+        self.time = time;
+        self
+    }
+    
+    /// Set the dominant torch index
+    pub fn with_torch(mut self, torch: Option<usize>) -> Self {
+        self.torch = torch;
+        self
+    }
+    
+    /// Set the local liquid fraction
+    pub fn with_liquid_fraction(mut self, liquid_fraction: f64) -> Self {
+        self.liquid_fraction = liquid_fraction;
+        self
+    }
+}
+
+/// Formula compiled once to an AST and evaluated any number of times
+#[derive(Debug, Clone)]
+pub struct CompiledFormula {
+    source: String,
+    ast: Arc<rhai::AST>,
+    /// Result unit from a trailing `[unit]` annotation
+    unit: Option<Unit>,
+}
+
+impl CompiledFormula {
+    /// Formula text
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+    
+    /// Result unit, if the formula is annotated; results are converted to SI
+    pub fn unit(&self) -> Option<&Unit> {
+        self.unit.as_ref()
+    }
+}
+
 /// Formula engine for safe mathematical expression evaluation
+///
+/// Formulas are compiled on first use and the AST is cached by formula text,
+/// so repeated evaluation (per cell, per step) does not re-parse them.
 pub struct FormulaEngine {
     engine: rhai::Engine,
     scope: rhai::Scope<'static>,
+    compiled: HashMap<String, Arc<CompiledFormula>>,
+    /// Start of the running evaluation, read by the progress callback
+    started: Arc<Mutex<Instant>>,
     max_execution_time: Duration,
     max_memory_usage: usize,
 }
@@ -20,27 +120,49 @@ impl FormulaEngine {
     pub fn new() -> Self {
         Self::with_limits(
             Duration::from_millis(100), // 100ms max execution time
-            1024 * 1024,                // 1MB max memory usage
+            1024,                       // 1KB max per string, array or map
         )
     }
     
     /// Create a new formula engine with custom safety limits
+    ///
+    /// Evaluations are aborted mid-run when they exceed the execution time,
+    /// the operation limit or the memory budget. The budget caps strings at
+    /// `max_memory_usage` bytes and arrays and maps at as many values as fit
+    /// in it. The caps hold per value, so a formula holding several values can
+    /// use a multiple of the budget until the operation limit stops it. The
+    /// error then reports the limit as [`SimulationError::FormulaLimitExceeded`].
     pub fn with_limits(max_execution_time: Duration, max_memory_usage: usize) -> Self {
         let mut engine = rhai::Engine::new();
         
         // Configure engine for safety and sandboxing
-        engine.set_max_operations(10000);     // Limit operations
-        engine.set_max_modules(0);            // Disable module loading
-        engine.set_max_call_levels(10);       // Limit recursion
-        engine.set_max_string_size(1024);     // Limit string size
-        engine.set_max_array_size(100);       // Limit array size
-        engine.set_max_map_size(100);         // Limit map size
+        let max_items = (max_memory_usage / std::mem::size_of::<rhai::Dynamic>()).max(1);
+        engine.set_max_operations(MAX_OPERATIONS);                   // Limit operations
+        engine.set_max_modules(0);                                   // Disable module loading
+        engine.set_max_call_levels(10);                              // Limit recursion
+        engine.set_max_string_size(max_memory_usage.max(1));         // Limit string size
+        engine.set_max_array_size(max_items);                        // Limit array size
+        engine.set_max_map_size(max_items);                          // Limit map size
+        
+        // Terminate evaluations that run past the time limit
+        let started = Arc::new(Mutex::new(Instant::now()));
+        let start = Arc::clone(&started);
+        engine.on_progress(move |operations| {
+            if !operations.is_multiple_of(PROGRESS_INTERVAL) {
+                return None;
+            }
+            let elapsed = start.lock().unwrap_or_else(|e| e.into_inner()).elapsed();
+            (elapsed > max_execution_time).then_some(rhai::Dynamic::UNIT)
+        });
         
         // Disable potentially dangerous functions
         engine.disable_symbol("import");
         engine.disable_symbol("export");
         engine.disable_symbol("eval");
         
+        // Dual-number arithmetic for derivatives
+        dual::register(&mut engine);
+        
         // Add mathematical constants
         let mut scope = rhai::Scope::new();
         scope.push_constant("PI", std::f64::consts::PI);
@@ -55,12 +177,27 @@ impl FormulaEngine {
         scope.push_constant("AVOGADRO", 6.02214076e23);          // mol⁻¹
         scope.push_constant("GAS_CONSTANT", 8.314462618);        // J/(mol·K)
         
-        Self { 
+        let mut engine = Self { 
             engine, 
             scope,
+            compiled: HashMap::new(),
+            started,
             max_execution_time,
             max_memory_usage,
-        }
+        };
+        engine.bind(&FormulaContext::new(0.0));
+        engine
+    }
+    
+    /// Set the standard variables in the scope
+    fn bind(&mut self, context: &FormulaContext) {
+        self.scope.set_value("T", context.temperature);
+        self.scope.set_value("TC", context.temperature - CELSIUS_OFFSET);
+        self.scope.set_value("r", context.r);
+        self.scope.set_value("z", context.z);
+        self.scope.set_value("t", context.time);
+        self.scope.set_value("torch", context.torch.map_or(-1, |index| index as rhai::INT));
+        self.scope.set_value("phi", context.liquid_fraction);
     }
     
     /// Evaluate a formula with given temperature and optional variables
@@ -75,12 +212,16 @@ impl FormulaEngine {
         temperature: f64, 
         variables: &HashMap<String, f64>
     ) -> Result<f64> {
-        // Validate input parameters
-        if !temperature.is_finite() {
-            return Err(crate::errors::SimulationError::FormulaError {
-                formula: formula.to_string(),
-                error: "Temperature is not finite".to_string(),
-            });
+        let compiled = self.compile(formula)?;
+        self.evaluate_compiled(&compiled, temperature, variables)
+    }
+    
+    /// Compile a formula, reusing the cached AST for formula text seen before
+    ///
+    /// Cached formulas are shared, so a cache hit copies no formula text.
+    pub fn compile(&mut self, formula: &str) -> Result<Arc<CompiledFormula>> {
+        if let Some(compiled) = self.compiled.get(formula) {
+            return Ok(Arc::clone(compiled));
         }
         
         if formula.trim().is_empty() {
@@ -90,8 +231,44 @@ impl FormulaEngine {
             });
         }
         
-        // Set temperature variable
-        self.scope.set_value("T", temperature);
+        let (expression, annotation) = units::split_formula_unit(formula);
+        let unit = annotation
+            .map(Unit::parse)
+            .transpose()
+            .map_err(|e| crate::errors::SimulationError::FormulaError {
+                formula: formula.to_string(),
+                error: format!("Invalid result unit: {}", e),
+            })?;
+        let ast = self.engine.compile(expression).map_err(|e| crate::errors::SimulationError::FormulaError {
+            formula: formula.to_string(),
+            error: format!("Syntax error: {}", e),
+        })?;
+        let compiled = Arc::new(CompiledFormula { source: formula.to_string(), ast: Arc::new(ast), unit });
+        
+        if self.compiled.len() >= MAX_CACHED_FORMULAS {
+            self.compiled.clear();
+        }
+        self.compiled.insert(formula.to_string(), Arc::clone(&compiled));
+        Ok(compiled)
+    }
+    
+    /// Number of compiled formulas held in the cache
+    pub fn compiled_count(&self) -> usize {
+        self.compiled.len()
+    }
+    
+    /// Evaluate a compiled formula with temperature and additional variables
+    ///
+    /// The other standard variables are reset to their defaults; additional
+    /// variables may override them.
+    pub fn evaluate_compiled(
+        &mut self,
+        formula: &CompiledFormula,
+        temperature: f64,
+        variables: &HashMap<String, f64>,
+    ) -> Result<f64> {
+        self.check_context(formula, &FormulaContext::new(temperature))?;
+        self.bind(&FormulaContext::new(temperature));
         
         // Set additional variables
         for (name, value) in variables {
@@ -100,46 +277,188 @@ impl FormulaEngine {
             }
         }
         
-        // Execute with timeout
-        let start_time = Instant::now();
+        self.run(formula)
+    }
+    
+    /// Evaluate a compiled formula with the standard variables of a context
+    pub fn evaluate_in_context(&mut self, formula: &CompiledFormula, context: &FormulaContext) -> Result<f64> {
+        self.check_context(formula, context)?;
+        self.bind(context);
+        self.run(formula)
+    }
+    
+    /// Compile and evaluate a formula with the standard variables of a context
+    pub fn evaluate_formula_in_context(&mut self, formula: &str, context: &FormulaContext) -> Result<f64> {
+        let compiled = self.compile(formula)?;
+        self.evaluate_in_context(&compiled, context)
+    }
+    
+    /// Evaluate a formula and its derivative with respect to temperature
+    ///
+    /// Returns (f(T), df/dT), computed exactly with dual numbers; see
+    /// [`super::dual`].
+    pub fn evaluate_with_derivative(&mut self, formula: &str, temperature: f64) -> Result<(f64, f64)> {
+        let compiled = self.compile(formula)?;
+        self.evaluate_in_context_with_derivative(&compiled, &FormulaContext::new(temperature))
+    }
+    
+    /// Evaluate a compiled formula and its temperature derivative in a context
+    pub fn evaluate_in_context_with_derivative(
+        &mut self,
+        formula: &CompiledFormula,
+        context: &FormulaContext,
+    ) -> Result<(f64, f64)> {
+        self.check_context(formula, context)?;
+        self.bind(context);
+        self.scope.set_value("T", Dual::variable(context.temperature));
+        self.scope.set_value("TC", Dual::variable(context.temperature - CELSIUS_OFFSET));
         
-        // Evaluate formula with error handling
-        let result = self.engine.eval_with_scope::<f64>(&mut self.scope, formula);
+        let result = self.execute(formula)?;
+        let result = match result.clone().try_cast::<Dual>() {
+            Some(dual) => dual,
+            // Formulas independent of T (or branches that drop it) return plain floats
+            None => match result.try_cast::<f64>() {
+                Some(value) => Dual::constant(value),
+                None => {
+                    return Err(crate::errors::SimulationError::FormulaError {
+                        formula: formula.source.clone(),
+                        error: "Evaluation error: result is not a number".to_string(),
+                    })
+                }
+            },
+        };
         
-        // Check execution time
-        if start_time.elapsed() > self.max_execution_time {
+        let value = self.check_result(formula, result.value)?;
+        let result = Dual {
+            value,
+            derivative: formula.unit.map_or(result.derivative, |unit| result.derivative * unit.factor),
+        };
+        if !result.derivative.is_finite() {
             return Err(crate::errors::SimulationError::FormulaError {
-                formula: formula.to_string(),
-                error: format!("Formula execution timeout (>{:?})", self.max_execution_time),
+                formula: formula.source.clone(),
+                error: format!("Derivative is not finite: {}", result.derivative),
             });
         }
+        Ok((result.value, result.derivative))
+    }
+    
+    /// Reject non-finite context values before they reach a formula
+    fn check_context(&self, formula: &CompiledFormula, context: &FormulaContext) -> Result<()> {
+        let values = [
+            ("Temperature", context.temperature),
+            ("Position r", context.r),
+            ("Position z", context.z),
+            ("Time", context.time),
+            ("Liquid fraction", context.liquid_fraction),
+        ];
+        match values.iter().find(|(_, value)| !value.is_finite()) {
+            Some((name, _)) => Err(crate::errors::SimulationError::FormulaError {
+                formula: formula.source.clone(),
+                error: format!("{} is not finite", name),
+            }),
+            None => Ok(()),
+        }
+    }
+    
+    /// Evaluate a compiled formula in the current scope
+    fn run(&mut self, formula: &CompiledFormula) -> Result<f64> {
+        let result = self.execute(formula)?;
+        match result.try_cast::<f64>() {
+            Some(value) => self.check_result(formula, value),
+            None => Err(crate::errors::SimulationError::FormulaError {
+                formula: formula.source.clone(),
+                error: "Evaluation error: result is not a number".to_string(),
+            }),
+        }
+    }
+    
+    /// Execute a compiled formula in the current scope within the resource limits
+    fn execute(&mut self, formula: &CompiledFormula) -> Result<rhai::Dynamic> {
+        let start_time = Instant::now();
+        *self.started.lock().unwrap_or_else(|e| e.into_inner()) = start_time;
         
-        match result {
-            Ok(value) => {
-                if value.is_finite() {
-                    // Apply safety limits to result
-                    if value.abs() > 1e15 {
-                        Err(crate::errors::SimulationError::FormulaError {
-                            formula: formula.to_string(),
-                            error: format!("Result too large: {}", value),
-                        })
-                    } else {
-                        Ok(value)
-                    }
-                } else {
-                    Err(crate::errors::SimulationError::FormulaError {
-                        formula: formula.to_string(),
-                        error: format!("Result is not finite: {}", value),
-                    })
-                }
+        // Evaluate formula with error handling, dropping any variables it declares
+        let scope_size = self.scope.len();
+        let result = self.engine.eval_ast_with_scope::<rhai::Dynamic>(&mut self.scope, &formula.ast);
+        self.scope.rewind(scope_size);
+        let result = result.map_err(|e| self.evaluation_error(formula, &e))?;
+        
+        // A single slow function call is not interrupted by the progress callback
+        if start_time.elapsed() > self.max_execution_time {
+            return Err(self.limit_error(formula, FormulaLimit::Timeout, "script terminated"));
+        }
+        Ok(result)
+    }
+    
+    /// Classify a Rhai error, separating exceeded resource limits
+    fn evaluation_error(&self, formula: &CompiledFormula, error: &rhai::EvalAltResult) -> SimulationError {
+        match error.unwrap_inner() {
+            rhai::EvalAltResult::ErrorTerminated(..) => self.limit_error(formula, FormulaLimit::Timeout, "script terminated"),
+            rhai::EvalAltResult::ErrorTooManyOperations(..) => self.limit_error(formula, FormulaLimit::Operations, "too many operations"),
+            rhai::EvalAltResult::ErrorDataTooLarge(data, ..) => {
+                self.limit_error(formula, FormulaLimit::Memory, &format!("{} too large", data))
             }
-            Err(e) => Err(crate::errors::SimulationError::FormulaError {
-                formula: formula.to_string(),
-                error: format!("Evaluation error: {}", e),
-            }),
+            _ => SimulationError::FormulaError {
+                formula: formula.source.clone(),
+                error: format!("Evaluation error: {}", error),
+            },
         }
     }
     
+    /// Error for an evaluation aborted by a resource limit
+    fn limit_error(&self, formula: &CompiledFormula, limit: FormulaLimit, reason: &str) -> SimulationError {
+        let bound = match limit {
+            FormulaLimit::Timeout => format!("{:?}", self.max_execution_time),
+            FormulaLimit::Operations => format!("{} operations", MAX_OPERATIONS),
+            FormulaLimit::Memory => format!("{} bytes", self.max_memory_usage),
+        };
+        SimulationError::FormulaLimitExceeded {
+            formula: formula.source.clone(),
+            limit,
+            details: format!("{} (limit {})", reason, bound),
+        }
+    }
+    
+    /// Convert a formula result to SI and apply the finiteness and magnitude limits
+    fn check_result(&self, formula: &CompiledFormula, value: f64) -> Result<f64> {
+        let value = formula.unit.map_or(value, |unit| unit.to_si(value));
+        if !value.is_finite() {
+            return Err(crate::errors::SimulationError::FormulaError {
+                formula: formula.source.clone(),
+                error: format!("Result is not finite: {}", value),
+            });
+        }
+        // Apply safety limits to result
+        if value.abs() > 1e15 {
+            return Err(crate::errors::SimulationError::FormulaError {
+                formula: formula.source.clone(),
+                error: format!("Result too large: {}", value),
+            });
+        }
+        Ok(value)
+    }
+    
+    /// Evaluate one formula at every temperature of a slice
+    pub fn evaluate_batch(&mut self, formula: &str, temperatures: &[f64]) -> Result<Vec<f64>> {
+        let compiled = self.compile(formula)?;
+        let variables = HashMap::new();
+        temperatures
+            .iter()
+            .map(|&temperature| self.evaluate_compiled(&compiled, temperature, &variables))
+            .collect()
+    }
+    
+    /// Evaluate one formula at every node of a temperature field
+    pub fn evaluate_array(&mut self, formula: &str, temperatures: &Array2<f64>) -> Result<Array2<f64>> {
+        let compiled = self.compile(formula)?;
+        let variables = HashMap::new();
+        let mut values = Array2::zeros(temperatures.raw_dim());
+        for (value, &temperature) in values.iter_mut().zip(temperatures.iter()) {
+            *value = self.evaluate_compiled(&compiled, temperature, &variables)?;
+        }
+        Ok(values)
+    }
+    
     /// Validate formula syntax and safety
     pub fn validate_formula(&mut self, formula: &str) -> Result<()> {
         // Check for empty formula
@@ -174,27 +493,16 @@ impl FormulaEngine {
             }
         }
         
-        // Try to compile the formula
-        match self.engine.compile(formula) {
-            Ok(_) => {
-                // Test evaluation with safe values
-                let test_result = self.evaluate_formula_with_vars(
-                    formula, 
-                    300.0, // Safe test temperature
-                    &HashMap::new()
-                );
-                
-                match test_result {
-                    Ok(_) => Ok(()),
-                    Err(e) => Err(crate::errors::SimulationError::FormulaError {
-                        formula: formula.to_string(),
-                        error: format!("Validation test failed: {}", e),
-                    }),
-                }
-            }
+        // Compile the formula (syntax errors are reported here)
+        let compiled = self.compile(formula)?;
+        
+        // Test evaluation with safe values
+        match self.evaluate_compiled(&compiled, 300.0, &HashMap::new()) {
+            Ok(_) => Ok(()),
+            Err(e) if e.formula_limit().is_some() => Err(e),
             Err(e) => Err(crate::errors::SimulationError::FormulaError {
                 formula: formula.to_string(),
-                error: format!("Syntax error: {}", e),
+                error: format!("Validation test failed: {}", e),
             }),
         }
     }
@@ -359,6 +667,38 @@ mod tests {
         assert!(engine.evaluate_formula("sqrt(-1.0)", 300.0).is_err());
     }
     
+    #[test]
+    fn test_resource_limits_abort_evaluation() {
+        let mut engine = FormulaEngine::new();
+        let runaway = "let x = 0.0; loop { x += 1.0; }";
+        let error = engine.evaluate_formula(runaway, 300.0).unwrap_err();
+        assert_eq!(error.formula_limit(), Some(FormulaLimit::Operations));
+        
+        let error = engine.evaluate_formula("let s = \"x\"; loop { s += s; }", 300.0).unwrap_err();
+        assert_eq!(error.formula_limit(), Some(FormulaLimit::Memory));
+        assert!(engine.evaluate_formula("sqrt(-1.0)", 300.0).unwrap_err().formula_limit().is_none());
+        
+        // Cloning a large array is stopped at the first oversized value
+        let clones = "let a = []; a.pad(65536, 0.0); let b = a; let c = a; let d = a; a.len().to_float()";
+        let error = engine.evaluate_formula(clones, 300.0).unwrap_err();
+        assert_eq!(error.formula_limit(), Some(FormulaLimit::Memory));
+        let error = engine.evaluate_formula("let a = []; loop { a.push(1.0); }", 300.0).unwrap_err();
+        assert_eq!(error.formula_limit(), Some(FormulaLimit::Memory));
+        let items = 1024 / std::mem::size_of::<rhai::Dynamic>();
+        let filled = format!("let a = []; a.pad({}, 1.0); let b = a; b.len().to_float()", items);
+        assert_eq!(engine.evaluate_formula(&filled, 300.0).unwrap(), items as f64);
+        
+        // The caps scale with the memory budget
+        let long_string = "let s = \"x\"; for i in 0..11 { s += s; } s.len().to_float()";
+        assert_eq!(engine.evaluate_formula(long_string, 300.0).unwrap_err().formula_limit(), Some(FormulaLimit::Memory));
+        let mut large = FormulaEngine::with_limits(Duration::from_millis(100), 4096);
+        assert_eq!(large.evaluate_formula(long_string, 300.0).unwrap(), 2048.0);
+        
+        let mut engine = FormulaEngine::with_limits(Duration::from_nanos(1), 1024 * 1024);
+        let error = engine.evaluate_formula(runaway, 300.0).unwrap_err();
+        assert_eq!(error.formula_limit(), Some(FormulaLimit::Timeout));
+    }
+    
     #[test]
     fn test_formula_with_variables() {
         let mut engine = FormulaEngine::new();
@@ -377,6 +717,103 @@ mod tests {
         assert!(result > 0.0 && result < 1000.0);
     }
     
+    #[test]
+    fn test_standard_variables() {
+        let mut engine = FormulaEngine::new();
+        let formula = "T + 100.0 * r + 10.0 * z + t + 1000.0 * phi + if torch < 0 { 0.5 } else { torch * 1.0 }";
+        let context = FormulaContext::new(300.0).at(0.5, 2.0).with_time(3.0).with_liquid_fraction(0.25).with_torch(Some(2));
+        let value = engine.evaluate_formula_in_context(formula, &context).unwrap();
+        assert!((value - (300.0 + 50.0 + 20.0 + 3.0 + 250.0 + 2.0)).abs() < 1e-9);
+        
+        // Plain temperature evaluation resets the other variables to their defaults
+        assert!((engine.evaluate_formula(formula, 300.0).unwrap() - 300.5).abs() < 1e-9);
+        assert!(engine.validate_formula("T * (1.0 + r + z + t + phi)").is_ok());
+        assert!(engine.evaluate_formula_in_context("T", &FormulaContext::new(300.0).with_time(f64::NAN)).is_err());
+    }
+    
+    #[test]
+    fn test_evaluate_with_derivative() {
+        let mut engine = FormulaEngine::new();
+        
+        let formula = "1.0 / (1.6e-7 * (1.0 + 0.004 * (T - 293.15)))";
+        let (value, derivative) = engine.evaluate_with_derivative(formula, 800.0).unwrap();
+        assert_eq!(value, engine.evaluate_formula(formula, 800.0).unwrap());
+        let h = 1e-3;
+        let numerical = (engine.evaluate_formula(formula, 800.0 + h).unwrap()
+            - engine.evaluate_formula(formula, 800.0 - h).unwrap()) / (2.0 * h);
+        assert!((derivative - numerical).abs() < 1e-6 * numerical.abs());
+        
+        // Every function available in value mode differentiates
+        let formula = "asin(T / 2000.0) + acos(T / 4000.0) + log(T, 2.0)";
+        let (value, _) = engine.evaluate_with_derivative(formula, 800.0).unwrap();
+        assert_eq!(value, engine.evaluate_formula(formula, 800.0).unwrap());
+        
+        // Constants and T-free branches have zero derivative
+        assert_eq!(engine.evaluate_with_derivative("PI * 2.0", 500.0).unwrap(), (2.0 * std::f64::consts::PI, 0.0));
+        assert_eq!(engine.evaluate_with_derivative("if T > 1000.0 { 5.0 } else { T / 100.0 }", 1500.0).unwrap().1, 0.0);
+        assert!((engine.evaluate_with_derivative("if T > 1000.0 { 5.0 } else { T / 100.0 }", 500.0).unwrap().1 - 0.01).abs() < 1e-15);
+        
+        // Plain evaluation still sees a float temperature afterwards
+        assert_eq!(engine.evaluate_formula("T * 2.0", 100.0).unwrap(), 200.0);
+        assert!(engine.evaluate_with_derivative("sqrt(T - 1000.0)", 500.0).is_err());
+    }
+    
+    #[test]
+    fn test_formula_result_units() {
+        let mut engine = FormulaEngine::new();
+        
+        // Celsius temperature and kW/m³ results convert to SI
+        assert!((engine.evaluate_formula("TC + 10.0 [degC]", 373.15).unwrap() - 383.15).abs() < 1e-9);
+        assert!((engine.evaluate_formula("2.5 [kW/m^3]", 300.0).unwrap() - 2500.0).abs() < 1e-9);
+        let (value, derivative) = engine.evaluate_with_derivative("0.05 * T [kW/(m*K)]", 1000.0).unwrap();
+        assert!((value - 50000.0).abs() < 1e-9 && (derivative - 50.0).abs() < 1e-12);
+        assert!(engine.compile("T [furlongs]").is_err());
+    }
+    
+    #[test]
+    fn test_compiled_formula_cache() {
+        let mut engine = FormulaEngine::new();
+        let formula = "50.0 * (1.0 - 0.0003 * (T - 273.15))";
+        
+        let compiled = engine.compile(formula).unwrap();
+        assert_eq!(compiled.source(), formula);
+        for t in [300.0, 600.0, 900.0] {
+            let direct = engine.evaluate_compiled(&compiled, t, &HashMap::new()).unwrap();
+            assert_eq!(engine.evaluate_formula(formula, t).unwrap(), direct);
+        }
+        assert_eq!(engine.compiled_count(), 1);
+        assert!(Arc::ptr_eq(&compiled, &engine.compile(formula).unwrap()));
+        
+        // Variables declared by a formula do not accumulate between evaluations
+        let local = "let dt = T - 300.0; 2.0 * dt";
+        assert_eq!(engine.evaluate_formula(local, 310.0).unwrap(), 20.0);
+        assert_eq!(engine.evaluate_formula(local, 320.0).unwrap(), 40.0);
+        assert_eq!(engine.compiled_count(), 2);
+        
+        // Syntax errors are reported without caching
+        assert!(engine.compile("2.0 +").is_err());
+        assert_eq!(engine.compiled_count(), 2);
+    }
+    
+    #[test]
+    fn test_batch_evaluation() {
+        let mut engine = FormulaEngine::new();
+        
+        let values = engine.evaluate_batch("460.0 + 0.27 * (T - 273.15)", &[273.15, 373.15, 1273.15]).unwrap();
+        assert_eq!(values.len(), 3);
+        assert!((values[1] - 487.0).abs() < 1e-9);
+        assert!((values[2] - 730.0).abs() < 1e-9);
+        
+        let temperatures = Array2::from_shape_fn((4, 3), |(i, j)| 300.0 + 100.0 * i as f64 + 10.0 * j as f64);
+        let field = engine.evaluate_array("T / 100.0", &temperatures).unwrap();
+        assert_eq!(field.dim(), (4, 3));
+        assert_eq!(field[[2, 1]], 5.1);
+        
+        // One failing node fails the batch
+        assert!(engine.evaluate_batch("sqrt(T - 400.0)", &[500.0, 300.0]).is_err());
+        assert!(engine.evaluate_array("T +", &temperatures).is_err());
+    }
+    
     #[test]
     fn test_physical_constants() {
         let mut engine = FormulaEngine::new();
@@ -7,6 +7,7 @@
 //! 
 //! # Core Components
 //! 
+//! - [`dual`] - Dual numbers for derivatives of formulas
 //! - [`engine`] - Core formula engine using Rhai scripting language
 //! - [`integration`] - Integration layer for connecting formulas with simulation
 //! 
@@ -29,6 +30,7 @@
 use crate::errors::Result;
 
 // Core formula modules
+pub mod dual;
 pub mod engine;
 pub mod integration;
 
@@ -36,7 +38,8 @@ pub mod integration;
 pub type FormulaResult = Result<f64>;
 
 // Re-export the main types from submodules
-pub use engine::FormulaEngine;
+pub use dual::Dual;
+pub use engine::{CompiledFormula, FormulaContext, FormulaEngine, STANDARD_VARIABLES};
 pub use integration::FormulaManager;
 
 
@@ -22,6 +22,7 @@
 //! - [`simulation`] - Core simulation engine and physics models
 //! - [`formula`] - Formula engine for custom mathematical expressions
 //! - [`errors`] - Comprehensive error handling system
+//! - [`units`] - Physical units for configuration quantities and formula results
 //! 
 //! # Example Usage
 //! 
@@ -39,6 +40,7 @@
 pub mod simulation;
 pub mod formula;
 pub mod errors;
+pub mod units;
 
 // Re-export commonly used types
 pub use errors::{SimulationError, Result};
@@ -9,11 +9,20 @@ mod absolute_distance_tests {
     use crate::simulation::{
         solver::{HeatSolver, SolverMethod},
         mesh::CylindricalMesh,
-        materials::MaterialLibrary,
+        materials::{Material, MaterialLibrary},
         physics::{PlasmaPhysics, PlasmaTorch, BoundaryConditions},
+        property_table::FormulaTabulation,
     };
+    use crate::formula::engine::FormulaEngine;
     use ndarray::Array2;
     
+    /// Library material with its formulas tabulated, as the simulation engine runs it
+    fn tabulated_material(name: &str) -> Material {
+        let mut material = MaterialLibrary::get_material(name).unwrap();
+        material.tabulate_formulas(&FormulaTabulation::default(), &mut FormulaEngine::new());
+        material
+    }
+    
     /// Helper function to calculate the radial distance where temperature drops to a threshold
     /// Returns the maximum radial distance from torch where temp > threshold
     fn calculate_heat_spread_distance(
@@ -82,7 +91,7 @@ mod absolute_distance_tests {
         let threshold_temp = 305.0; // Temperature threshold for measuring spread (5K above ambient)
         
         // Material: Steel with thermal diffusivity α ≈ 1.2×10⁻⁵ m²/s
-        let material = MaterialLibrary::get_material("Carbon Steel").unwrap();
+        let material = tabulated_material("Carbon Steel");
         let bc = BoundaryConditions::default();
         
         println!("Material: Carbon Steel");
@@ -217,7 +226,7 @@ mod absolute_distance_tests {
         // Verify that Steel has the expected thermal diffusivity
         println!("\n=== Verifying Steel Thermal Diffusivity ===\n");
         
-        let material = MaterialLibrary::get_material("Carbon Steel").unwrap();
+        let material = tabulated_material("Carbon Steel");
         let torch = PlasmaTorch::new((0.0, 0.5), 100.0, 0.8, 0.1).unwrap();
         let bc = BoundaryConditions::default();
         let physics = PlasmaPhysics::new(vec![torch], material, bc).unwrap();
@@ -226,7 +235,7 @@ mod absolute_distance_tests {
         let reference_temp = 500.0;
         let k = physics.get_thermal_conductivity(reference_temp);
         let cp = physics.get_specific_heat(reference_temp);
-        let rho = physics.get_density();
+        let rho = physics.get_density(reference_temp);
         
         let alpha = k / (rho * cp);
         
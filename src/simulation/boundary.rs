@@ -0,0 +1,516 @@
+//! Boundary condition definitions for the furnace enclosure
+//!
+//! This module describes the thermal conditions applied on the outer wall,
+//! top and bottom of the cylindrical domain (the axis is always symmetric).
+//! Each boundary carries a default condition that can be overridden on
+//! segments along its length, so a water-cooled roof, an insulated hearth
+//! and an open tap hole can be modelled in the same run.
+
+use crate::errors::{Result, SimulationError};
+use crate::formula::engine::{FormulaContext, FormulaEngine};
+use crate::units::Dimension;
+
+/// Stefan-Boltzmann constant in W/(m²·K⁴)
+const STEFAN_BOLTZMANN: f64 = 5.67e-8;
+
+/// Quantity produced by a formula boundary condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum FormulaQuantity {
+    /// Formula gives the surface temperature in K
+    Temperature,
+    /// Formula gives the heat flux into the domain in W/m²
+    HeatFlux,
+}
+
+/// Thermal condition applied on a boundary segment
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum BoundaryCondition {
+    /// No heat flow across the boundary (∂T/∂n = 0)
+    Adiabatic,
+    /// Prescribed surface temperature in K
+    FixedTemperature { temperature: f64 },
+    /// Prescribed heat flux into the domain in W/m² (negative values extract heat)
+    FixedFlux { heat_flux: f64 },
+    /// Convective exchange: q = h * (T - T_amb)
+    Convection {
+        heat_transfer_coefficient: f64,
+        ambient_temperature: f64,
+    },
+    /// Radiative exchange: q = ε * σ * (T⁴ - T_amb⁴)
+    Radiation {
+        emissivity: f64,
+        ambient_temperature: f64,
+    },
+    /// Convection and radiation to the same ambient
+    Combined {
+        heat_transfer_coefficient: f64,
+        emissivity: f64,
+        ambient_temperature: f64,
+    },
+    /// Rhai formula in the standard variables (T, r, z, t, torch, phi) giving
+    /// a temperature or a heat flux
+    Formula {
+        expression: String,
+        quantity: FormulaQuantity,
+    },
+    /// Water-cooled jacket: coolant enters at the start of the segment and flows
+    /// along the boundary (upwards on the wall, outwards on top and bottom),
+    /// heating up as it removes q = h * (T - T_coolant(s))
+    CooledJacket {
+        /// Coolant inlet temperature in K
+        inlet_temperature: f64,
+        /// Coolant mass flow rate in kg/s
+        mass_flow_rate: f64,
+        /// Coolant specific heat in J/(kg·K)
+        specific_heat: f64,
+        /// Wall-to-coolant heat transfer coefficient in W/(m²·K)
+        heat_transfer_coefficient: f64,
+    },
+}
+
+impl BoundaryCondition {
+    /// Calculate the surface temperature from a heat balance with the interior
+    ///
+    /// Solves k * (T_interior - T_wall) / d = q_out(T_wall) for T_wall, where d
+    /// is the distance to the interior node. Radiation is linearized around the
+    /// current surface temperature so the update stays stable for large losses.
+    /// An external flux (e.g. from enclosure radiation) is added to the balance
+    /// for every condition that does not prescribe the temperature directly.
+    /// A cooled jacket exchanges heat with coolant at the inlet temperature;
+    /// use [`BoundaryCondition::with_coolant_temperature`] to apply the local
+    /// coolant temperature.
+    ///
+    /// # Arguments
+    /// * `t_interior` - Temperature of the adjacent interior node in K
+    /// * `t_surface` - Current surface temperature in K
+    /// * `conductivity` - Thermal conductivity at the surface in W/(m·K)
+    /// * `distance` - Distance to the interior node in meters
+    /// * `external_flux` - Additional heat flux into the domain in W/m²
+    /// * `context` - Formula variables at the surface node; `T` is taken as `t_surface`
+    /// * `formula_engine` - Engine used by formula conditions
+    #[allow(clippy::too_many_arguments)]
+    pub fn surface_temperature(
+        &self,
+        t_interior: f64,
+        t_surface: f64,
+        conductivity: f64,
+        distance: f64,
+        external_flux: f64,
+        context: &FormulaContext,
+        formula_engine: &mut FormulaEngine,
+    ) -> Result<f64> {
+        let conductance = conductivity / distance;
+
+        let q = external_flux;
+
+        let new_temp = match self {
+            BoundaryCondition::Adiabatic => t_interior + q / conductance,
+            BoundaryCondition::FixedTemperature { temperature } => *temperature,
+            BoundaryCondition::FixedFlux { heat_flux } => t_interior + (heat_flux + q) / conductance,
+            BoundaryCondition::Convection { heat_transfer_coefficient, ambient_temperature } => {
+                let h = *heat_transfer_coefficient;
+                (conductance * t_interior + h * ambient_temperature + q) / (conductance + h)
+            }
+            BoundaryCondition::Radiation { emissivity, ambient_temperature } => {
+                let h_rad = radiative_coefficient(*emissivity, t_surface, *ambient_temperature);
+                (conductance * t_interior + h_rad * ambient_temperature + q) / (conductance + h_rad)
+            }
+            BoundaryCondition::Combined { heat_transfer_coefficient, emissivity, ambient_temperature } => {
+                let h = heat_transfer_coefficient
+                    + radiative_coefficient(*emissivity, t_surface, *ambient_temperature);
+                (conductance * t_interior + h * ambient_temperature + q) / (conductance + h)
+            }
+            BoundaryCondition::CooledJacket { inlet_temperature, heat_transfer_coefficient, .. } => {
+                let h = *heat_transfer_coefficient;
+                (conductance * t_interior + h * inlet_temperature + q) / (conductance + h)
+            }
+            BoundaryCondition::Formula { expression, quantity } => {
+                let context = FormulaContext { temperature: t_surface, ..*context };
+                let value = formula_engine.evaluate_formula_in_context(expression, &context)?;
+                match quantity {
+                    FormulaQuantity::Temperature => value,
+                    FormulaQuantity::HeatFlux => t_interior + (value + q) / conductance,
+                }
+            }
+        };
+
+        if !new_temp.is_finite() || new_temp <= 0.0 {
+            return Err(SimulationError::PhysicsError {
+                operation: "boundary condition".to_string(),
+                details: format!("Non-physical surface temperature {} from {:?}", new_temp, self),
+            });
+        }
+
+        Ok(new_temp)
+    }
+
+    /// Validate condition parameters
+    pub fn validate(&self, name: &str) -> Result<()> {
+        use crate::errors::validation::{validate_positive, validate_range};
+
+        match self {
+            BoundaryCondition::Adiabatic => Ok(()),
+            BoundaryCondition::FixedTemperature { temperature } => {
+                validate_positive(*temperature, &format!("{} temperature", name))
+            }
+            BoundaryCondition::FixedFlux { heat_flux } => {
+                if heat_flux.is_finite() {
+                    Ok(())
+                } else {
+                    Err(SimulationError::InvalidParameter {
+                        parameter: format!("{} heat flux", name),
+                        value: heat_flux.to_string(),
+                        range: "finite value".to_string(),
+                    })
+                }
+            }
+            BoundaryCondition::Convection { heat_transfer_coefficient, ambient_temperature } => {
+                validate_range(*heat_transfer_coefficient, 0.0, 1.0e6, &format!("{} heat transfer coefficient", name))?;
+                validate_positive(*ambient_temperature, &format!("{} ambient temperature", name))
+            }
+            BoundaryCondition::Radiation { emissivity, ambient_temperature } => {
+                validate_range(*emissivity, 0.0, 1.0, &format!("{} emissivity", name))?;
+                validate_positive(*ambient_temperature, &format!("{} ambient temperature", name))
+            }
+            BoundaryCondition::Combined { heat_transfer_coefficient, emissivity, ambient_temperature } => {
+                validate_range(*heat_transfer_coefficient, 0.0, 1.0e6, &format!("{} heat transfer coefficient", name))?;
+                validate_range(*emissivity, 0.0, 1.0, &format!("{} emissivity", name))?;
+                validate_positive(*ambient_temperature, &format!("{} ambient temperature", name))
+            }
+            BoundaryCondition::Formula { expression, quantity } => {
+                crate::errors::validation::validate_non_empty_string(expression, &format!("{} formula", name))?;
+                let (dimension, what) = match quantity {
+                    FormulaQuantity::Temperature => (Dimension::TEMPERATURE, "surface temperature"),
+                    FormulaQuantity::HeatFlux => (Dimension::HEAT_FLUX, "heat flux"),
+                };
+                crate::units::check_formula_dimension(expression, dimension, &format!("{} {}", name, what))
+            }
+            BoundaryCondition::CooledJacket { inlet_temperature, mass_flow_rate, specific_heat, heat_transfer_coefficient } => {
+                validate_positive(*inlet_temperature, &format!("{} coolant inlet temperature", name))?;
+                validate_positive(*mass_flow_rate, &format!("{} coolant mass flow rate", name))?;
+                validate_positive(*specific_heat, &format!("{} coolant specific heat", name))?;
+                validate_range(*heat_transfer_coefficient, 0.0, 1.0e6, &format!("{} heat transfer coefficient", name))
+            }
+        }
+    }
+    
+    /// Split of the heat lost through the surface into (convective, radiative)
+    /// fractions, using the linearized radiative coefficient at `t_surface`
+    ///
+    /// Returns `None` for conditions that do not exchange heat with an ambient.
+    pub fn loss_fractions(&self, t_surface: f64) -> Option<(f64, f64)> {
+        match self {
+            BoundaryCondition::Convection { .. } | BoundaryCondition::CooledJacket { .. } => Some((1.0, 0.0)),
+            BoundaryCondition::Radiation { .. } => Some((0.0, 1.0)),
+            BoundaryCondition::Combined { heat_transfer_coefficient, emissivity, ambient_temperature } => {
+                let h_rad = radiative_coefficient(*emissivity, t_surface, *ambient_temperature);
+                let h_total = heat_transfer_coefficient + h_rad;
+                if h_total > 0.0 {
+                    Some((heat_transfer_coefficient / h_total, h_rad / h_total))
+                } else {
+                    Some((1.0, 0.0))
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Replace the coolant inlet temperature of a cooled jacket by the local
+    /// coolant temperature (other conditions are returned unchanged)
+    pub fn with_coolant_temperature(&self, coolant_temperature: f64) -> Self {
+        match self {
+            BoundaryCondition::CooledJacket { mass_flow_rate, specific_heat, heat_transfer_coefficient, .. } => {
+                BoundaryCondition::CooledJacket {
+                    inlet_temperature: coolant_temperature,
+                    mass_flow_rate: *mass_flow_rate,
+                    specific_heat: *specific_heat,
+                    heat_transfer_coefficient: *heat_transfer_coefficient,
+                }
+            }
+            other => other.clone(),
+        }
+    }
+
+    /// Replace the emissivity of a radiative condition by a local value
+    /// (other conditions are returned unchanged)
+    pub fn with_emissivity(&self, emissivity: f64) -> Self {
+        match self {
+            BoundaryCondition::Radiation { ambient_temperature, .. } => {
+                BoundaryCondition::Radiation { emissivity, ambient_temperature: *ambient_temperature }
+            }
+            BoundaryCondition::Combined { heat_transfer_coefficient, ambient_temperature, .. } => BoundaryCondition::Combined {
+                heat_transfer_coefficient: *heat_transfer_coefficient,
+                emissivity,
+                ambient_temperature: *ambient_temperature,
+            },
+            other => other.clone(),
+        }
+    }
+}
+
+/// Coolant energy balance of one cooled jacket
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CoolantReport {
+    /// Boundary carrying the jacket ("outer wall", "top" or "bottom")
+    pub boundary: String,
+    /// Segment index of the jacket (None for the boundary default)
+    pub segment: Option<usize>,
+    /// Coolant inlet temperature in K
+    pub inlet_temperature: f64,
+    /// Coolant outlet temperature in K
+    pub outlet_temperature: f64,
+    /// Heat removed by the coolant in W
+    pub heat_removed: f64,
+}
+
+/// Coolant marched along a jacket, node by node in the flow direction
+#[derive(Debug, Clone)]
+pub struct CoolantMarch {
+    /// Coolant capacity rate ṁ * cp in W/K
+    capacity_rate: f64,
+    heat_transfer_coefficient: f64,
+    /// Coolant temperature at the current position in K
+    temperature: f64,
+    pub report: CoolantReport,
+}
+
+impl CoolantMarch {
+    /// Start at the inlet of a cooled jacket (None for other conditions)
+    pub fn start(condition: &BoundaryCondition, boundary: &str, segment: Option<usize>) -> Option<Self> {
+        match condition {
+            BoundaryCondition::CooledJacket { inlet_temperature, mass_flow_rate, specific_heat, heat_transfer_coefficient } => {
+                Some(Self {
+                    capacity_rate: mass_flow_rate * specific_heat,
+                    heat_transfer_coefficient: *heat_transfer_coefficient,
+                    temperature: *inlet_temperature,
+                    report: CoolantReport {
+                        boundary: boundary.to_string(),
+                        segment,
+                        inlet_temperature: *inlet_temperature,
+                        outlet_temperature: *inlet_temperature,
+                        heat_removed: 0.0,
+                    },
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Pass a wall patch of `area` (m²) at `wall_temperature` (K)
+    ///
+    /// Returns the mean coolant temperature over the patch. The coolant rise is
+    /// taken implicitly so a large patch cannot heat the coolant past the wall.
+    pub fn advance(&mut self, wall_temperature: f64, area: f64) -> f64 {
+        let conductance = self.heat_transfer_coefficient * area;
+        let rise = conductance * (wall_temperature - self.temperature) / (self.capacity_rate + 0.5 * conductance);
+        let local = self.temperature + 0.5 * rise;
+
+        self.temperature += rise;
+        self.report.outlet_temperature = self.temperature;
+        self.report.heat_removed += self.capacity_rate * rise;
+        local
+    }
+}
+
+/// Linearized radiative heat transfer coefficient: ε * σ * (T² + T_amb²) * (T + T_amb)
+fn radiative_coefficient(emissivity: f64, t_surface: f64, t_ambient: f64) -> f64 {
+    emissivity * STEFAN_BOLTZMANN * (t_surface * t_surface + t_ambient * t_ambient) * (t_surface + t_ambient)
+}
+
+/// Condition applied on part of a boundary
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BoundarySegment {
+    /// Segment start along the boundary in meters (z for the wall, r for top/bottom)
+    pub start: f64,
+    /// Segment end along the boundary in meters
+    pub end: f64,
+    pub condition: BoundaryCondition,
+}
+
+impl BoundarySegment {
+    /// Check if a boundary coordinate falls within this segment
+    pub fn contains(&self, coordinate: f64) -> bool {
+        coordinate >= self.start && coordinate <= self.end
+    }
+}
+
+/// Complete specification of a single boundary
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BoundarySpec {
+    /// Condition used where no segment applies
+    pub default: BoundaryCondition,
+    /// Segment overrides; later segments take precedence where they overlap
+    #[serde(default)]
+    pub segments: Vec<BoundarySegment>,
+}
+
+impl BoundarySpec {
+    /// Create a boundary with the same condition everywhere
+    pub fn uniform(condition: BoundaryCondition) -> Self {
+        Self {
+            default: condition,
+            segments: Vec::new(),
+        }
+    }
+
+    /// Add a segment override between `start` and `end`
+    pub fn with_segment(mut self, start: f64, end: f64, condition: BoundaryCondition) -> Self {
+        self.segments.push(BoundarySegment { start, end, condition });
+        self
+    }
+
+    /// Get the condition applied at a coordinate along the boundary
+    pub fn condition_at(&self, coordinate: f64) -> &BoundaryCondition {
+        match self.segment_index_at(coordinate) {
+            Some(index) => &self.segments[index].condition,
+            None => &self.default,
+        }
+    }
+    
+    /// Index of the segment applied at a coordinate (None where the default applies)
+    pub fn segment_index_at(&self, coordinate: f64) -> Option<usize> {
+        self.segments.iter().rposition(|segment| segment.contains(coordinate))
+    }
+
+    /// Validate the default condition and all segments
+    pub fn validate(&self, name: &str) -> Result<()> {
+        self.default.validate(name)?;
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            let segment_name = format!("{} segment[{}]", name, i);
+            if !(segment.start.is_finite() && segment.end.is_finite()) || segment.end < segment.start {
+                return Err(SimulationError::InvalidParameter {
+                    parameter: format!("{} range", segment_name),
+                    value: format!("({}, {})", segment.start, segment.end),
+                    range: "finite start ≤ end".to_string(),
+                });
+            }
+            segment.condition.validate(&segment_name)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Boundary specifications for every non-axis boundary of the domain
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BoundarySet {
+    pub outer_wall: BoundarySpec,
+    pub top: BoundarySpec,
+    pub bottom: BoundarySpec,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_lookup() {
+        let spec = BoundarySpec::uniform(BoundaryCondition::Adiabatic)
+            .with_segment(0.0, 0.5, BoundaryCondition::FixedTemperature { temperature: 350.0 })
+            .with_segment(0.4, 0.6, BoundaryCondition::FixedFlux { heat_flux: -100.0 });
+
+        assert!(matches!(spec.condition_at(0.2), BoundaryCondition::FixedTemperature { .. }));
+        assert!(matches!(spec.condition_at(0.45), BoundaryCondition::FixedFlux { .. }));
+        assert!(matches!(spec.condition_at(0.9), BoundaryCondition::Adiabatic));
+    }
+
+    #[test]
+    fn test_surface_temperature_balances() {
+        let mut engine = FormulaEngine::new();
+        let context = FormulaContext::new(500.0).at(1.0, 0.5);
+
+        // Adiabatic copies the interior temperature
+        let t = BoundaryCondition::Adiabatic
+            .surface_temperature(500.0, 450.0, 50.0, 0.01, 0.0, &context, &mut engine).unwrap();
+        assert_eq!(t, 500.0);
+
+        // Convection lies between interior and ambient
+        let convection = BoundaryCondition::Convection {
+            heat_transfer_coefficient: 1000.0,
+            ambient_temperature: 300.0,
+        };
+        let t = convection.surface_temperature(500.0, 500.0, 10.0, 0.01, 0.0, &context, &mut engine).unwrap();
+        let expected = (1000.0 * 500.0 + 1000.0 * 300.0) / 2000.0;
+        assert!((t - expected).abs() < 1e-10);
+
+        // Positive flux heats the surface above the interior
+        let flux = BoundaryCondition::FixedFlux { heat_flux: 1000.0 };
+        let t = flux.surface_temperature(500.0, 500.0, 10.0, 0.01, 0.0, &context, &mut engine).unwrap();
+        assert!((t - 501.0).abs() < 1e-10);
+
+        // Radiation cools a hot surface
+        let radiation = BoundaryCondition::Radiation { emissivity: 0.9, ambient_temperature: 300.0 };
+        let t = radiation.surface_temperature(1500.0, 1500.0, 10.0, 0.01, 0.0, &context, &mut engine).unwrap();
+        assert!(t < 1500.0 && t > 300.0);
+    }
+
+    #[test]
+    fn test_formula_condition() {
+        let mut engine = FormulaEngine::new();
+        let condition = BoundaryCondition::Formula {
+            expression: "300.0 + 100.0 * z + 10.0 * t".to_string(),
+            quantity: FormulaQuantity::Temperature,
+        };
+
+        let context = FormulaContext::new(500.0).at(0.2, 1.0).with_time(2.0);
+        let t = condition.surface_temperature(500.0, 500.0, 10.0, 0.01, 0.0, &context, &mut engine).unwrap();
+        assert!((t - 420.0).abs() < 1e-10);
+
+        // Torch index and liquid fraction are bound as well
+        let condition = BoundaryCondition::Formula {
+            expression: "if torch == 1 { 2.0e4 * phi } else { 0.0 }".to_string(),
+            quantity: FormulaQuantity::HeatFlux,
+        };
+        let context = context.with_torch(Some(1)).with_liquid_fraction(0.5);
+        let t = condition.surface_temperature(500.0, 500.0, 10.0, 0.01, 0.0, &context, &mut engine).unwrap();
+        assert!((t - 510.0).abs() < 1e-10);
+        let t = condition.surface_temperature(500.0, 500.0, 10.0, 0.01, 0.0, &context.with_torch(None), &mut engine).unwrap();
+        assert!((t - 500.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_coolant_march_energy_balance() {
+        let jacket = BoundaryCondition::CooledJacket {
+            inlet_temperature: 300.0,
+            mass_flow_rate: 0.5,
+            specific_heat: 4180.0,
+            heat_transfer_coefficient: 2000.0,
+        };
+        let mut coolant = CoolantMarch::start(&jacket, "outer wall", None).unwrap();
+        assert!(CoolantMarch::start(&BoundaryCondition::Adiabatic, "top", None).is_none());
+
+        // Coolant warms along the wall and stays below the wall temperature
+        let first = coolant.advance(500.0, 0.5);
+        let second = coolant.advance(500.0, 0.5);
+        assert!(first > 300.0 && second > first && second < 500.0);
+
+        let report = &coolant.report;
+        let expected = 0.5 * 4180.0 * (report.outlet_temperature - 300.0);
+        assert!((report.heat_removed - expected).abs() < 1e-9);
+
+        // The local coolant temperature replaces the inlet temperature
+        let mut engine = FormulaEngine::new();
+        let t = jacket
+            .with_coolant_temperature(second)
+            .surface_temperature(500.0, 500.0, 10.0, 0.01, 0.0, &FormulaContext::new(500.0).at(1.0, 0.5), &mut engine)
+            .unwrap();
+        let expected = (1000.0 * 500.0 + 2000.0 * second) / 3000.0;
+        assert!((t - expected).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_boundary_validation() {
+        assert!(BoundarySpec::uniform(BoundaryCondition::Adiabatic).validate("top").is_ok());
+
+        let invalid = BoundarySpec::uniform(BoundaryCondition::Radiation {
+            emissivity: 1.5,
+            ambient_temperature: 300.0,
+        });
+        assert!(invalid.validate("top").is_err());
+
+        let reversed = BoundarySpec::uniform(BoundaryCondition::Adiabatic)
+            .with_segment(0.5, 0.1, BoundaryCondition::Adiabatic);
+        assert!(reversed.validate("top").is_err());
+    }
+}
@@ -0,0 +1,414 @@
+//! Mixture and composite materials
+//!
+//! Charges are often blends of several materials (shredded waste, glass frit
+//! with soil, scrap mixes). [`CompositeBuilder`] combines constituent
+//! materials given by mass or volume fraction into a single [`Material`]:
+//!
+//! - density is volume-weighted, ρ = Σ φᵢ ρᵢ, with mass and volume
+//!   fractions converted at the lower end of the temperature range
+//! - specific heat is mass-weighted, cp = Σ wᵢ cpᵢ
+//! - emissivity is volume-weighted
+//! - conductivity follows the selected [`MixingRule`]
+//!
+//! Temperature-dependent constituent properties are sampled over a
+//! temperature range into a property table; constant constituents give
+//! constant composite properties. Phase change, anisotropy and reactions of
+//! the constituents are not carried over to the composite.
+
+use super::materials::{Material, Property};
+use super::property_table::PropertyTable;
+use crate::errors::{Result, SimulationError};
+use crate::formula::engine::FormulaEngine;
+
+/// Tolerance on the sum of the constituent fractions
+const FRACTION_TOLERANCE: f64 = 1e-6;
+
+/// Basis of the constituent fractions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum FractionBasis {
+    Mass,
+    Volume,
+}
+
+/// Effective conductivity model of the composite
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum MixingRule {
+    /// Layers parallel to the heat flow, k = Σ φᵢ kᵢ (upper bound)
+    Parallel,
+    /// Layers in series with the heat flow, 1/k = Σ φᵢ/kᵢ (lower bound)
+    Series,
+    /// Maxwell-Eucken with the first constituent as the continuous phase and
+    /// the others dispersed in it
+    MaxwellEucken,
+    /// Packed bed of the constituents (combined in parallel) with gas-filled
+    /// pores, after Zehner and Schlünder
+    PorousBed {
+        /// Void fraction of the bed
+        porosity: f64,
+        /// Conductivity of the pore gas in W/(m·K)
+        gas_conductivity: Property,
+    },
+}
+
+impl MixingRule {
+    fn validate(&self) -> Result<()> {
+        if let MixingRule::PorousBed { porosity, gas_conductivity } = self {
+            if !(*porosity > 0.0 && *porosity < 1.0) {
+                return Err(SimulationError::InvalidParameter {
+                    parameter: "porosity".to_string(),
+                    value: porosity.to_string(),
+                    range: "(0.0, 1.0)".to_string(),
+                });
+            }
+            gas_conductivity.validate("gas_conductivity")?;
+        }
+        Ok(())
+    }
+
+    /// Effective conductivity from constituent conductivities and volume fractions
+    fn conductivity(&self, conductivities: &[f64], volume_fractions: &[f64], gas_conductivity: f64) -> f64 {
+        let parallel = || conductivities.iter().zip(volume_fractions).map(|(k, phi)| k * phi).sum::<f64>();
+
+        match self {
+            MixingRule::Parallel => parallel(),
+            MixingRule::Series => {
+                if conductivities.iter().zip(volume_fractions).any(|(&k, &phi)| k <= 0.0 && phi > 0.0) {
+                    return 0.0;
+                }
+                1.0 / conductivities.iter().zip(volume_fractions).map(|(k, phi)| phi / k).sum::<f64>()
+            }
+            MixingRule::MaxwellEucken => {
+                let k_continuous = conductivities[0];
+                let (mut numerator, mut denominator) = (volume_fractions[0] * k_continuous, volume_fractions[0]);
+                for (&k, &phi) in conductivities.iter().zip(volume_fractions).skip(1) {
+                    let sum = 2.0 * k_continuous + k;
+                    let weight = if sum > 0.0 { 3.0 * k_continuous / sum } else { 1.0 };
+                    numerator += phi * k * weight;
+                    denominator += phi * weight;
+                }
+                if denominator > 0.0 { numerator / denominator } else { 0.0 }
+            }
+            MixingRule::PorousBed { porosity, .. } => zehner_schlunder(parallel(), gas_conductivity, *porosity),
+        }
+    }
+}
+
+/// Effective conductivity of a packed bed of solid conductivity `k_solid`
+/// with pore gas conductivity `k_gas` (Zehner-Schlünder, without radiation)
+fn zehner_schlunder(k_solid: f64, k_gas: f64, porosity: f64) -> f64 {
+    if k_gas <= 0.0 || k_solid <= 0.0 {
+        return 0.0;
+    }
+
+    let b = 1.25 * ((1.0 - porosity) / porosity).powf(10.0 / 9.0);
+    let mut lambda = k_gas / k_solid;
+    // The expression is regular at λB = 1 but evaluates as 0/0 there
+    if (1.0 - lambda * b).abs() < 1e-9 {
+        lambda *= 1.0 + 1e-6;
+    }
+
+    let n = 1.0 - lambda * b;
+    let core = (1.0 - lambda) * b / (n * n) * (1.0 / (lambda * b)).ln() - 0.5 * (b + 1.0) - (b - 1.0) / n;
+    let root = (1.0 - porosity).sqrt();
+    k_gas * (1.0 - root + 2.0 * root / n * core)
+}
+
+/// Constituent material and its fraction
+#[derive(Debug, Clone)]
+pub struct Constituent {
+    pub material: Material,
+    /// Mass or volume fraction, depending on the builder basis
+    pub fraction: f64,
+}
+
+/// Builder for composite materials
+#[derive(Debug, Clone)]
+pub struct CompositeBuilder {
+    name: String,
+    basis: FractionBasis,
+    constituents: Vec<Constituent>,
+    mixing_rule: MixingRule,
+    /// (min, max) temperature in K and number of samples for tabulated properties
+    temperature_range: (f64, f64),
+    samples: usize,
+}
+
+impl CompositeBuilder {
+    /// Start a composite with fractions on the given basis, mixed in parallel
+    pub fn new(name: &str, basis: FractionBasis) -> Self {
+        Self {
+            name: name.to_string(),
+            basis,
+            constituents: Vec::new(),
+            mixing_rule: MixingRule::Parallel,
+            temperature_range: (300.0, 3000.0),
+            samples: 55,
+        }
+    }
+
+    /// Add a constituent with its mass or volume fraction
+    pub fn with_constituent(mut self, material: Material, fraction: f64) -> Result<Self> {
+        material.validate()?;
+        crate::errors::validation::validate_range(fraction, 0.0, 1.0, &format!("fraction of {}", material.name))?;
+        self.constituents.push(Constituent { material, fraction });
+        Ok(self)
+    }
+
+    /// Set the conductivity mixing rule
+    pub fn with_mixing_rule(mut self, mixing_rule: MixingRule) -> Result<Self> {
+        mixing_rule.validate()?;
+        self.mixing_rule = mixing_rule;
+        Ok(self)
+    }
+
+    /// Set the temperature range (K) and sample count used to tabulate
+    /// temperature-dependent composite properties
+    pub fn with_temperature_range(mut self, min: f64, max: f64, samples: usize) -> Result<Self> {
+        crate::errors::validation::validate_positive(min, "composite minimum temperature")?;
+        crate::errors::validation::validate_range(max, min, f64::INFINITY, "composite maximum temperature")?;
+        if samples < 2 || max <= min {
+            return Err(SimulationError::ConfigurationError {
+                component: "composite material".to_string(),
+                issue: "Tabulation needs a non-empty temperature range and at least 2 samples".to_string(),
+            });
+        }
+        self.temperature_range = (min, max);
+        self.samples = samples;
+        Ok(self)
+    }
+
+    /// Volume fractions of the constituents
+    pub fn volume_fractions(&self) -> Result<Vec<f64>> {
+        self.check_fractions()?;
+        Ok(match self.basis {
+            FractionBasis::Volume => self.fractions(),
+            FractionBasis::Mass => {
+                normalize(self.constituents.iter().zip(self.reference_densities()?).map(|(c, rho)| c.fraction / rho))
+            }
+        })
+    }
+
+    /// Mass fractions of the constituents
+    pub fn mass_fractions(&self) -> Result<Vec<f64>> {
+        self.check_fractions()?;
+        Ok(match self.basis {
+            FractionBasis::Mass => self.fractions(),
+            FractionBasis::Volume => {
+                normalize(self.constituents.iter().zip(self.reference_densities()?).map(|(c, rho)| c.fraction * rho))
+            }
+        })
+    }
+
+    /// Build the composite material
+    pub fn build(&self) -> Result<Material> {
+        let volume_fractions = self.volume_fractions()?;
+        let mass_fractions = self.mass_fractions()?;
+        let materials: Vec<&Material> = self.constituents.iter().map(|c| &c.material).collect();
+
+        let solid_fraction = match self.mixing_rule {
+            MixingRule::PorousBed { porosity, .. } => 1.0 - porosity,
+            _ => 1.0,
+        };
+
+        let mut engine = FormulaEngine::new();
+        let density = |t: f64, engine: &mut FormulaEngine| -> Result<f64> {
+            let mut rho = 0.0;
+            for (m, phi) in materials.iter().zip(&volume_fractions) {
+                rho += phi * m.get_density(t, Some(&mut *engine))?;
+            }
+            Ok(solid_fraction * rho)
+        };
+        let emissivity = |t: f64, engine: &mut FormulaEngine| -> Result<f64> {
+            let mut epsilon = 0.0;
+            for (m, phi) in materials.iter().zip(&volume_fractions) {
+                epsilon += phi * m.get_emissivity(t, Some(&mut *engine))?;
+            }
+            Ok(epsilon)
+        };
+        let conductivity = |t: f64, engine: &mut FormulaEngine| -> Result<f64> {
+            let k = materials
+                .iter()
+                .map(|m| m.get_thermal_conductivity(t, Some(&mut *engine)))
+                .collect::<Result<Vec<_>>>()?;
+            let k_gas = match self.mixing_rule {
+                MixingRule::PorousBed { ref gas_conductivity, .. } => gas_conductivity.evaluate(t, Some(&mut *engine))?,
+                _ => 0.0,
+            };
+            Ok(self.mixing_rule.conductivity(&k, &volume_fractions, k_gas))
+        };
+        let specific_heat = |t: f64, engine: &mut FormulaEngine| -> Result<f64> {
+            let mut cp = 0.0;
+            for (m, w) in materials.iter().zip(&mass_fractions) {
+                cp += w * m.get_specific_heat(t, Some(&mut *engine))?;
+            }
+            Ok(cp)
+        };
+
+        let gas_constant = match self.mixing_rule {
+            MixingRule::PorousBed { ref gas_conductivity, .. } => matches!(gas_conductivity, Property::Constant(_)),
+            _ => true,
+        };
+        let conductivity_constant = gas_constant && materials.iter().all(|m| is_constant(&m.thermal_conductivity) && m.phases.is_none());
+        let specific_heat_constant = materials.iter().all(|m| is_constant(&m.specific_heat) && m.phases.is_none());
+
+        let thermal_conductivity = self.property(conductivity_constant, &mut engine, &conductivity)?;
+        let specific_heat = self.property(specific_heat_constant, &mut engine, &specific_heat)?;
+        let density = self.property(materials.iter().all(|m| is_constant(&m.density)), &mut engine, &density)?;
+        let emissivity = self.property(materials.iter().all(|m| is_constant(&m.emissivity)), &mut engine, &emissivity)?;
+
+        Material::with_properties(self.name.clone(), 1.0, thermal_conductivity, specific_heat, 0.0, None, None)?
+            .with_density(density)?
+            .with_emissivity(emissivity)
+    }
+
+    /// Evaluate a composite property once if constant, otherwise tabulate it
+    fn property(
+        &self,
+        constant: bool,
+        engine: &mut FormulaEngine,
+        evaluate: &dyn Fn(f64, &mut FormulaEngine) -> Result<f64>,
+    ) -> Result<Property> {
+        let (min, max) = self.temperature_range;
+        if constant {
+            return Ok(Property::Constant(evaluate(min, engine)?));
+        }
+
+        let step = (max - min) / (self.samples - 1) as f64;
+        let points = (0..self.samples)
+            .map(|n| {
+                let t = min + step * n as f64;
+                evaluate(t, engine).map(|value| (t, value))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Property::Tabulated(PropertyTable::new(points)))
+    }
+
+    /// Constituent densities at the lower end of the temperature range,
+    /// used to convert between mass and volume fractions
+    fn reference_densities(&self) -> Result<Vec<f64>> {
+        let mut engine = FormulaEngine::new();
+        self.constituents
+            .iter()
+            .map(|c| c.material.get_density(self.temperature_range.0, Some(&mut engine)))
+            .collect()
+    }
+
+    fn fractions(&self) -> Vec<f64> {
+        self.constituents.iter().map(|c| c.fraction).collect()
+    }
+
+    fn check_fractions(&self) -> Result<()> {
+        let total: f64 = self.constituents.iter().map(|c| c.fraction).sum();
+        if self.constituents.is_empty() || (total - 1.0).abs() > FRACTION_TOLERANCE {
+            return Err(SimulationError::ConfigurationError {
+                component: format!("composite material '{}'", self.name),
+                issue: format!("Constituent fractions must sum to 1, got {}", total),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn is_constant(property: &Property) -> bool {
+    matches!(property, Property::Constant(_))
+}
+
+fn normalize(values: impl Iterator<Item = f64>) -> Vec<f64> {
+    let values: Vec<f64> = values.collect();
+    let total: f64 = values.iter().sum();
+    values.into_iter().map(|value| value / total).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constant_material(name: &str, density: f64, k: f64, cp: f64, emissivity: f64) -> Material {
+        Material::with_properties(name.to_string(), density, Property::Constant(k), Property::Constant(cp), emissivity, None, None)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_fraction_conversion_and_weighting() {
+        let glass = constant_material("Glass Frit", 2500.0, 1.0, 800.0, 0.9);
+        let soil = constant_material("Soil", 1250.0, 0.5, 1200.0, 0.7);
+
+        // Equal masses: the lighter soil takes two thirds of the volume
+        let builder = CompositeBuilder::new("Frit + Soil", FractionBasis::Mass)
+            .with_constituent(glass, 0.5).unwrap()
+            .with_constituent(soil, 0.5).unwrap();
+        let phi = builder.volume_fractions().unwrap();
+        assert!((phi[0] - 1.0 / 3.0).abs() < 1e-12 && (phi[1] - 2.0 / 3.0).abs() < 1e-12);
+
+        let blend = builder.build().unwrap();
+        assert!((blend.get_density(500.0, None).unwrap() - (2500.0 / 3.0 + 1250.0 * 2.0 / 3.0)).abs() < 1e-9);
+        assert!((blend.get_specific_heat(500.0, None).unwrap() - 1000.0).abs() < 1e-9);
+        assert!((blend.get_emissivity(500.0, None).unwrap() - (0.9 / 3.0 + 0.7 * 2.0 / 3.0)).abs() < 1e-12);
+        assert!(matches!(blend.thermal_conductivity, Property::Constant(_)));
+        assert!(blend.validate().is_ok());
+
+        // Fractions must add up to one
+        let partial = CompositeBuilder::new("Partial", FractionBasis::Volume)
+            .with_constituent(constant_material("A", 1000.0, 1.0, 1000.0, 0.5), 0.6).unwrap();
+        assert!(partial.build().is_err());
+        assert!(CompositeBuilder::new("Empty", FractionBasis::Volume).build().is_err());
+    }
+
+    #[test]
+    fn test_mixing_rules() {
+        let (k, phi) = ([10.0, 1.0], [0.5, 0.5]);
+        let parallel = MixingRule::Parallel.conductivity(&k, &phi, 0.0);
+        let series = MixingRule::Series.conductivity(&k, &phi, 0.0);
+        let maxwell = MixingRule::MaxwellEucken.conductivity(&k, &phi, 0.0);
+        assert_eq!(parallel, 5.5);
+        assert!((series - 1.0 / 0.55).abs() < 1e-12);
+        assert!(series < maxwell && maxwell < parallel);
+
+        // Maxwell-Eucken with a conductive matrix exceeds that with an insulating one
+        let inverted = MixingRule::MaxwellEucken.conductivity(&[1.0, 10.0], &phi, 0.0);
+        assert!(inverted < maxwell);
+
+        // A bed of gas-conductivity solid conducts like the gas
+        assert!((zehner_schlunder(0.5, 0.5, 0.4) - 0.5).abs() < 1e-9);
+        let bed = zehner_schlunder(20.0, 0.05, 0.4);
+        assert!(bed > 0.05 && bed < 0.6 * 20.0);
+        assert!(zehner_schlunder(20.0, 0.05, 0.6) < bed);
+
+        assert!(MixingRule::PorousBed { porosity: 1.0, gas_conductivity: Property::Constant(0.03) }.validate().is_err());
+    }
+
+    #[test]
+    fn test_temperature_dependent_composite() {
+        let scrap = Material::with_properties(
+            "Scrap".to_string(),
+            7800.0,
+            Property::Table(vec![(300.0, 50.0), (1300.0, 30.0)]),
+            Property::Formula("450.0 + 0.2 * (T - 300.0)".to_string()),
+            0.8,
+            Some(1800.0),
+            Some(2.7e5),
+        )
+        .unwrap();
+        let air = Property::Formula("0.0263 + 5.0e-5 * (T - 300.0)".to_string());
+
+        let bed = CompositeBuilder::new("Scrap Bed", FractionBasis::Volume)
+            .with_constituent(scrap, 1.0).unwrap()
+            .with_mixing_rule(MixingRule::PorousBed { porosity: 0.5, gas_conductivity: air }).unwrap()
+            .with_temperature_range(300.0, 1300.0, 11).unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(bed.get_density(800.0, None).unwrap(), 3900.0);
+        assert!(bed.melting_point.is_none());
+        let cp = bed.get_specific_heat(800.0, None).unwrap();
+        assert!((cp - 550.0).abs() < 1e-9);
+        assert_eq!(bed.thermal_conductivity.table_points().unwrap().len(), 11);
+
+        // The composite works anywhere a material does
+        let mut database = crate::simulation::material_db::MaterialDatabase::new();
+        database.insert(bed).unwrap();
+        assert!(database.contains("Scrap Bed"));
+
+        assert!(CompositeBuilder::new("Bad", FractionBasis::Mass).with_temperature_range(500.0, 400.0, 10).is_err());
+    }
+}
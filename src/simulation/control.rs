@@ -0,0 +1,368 @@
+//! Closed-loop torch power control
+//!
+//! A PID controller drives a measured temperature towards a setpoint by
+//! adjusting the power of one or more torches every control interval. The
+//! measurement is either a probe or an aggregate of the temperature field.
+//! The controller output is the power of each controlled torch in kW:
+//!
+//! P = P₀ + Kp e + Ki ∫e dt - Kd dT/dt
+//!
+//! where e = T_set - T and P₀ is the initial torch power. The derivative acts
+//! on the measurement so setpoint steps do not kick the output, and the
+//! integral stops accumulating while the output is saturated in the direction
+//! of the error (anti-windup).
+//!
+//! A controller covers a single zone: all controlled torches follow one
+//! setpoint and one measurement and receive the same power, so they must
+//! start at equal power.
+
+use super::mesh::CylindricalMesh;
+use super::probes::{Probe, ProbeRecorder};
+use crate::errors::{Result, SimulationError};
+use ndarray::Array2;
+
+/// Target temperature of the controller
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum Setpoint {
+    /// Constant temperature in K
+    Constant(f64),
+    /// (time in s, temperature in K) points, interpolated linearly and held
+    /// constant outside the schedule
+    Schedule(Vec<(f64, f64)>),
+}
+
+impl Setpoint {
+    /// Setpoint temperature at a time
+    pub fn value_at(&self, time: f64) -> f64 {
+        match self {
+            Setpoint::Constant(value) => *value,
+            Setpoint::Schedule(points) => {
+                let (first, last) = (points[0], points[points.len() - 1]);
+                if time <= first.0 {
+                    return first.1;
+                }
+                if time >= last.0 {
+                    return last.1;
+                }
+                points
+                    .windows(2)
+                    .find(|w| time <= w[1].0)
+                    .map_or(last.1, |w| w[0].1 + (w[1].1 - w[0].1) * (time - w[0].0) / (w[1].0 - w[0].0))
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        match self {
+            Setpoint::Constant(value) => crate::errors::validation::validate_positive(*value, "controller setpoint"),
+            Setpoint::Schedule(points) => {
+                if points.is_empty() || points.windows(2).any(|w| w[1].0 <= w[0].0) {
+                    return Err(SimulationError::ConfigurationError {
+                        component: "controller setpoint".to_string(),
+                        issue: "Schedule must be non-empty with strictly increasing times".to_string(),
+                    });
+                }
+                for &(_, value) in points {
+                    crate::errors::validation::validate_positive(value, "controller setpoint")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Temperature the controller acts on
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum ControlVariable {
+    /// Temperature at a named probe
+    Probe(String),
+    /// Volume-weighted mean temperature of the charge
+    MeanTemperature,
+    /// Highest temperature in the charge
+    MaxTemperature,
+}
+
+/// PID gains
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct PidGains {
+    /// Proportional gain in kW/K
+    pub kp: f64,
+    /// Integral gain in kW/(K·s)
+    pub ki: f64,
+    /// Derivative gain in kW·s/K
+    pub kd: f64,
+}
+
+impl Default for PidGains {
+    fn default() -> Self {
+        Self { kp: 1.0, ki: 0.05, kd: 0.0 }
+    }
+}
+
+/// Controller configuration
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ControllerConfig {
+    pub setpoint: Setpoint,
+    pub measurement: ControlVariable,
+    #[serde(default)]
+    pub gains: PidGains,
+    /// Indices of the controlled torches (all torches if empty), driven to one common power
+    #[serde(default)]
+    pub torches: Vec<usize>,
+    /// Lower power limit of each controlled torch in kW
+    pub min_power: f64,
+    /// Upper power limit of each controlled torch in kW
+    pub max_power: f64,
+    /// Time between controller updates in seconds
+    pub control_interval: f64,
+}
+
+impl Default for ControllerConfig {
+    fn default() -> Self {
+        Self {
+            setpoint: Setpoint::Constant(1000.0),
+            measurement: ControlVariable::MeanTemperature,
+            gains: PidGains::default(),
+            torches: Vec::new(),
+            min_power: 1.0,
+            max_power: 1000.0,
+            control_interval: 1.0,
+        }
+    }
+}
+
+impl ControllerConfig {
+    /// Indices of the controlled torches out of `torch_count`
+    pub fn controlled_torches(&self, torch_count: usize) -> Vec<usize> {
+        if self.torches.is_empty() {
+            (0..torch_count).collect()
+        } else {
+            self.torches.clone()
+        }
+    }
+
+    /// Validate the configuration against the torches and probes of a simulation
+    pub fn validate(&self, torches: &[super::TorchConfig], probes: &[Probe]) -> Result<()> {
+        use crate::errors::validation::{validate_positive, validate_range};
+
+        self.setpoint.validate()?;
+        if let ControlVariable::Probe(ref name) = self.measurement {
+            if !probes.iter().any(|probe| &probe.name == name) {
+                return Err(SimulationError::ConfigurationError {
+                    component: "controller".to_string(),
+                    issue: format!("Measurement probe '{}' is not defined", name),
+                });
+            }
+        }
+        for (name, gain) in [("Kp", self.gains.kp), ("Ki", self.gains.ki), ("Kd", self.gains.kd)] {
+            validate_range(gain, 0.0, 1.0e6, &format!("controller gain {}", name))?;
+        }
+        if let Some(&index) = self.torches.iter().find(|&&index| index >= torches.len()) {
+            return Err(SimulationError::InvalidParameter {
+                parameter: "controlled torch index".to_string(),
+                value: index.to_string(),
+                range: format!("[0, {})", torches.len()),
+            });
+        }
+        let controlled = self.controlled_torches(torches.len());
+        if let Some(&first) = controlled.first() {
+            if let Some(&other) = controlled.iter().find(|&&k| torches[k].power != torches[first].power) {
+                return Err(SimulationError::ConfigurationError {
+                    component: "controller".to_string(),
+                    issue: format!(
+                        "Controlled torches {} and {} start at {} kW and {} kW, but a controller drives all of its \
+                         torches to one common power from a single setpoint and measurement; torches in different \
+                         zones cannot be controlled independently",
+                        first, other, torches[first].power, torches[other].power
+                    ),
+                });
+            }
+        }
+        validate_range(self.min_power, 1.0, 1000.0, "controller minimum power")?;
+        validate_range(self.max_power, self.min_power, 1000.0, "controller maximum power")?;
+        validate_positive(self.control_interval, "control interval")?;
+        Ok(())
+    }
+}
+
+/// Controller state after one update
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ControllerSample {
+    pub time: f64,
+    /// Setpoint temperature in K
+    pub setpoint: f64,
+    /// Measured temperature in K
+    pub measurement: f64,
+    /// Power commanded to each controlled torch in kW
+    pub power: f64,
+    /// Whether the output was held at a power limit
+    pub saturated: bool,
+}
+
+/// Logged controller updates
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ControllerHistory {
+    pub samples: Vec<ControllerSample>,
+}
+
+/// PID torch power controller
+#[derive(Debug, Clone)]
+pub struct PidController {
+    pub config: ControllerConfig,
+    /// Power of each controlled torch in kW
+    pub power: f64,
+    pub history: ControllerHistory,
+    bias: f64,
+    integral: f64,
+    last_update: f64,
+    previous_measurement: Option<f64>,
+}
+
+impl PidController {
+    /// Create a controller starting from the current torch power in kW
+    pub fn new(config: ControllerConfig, initial_power: f64) -> Self {
+        let power = initial_power.clamp(config.min_power, config.max_power);
+        Self {
+            config,
+            power,
+            history: ControllerHistory::default(),
+            bias: power,
+            integral: 0.0,
+            last_update: 0.0,
+            previous_measurement: None,
+        }
+    }
+
+    /// Check whether a control interval has elapsed
+    pub fn is_due(&self, time: f64) -> bool {
+        time - self.last_update >= self.config.control_interval - 1e-12
+    }
+
+    /// Evaluate the controlled temperature
+    pub fn measure(&self, mesh: &CylindricalMesh, temperature: &Array2<f64>, probes: Option<&ProbeRecorder>) -> f64 {
+        match self.config.measurement {
+            ControlVariable::Probe(ref name) => probes
+                .and_then(|recorder| recorder.histories.iter().find(|history| &history.name == name))
+                .and_then(|history| history.temperatures.last().copied())
+                .unwrap_or(f64::NAN),
+            ControlVariable::MeanTemperature => {
+                let (mut weighted, mut volume) = (0.0, 0.0);
+                for ((i, j), &t) in temperature.indexed_iter() {
+                    let v = mesh.get_cell_volume(i, j);
+                    weighted += t * v;
+                    volume += v;
+                }
+                weighted / volume
+            }
+            ControlVariable::MaxTemperature => temperature.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+
+    /// Update the output from a measurement and return the new torch power in kW
+    pub fn update(&mut self, time: f64, measurement: f64) -> f64 {
+        let dt = time - self.last_update;
+        let setpoint = self.config.setpoint.value_at(time);
+        let error = setpoint - measurement;
+        let gains = self.config.gains;
+
+        let derivative = match self.previous_measurement {
+            Some(previous) if dt > 0.0 => -(measurement - previous) / dt,
+            _ => 0.0,
+        };
+        let candidate = self.integral + gains.ki * error * dt;
+        let unclamped = self.bias + gains.kp * error + candidate + gains.kd * derivative;
+
+        // Anti-windup: hold the integral while saturated in the direction of the error
+        let winding_up = (unclamped > self.config.max_power && error > 0.0)
+            || (unclamped < self.config.min_power && error < 0.0);
+        if !winding_up {
+            self.integral = candidate;
+        }
+
+        self.power = unclamped.clamp(self.config.min_power, self.config.max_power);
+        self.last_update = time;
+        self.previous_measurement = Some(measurement);
+        self.history.samples.push(ControllerSample {
+            time,
+            setpoint,
+            measurement,
+            power: self.power,
+            saturated: self.power != unclamped,
+        });
+
+        self.power
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::TorchConfig;
+
+    #[test]
+    fn test_setpoint_schedule() {
+        let schedule = Setpoint::Schedule(vec![(0.0, 500.0), (10.0, 1500.0), (20.0, 1500.0)]);
+        assert_eq!(schedule.value_at(-1.0), 500.0);
+        assert_eq!(schedule.value_at(5.0), 1000.0);
+        assert_eq!(schedule.value_at(15.0), 1500.0);
+        assert_eq!(schedule.value_at(100.0), 1500.0);
+        assert_eq!(Setpoint::Constant(800.0).value_at(3.0), 800.0);
+
+        assert!(Setpoint::Schedule(vec![(1.0, 500.0), (1.0, 600.0)]).validate().is_err());
+        assert!(Setpoint::Schedule(Vec::new()).validate().is_err());
+    }
+
+    #[test]
+    fn test_pid_output_and_anti_windup() {
+        let config = ControllerConfig {
+            setpoint: Setpoint::Constant(1000.0),
+            gains: PidGains { kp: 0.5, ki: 0.1, kd: 0.0 },
+            min_power: 10.0,
+            max_power: 200.0,
+            ..Default::default()
+        };
+        let mut controller = PidController::new(config, 100.0);
+
+        // Below the setpoint the power rises: P₀ + Kp e + Ki e dt
+        assert!((controller.update(1.0, 900.0) - (100.0 + 50.0 + 10.0)).abs() < 1e-12);
+
+        // A large error saturates the output without winding up the integral
+        for step in 2..50 {
+            controller.update(step as f64, 300.0);
+        }
+        assert_eq!(controller.power, 200.0);
+        assert!(controller.history.samples.last().unwrap().saturated);
+
+        // Once above the setpoint the output leaves saturation immediately
+        let power = controller.update(50.0, 1100.0);
+        assert!(power < 200.0);
+        assert_eq!(controller.history.samples.len(), 50);
+    }
+
+    #[test]
+    fn test_controller_validation() {
+        let probes = vec![Probe::new("tc1", 0.5, 1.0)];
+        let config = ControllerConfig {
+            measurement: ControlVariable::Probe("tc1".to_string()),
+            torches: vec![0],
+            ..Default::default()
+        };
+        let torch = [TorchConfig::default()];
+        assert!(config.validate(&torch, &probes).is_ok());
+        assert!(config.validate(&torch, &[]).is_err());
+        assert!(config.validate(&[], &probes).is_err());
+
+        let inverted = ControllerConfig { min_power: 500.0, max_power: 100.0, ..Default::default() };
+        assert!(inverted.validate(&torch, &[]).is_err());
+        let untimed = ControllerConfig { control_interval: 0.0, ..Default::default() };
+        assert!(untimed.validate(&torch, &[]).is_err());
+
+        // One controller is one zone: torches at different powers can't share its output
+        let zones = [TorchConfig::default(), TorchConfig { power: 50.0, ..TorchConfig::default() }];
+        let shared = ControllerConfig::default();
+        let error = shared.validate(&zones, &[]).unwrap_err();
+        assert!(error.to_string().contains("one common power"));
+        let single = ControllerConfig { torches: vec![1], ..Default::default() };
+        assert!(single.validate(&zones, &[]).is_ok());
+    }
+}
@@ -0,0 +1,206 @@
+//! Effective natural convection in molten regions
+//!
+//! Buoyancy-driven flow in liquid pools mixes heat far faster than
+//! conduction alone. Instead of solving for the flow, this module raises the
+//! thermal conductivity of liquid cells by a Nusselt-number multiplier based
+//! on a local Rayleigh number:
+//!
+//! Ra = g β |∇T| L⁴ / (ν α),  Nu = max(1, C Ra^n)
+//!
+//! The multiplier 1 + f_l (Nu - 1) blends between pure conduction in solid
+//! cells (liquid fraction f_l = 0) and the full enhancement in liquid cells.
+
+use crate::errors::{Result, SimulationError};
+use ndarray::Array2;
+
+/// Gravitational acceleration in m/s²
+const GRAVITY: f64 = 9.81;
+
+/// Natural convection configuration
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct NaturalConvectionConfig {
+    /// Width of the melting range centred on the melting point in K
+    pub mushy_range: f64,
+    /// Volumetric thermal expansion coefficient of the liquid in 1/K
+    pub thermal_expansion: f64,
+    /// Kinematic viscosity of the liquid in m²/s
+    pub kinematic_viscosity: f64,
+    /// Characteristic length of the liquid pool in meters
+    pub characteristic_length: f64,
+    /// Nusselt correlation coefficient C
+    pub nusselt_coefficient: f64,
+    /// Nusselt correlation exponent n
+    pub nusselt_exponent: f64,
+    /// Upper bound on the conductivity multiplier
+    pub max_multiplier: f64,
+}
+
+impl Default for NaturalConvectionConfig {
+    /// Values representative of molten steel
+    fn default() -> Self {
+        Self {
+            mushy_range: 50.0,
+            thermal_expansion: 1.2e-4,
+            kinematic_viscosity: 8.0e-7,
+            characteristic_length: 0.1,
+            nusselt_coefficient: 0.15,
+            nusselt_exponent: 1.0 / 3.0,
+            max_multiplier: 50.0,
+        }
+    }
+}
+
+impl NaturalConvectionConfig {
+    /// Validate configuration parameters
+    pub fn validate(&self) -> Result<()> {
+        use crate::errors::validation::{validate_positive, validate_range};
+
+        validate_positive(self.mushy_range, "mushy range")?;
+        validate_positive(self.thermal_expansion, "thermal expansion coefficient")?;
+        validate_positive(self.kinematic_viscosity, "kinematic viscosity")?;
+        validate_positive(self.characteristic_length, "characteristic length")?;
+        validate_positive(self.nusselt_coefficient, "Nusselt coefficient")?;
+        validate_range(self.nusselt_exponent, 0.0, 1.0, "Nusselt exponent")?;
+        validate_range(self.max_multiplier, 1.0, 1.0e4, "maximum conductivity multiplier")?;
+        Ok(())
+    }
+
+    /// Nusselt number for a Rayleigh number
+    pub fn nusselt_number(&self, rayleigh: f64) -> f64 {
+        (self.nusselt_coefficient * rayleigh.max(0.0).powf(self.nusselt_exponent)).max(1.0)
+    }
+}
+
+/// Natural convection enhancement model
+#[derive(Debug, Clone)]
+pub struct NaturalConvection {
+    pub config: NaturalConvectionConfig,
+    /// Liquid fraction of each node
+    pub liquid_fraction: Array2<f64>,
+    /// Effective conductivity multiplier of each node
+    pub multiplier: Array2<f64>,
+}
+
+impl NaturalConvection {
+    /// Create the model for a mesh and material
+    pub fn new(
+        config: NaturalConvectionConfig,
+        mesh: &super::mesh::CylindricalMesh,
+        material: &super::materials::Material,
+    ) -> Result<Self> {
+        config.validate()?;
+
+        if material.melting_point.is_none() {
+            return Err(SimulationError::ConfigurationError {
+                component: "natural convection".to_string(),
+                issue: format!("Material '{}' has no melting point", material.name),
+            });
+        }
+
+        let shape = (mesh.nr, mesh.nz);
+        Ok(Self {
+            config,
+            liquid_fraction: Array2::zeros(shape),
+            multiplier: Array2::ones(shape),
+        })
+    }
+
+    /// Update the liquid fraction and conductivity multiplier from the temperature field
+    pub fn update(
+        &mut self,
+        mesh: &super::mesh::CylindricalMesh,
+        physics: &super::physics::PlasmaPhysics,
+        temperature: &Array2<f64>,
+    ) {
+        let (nr, nz) = (mesh.nr, mesh.nz);
+        let material = &physics.material;
+        let length = self.config.characteristic_length;
+
+        for i in 0..nr {
+            for j in 0..nz {
+                let t = temperature[[i, j]];
+                let fraction = material.liquid_fraction(t, self.config.mushy_range);
+                self.liquid_fraction[[i, j]] = fraction;
+
+                if fraction <= 0.0 {
+                    self.multiplier[[i, j]] = 1.0;
+                    continue;
+                }
+
+                // Local temperature gradient magnitude
+                let dt_dr = if i == 0 {
+                    0.0
+                } else if i == nr - 1 {
+                    (t - temperature[[i - 1, j]]) / mesh.dr
+                } else {
+                    (temperature[[i + 1, j]] - temperature[[i - 1, j]]) / (2.0 * mesh.dr)
+                };
+                let dt_dz = if j == 0 {
+                    (temperature[[i, j + 1]] - t) / mesh.dz
+                } else if j == nz - 1 {
+                    (t - temperature[[i, j - 1]]) / mesh.dz
+                } else {
+                    (temperature[[i, j + 1]] - temperature[[i, j - 1]]) / (2.0 * mesh.dz)
+                };
+                let gradient = (dt_dr * dt_dr + dt_dz * dt_dz).sqrt();
+
+                let alpha = physics.get_thermal_conductivity(t) / (physics.get_density(t) * physics.get_specific_heat(t));
+                let rayleigh = GRAVITY * self.config.thermal_expansion * gradient * length.powi(4)
+                    / (self.config.kinematic_viscosity * alpha);
+                let nusselt = self.config.nusselt_number(rayleigh);
+
+                self.multiplier[[i, j]] = (1.0 + fraction * (nusselt - 1.0)).min(self.config.max_multiplier);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::materials::MaterialLibrary;
+    use crate::simulation::mesh::CylindricalMesh;
+    use crate::simulation::physics::{BoundaryConditions, PlasmaPhysics, PlasmaTorch};
+
+    fn physics(material: &str) -> PlasmaPhysics {
+        let torch = PlasmaTorch::new((0.0, 1.0), 100.0, 0.8, 0.1).unwrap();
+        let material = MaterialLibrary::get_material(material).unwrap();
+        PlasmaPhysics::new(vec![torch], material, BoundaryConditions::default()).unwrap()
+    }
+
+    #[test]
+    fn test_nusselt_correlation() {
+        let config = NaturalConvectionConfig::default();
+        assert_eq!(config.nusselt_number(0.0), 1.0);
+        assert!((config.nusselt_number(1.0e9) - 0.15 * 1000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_multiplier_only_in_liquid_cells() {
+        let mesh = CylindricalMesh::new(0.5, 1.0, 10, 10).unwrap();
+        let physics = physics("Carbon Steel");
+        let mut model = NaturalConvection::new(NaturalConvectionConfig::default(), &mesh, &physics.material).unwrap();
+
+        // Liquid lower half with a vertical gradient, solid upper half
+        let mut temperature = mesh.create_temperature_array(1000.0);
+        for i in 0..mesh.nr {
+            for j in 0..5 {
+                temperature[[i, j]] = 2100.0 - 20.0 * j as f64;
+            }
+        }
+        model.update(&mesh, &physics, &temperature);
+
+        assert_eq!(model.liquid_fraction[[3, 2]], 1.0);
+        assert!(model.multiplier[[3, 2]] > 1.0);
+        assert_eq!(model.liquid_fraction[[3, 8]], 0.0);
+        assert_eq!(model.multiplier[[3, 8]], 1.0);
+        assert!(model.multiplier.iter().all(|&m| m <= model.config.max_multiplier));
+    }
+
+    #[test]
+    fn test_requires_melting_point() {
+        let mesh = CylindricalMesh::new(0.5, 1.0, 10, 10).unwrap();
+        let concrete = MaterialLibrary::get_material("Concrete").unwrap();
+        assert!(NaturalConvection::new(NaturalConvectionConfig::default(), &mesh, &concrete).is_err());
+    }
+}
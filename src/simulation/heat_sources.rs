@@ -0,0 +1,135 @@
+//! Formula-defined volumetric heat sources
+//!
+//! Heat input that the torch model does not cover (an induction coil, a
+//! burner, an exothermic layer of the charge) is given as Rhai formulas in
+//! the standard simulation variables T, TC, r, z, t, torch and phi; see
+//! [`crate::formula::engine::STANDARD_VARIABLES`]. Conditionals confine a
+//! source to part of the furnace, e.g. `if z < 0.2 && r < 0.3 { 5.0e5 } else { 0.0 }`.
+
+use crate::errors::{Result, SimulationError};
+use crate::formula::engine::{CompiledFormula, FormulaEngine};
+use crate::units::Dimension;
+use ndarray::Array2;
+use std::sync::Arc;
+
+/// Volumetric heat source defined by a formula
+///
+/// The expression may carry a result unit such as `[kW/m^3]`; see [`crate::units`].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FormulaHeatSource {
+    pub name: String,
+    /// Rhai formula giving the heat source in W/m³ (negative values remove heat)
+    pub expression: String,
+}
+
+impl FormulaHeatSource {
+    /// Create a named formula heat source
+    pub fn new(name: &str, expression: &str) -> Self {
+        Self { name: name.to_string(), expression: expression.to_string() }
+    }
+
+    /// Validate the name and result unit and compile the formula
+    pub fn validate(&self, formula_engine: &mut FormulaEngine) -> Result<Arc<CompiledFormula>> {
+        crate::errors::validation::validate_non_empty_string(&self.name, "heat source name")?;
+        let source_error = |e: SimulationError| SimulationError::ConfigurationError {
+            component: format!("heat source '{}'", self.name),
+            issue: e.to_string(),
+        };
+        crate::units::check_formula_dimension(&self.expression, Dimension::HEAT_SOURCE, "volumetric heat source")
+            .map_err(source_error)?;
+        formula_engine.compile(&self.expression).map_err(source_error)
+    }
+}
+
+/// Formula heat sources evaluated on the mesh
+#[derive(Debug, Clone)]
+pub struct FormulaSources {
+    pub sources: Vec<FormulaHeatSource>,
+    compiled: Vec<Arc<CompiledFormula>>,
+    /// Summed heat source in W/m³
+    pub heat_source: Array2<f64>,
+}
+
+impl FormulaSources {
+    /// Compile the source formulas
+    pub fn new(
+        sources: Vec<FormulaHeatSource>,
+        mesh: &super::mesh::CylindricalMesh,
+        formula_engine: &mut FormulaEngine,
+    ) -> Result<Self> {
+        let compiled = sources.iter().map(|source| source.validate(formula_engine)).collect::<Result<Vec<_>>>()?;
+        Ok(Self { sources, compiled, heat_source: Array2::zeros((mesh.nr, mesh.nz)) })
+    }
+
+    /// Evaluate all sources at every node for the current field and time
+    ///
+    /// The liquid fraction `phi` comes from the region material when
+    /// regions are set, otherwise from the base material.
+    pub fn update(
+        &mut self,
+        mesh: &super::mesh::CylindricalMesh,
+        physics: &super::physics::PlasmaPhysics,
+        regions: Option<&super::regions::RegionMap>,
+        temperature: &Array2<f64>,
+        time: f64,
+        formula_engine: &mut FormulaEngine,
+    ) -> Result<()> {
+        for ((i, j), value) in self.heat_source.indexed_iter_mut() {
+            let t = temperature[[i, j]];
+            let mut context = physics.formula_context(mesh.r_coords[i], mesh.z_coords[j], t, time);
+            if let Some(regions) = regions {
+                context.liquid_fraction = regions.material_at(i, j).liquid_fraction(t, 0.0);
+            }
+
+            *value = 0.0;
+            for (source, compiled) in self.sources.iter().zip(&self.compiled) {
+                *value += formula_engine.evaluate_in_context(compiled, &context).map_err(|e| SimulationError::ConfigurationError {
+                    component: format!("heat source '{}'", source.name),
+                    issue: format!("{} at r = {:.3} m, z = {:.3} m", e, context.r, context.z),
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::materials::Material;
+    use crate::simulation::mesh::CylindricalMesh;
+    use crate::simulation::physics::{BoundaryConditions, PlasmaPhysics, PlasmaTorch};
+
+    #[test]
+    fn test_formula_sources_use_standard_variables() {
+        let mesh = CylindricalMesh::new(1.0, 2.0, 11, 21).unwrap();
+        let torches = vec![
+            PlasmaTorch::new((0.0, 0.5), 100.0, 0.8, 0.1).unwrap(),
+            PlasmaTorch::new((0.0, 1.5), 100.0, 0.8, 0.1).unwrap(),
+        ];
+        let physics = PlasmaPhysics::new(torches, Material::default(), BoundaryConditions::default()).unwrap();
+        let mut engine = FormulaEngine::new();
+
+        let sources = vec![
+            FormulaHeatSource::new("bottom layer", "if z < 0.5 { 1.0e5 } else { 0.0 }"),
+            FormulaHeatSource::new("upper torch zone", "if torch == 1 { 10.0 * t } else { 0.0 }"),
+        ];
+        let mut model = FormulaSources::new(sources, &mesh, &mut engine).unwrap();
+        let temperature = mesh.create_temperature_array(300.0);
+        model.update(&mesh, &physics, None, &temperature, 2.0, &mut engine).unwrap();
+
+        assert_eq!(model.heat_source[[5, 2]], 1.0e5);
+        assert_eq!(model.heat_source[[5, 8]], 0.0);
+        assert_eq!(model.heat_source[[0, 18]], 20.0);
+    }
+
+    #[test]
+    fn test_invalid_source_formula() {
+        let mesh = CylindricalMesh::new(1.0, 2.0, 11, 21).unwrap();
+        let mut engine = FormulaEngine::new();
+        let sources = vec![FormulaHeatSource::new("broken", "1.0 +* z")];
+        assert!(FormulaSources::new(sources, &mesh, &mut engine).is_err());
+        let sources = vec![FormulaHeatSource::new("", "1.0")];
+        assert!(FormulaSources::new(sources, &mesh, &mut engine).is_err());
+    }
+}
@@ -0,0 +1,354 @@
+//! Energy balance ledger
+//!
+//! Accumulates every term of the furnace energy balance over the run:
+//!
+//! Σ torch input + coupled sources = Σ boundary losses + ΔE_sensible + E_latent + residual
+//!
+//! Torch and coupled source energy is integrated over the interior nodes,
+//! which are the nodes that receive volumetric heat in the solver. Sensible
+//! heat is accumulated over the same nodes step by step, with the heat
+//! capacity the solver used for the step, so stored heat and input are
+//! measured alike. Boundary losses are the conductive flux arriving at each
+//! surface node from its interior neighbour (corners, which only touch edge
+//! nodes, are skipped), split per boundary segment into convection and
+//! radiation by the coefficients of the boundary condition. Heat leaving
+//! through conditions without an ambient (fixed temperature, prescribed flux,
+//! formulas) is booked as other loss. The residual is the discretization
+//! error of the balance; it vanishes when no heat is conducted between
+//! interior nodes, e.g. for a uniform source in an insulated charge.
+
+use super::boundary::{BoundarySet, BoundarySpec};
+use super::mesh::{BoundaryType, CylindricalMesh};
+use super::physics::PlasmaPhysics;
+use super::solver::HeatSolver;
+use ndarray::Array2;
+
+/// Energy lost through one boundary segment in J
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BoundaryLoss {
+    /// Boundary name ("outer wall", "top" or "bottom")
+    pub boundary: String,
+    /// Segment index (None for the boundary default)
+    pub segment: Option<usize>,
+    pub convection: f64,
+    pub radiation: f64,
+    /// Loss through conditions without an ambient (fixed temperature, flux, formula)
+    pub other: f64,
+}
+
+impl BoundaryLoss {
+    /// Total loss through the segment in J
+    pub fn total(&self) -> f64 {
+        self.convection + self.radiation + self.other
+    }
+}
+
+/// Cumulative energy balance at one point in time, all terms in J
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct LedgerEntry {
+    pub time: f64,
+    /// Energy deposited by each torch
+    pub torch_input: Vec<f64>,
+    /// Net energy from coupled volumetric sources (Joule heating, reactions, P1 radiation)
+    pub coupled_sources: f64,
+    /// Energy lost through each boundary segment
+    pub boundary_losses: Vec<BoundaryLoss>,
+    /// Change in sensible heat stored in the charge
+    pub sensible_heat: f64,
+    /// Latent heat carried off by evaporated moisture
+    pub latent_heat: f64,
+    /// Input minus losses and stored energy
+    pub residual: f64,
+}
+
+impl LedgerEntry {
+    /// Total energy input
+    pub fn total_input(&self) -> f64 {
+        self.torch_input.iter().sum::<f64>() + self.coupled_sources
+    }
+
+    /// Total boundary loss
+    pub fn total_loss(&self) -> f64 {
+        self.boundary_losses.iter().map(BoundaryLoss::total).sum()
+    }
+}
+
+/// Energy balance time series
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct EnergyLedger {
+    pub entries: Vec<LedgerEntry>,
+    /// Running totals since the start of the simulation
+    #[serde(skip)]
+    totals: LedgerEntry,
+    /// Temperature field at the end of the last recorded step
+    #[serde(skip)]
+    previous: Array2<f64>,
+}
+
+impl EnergyLedger {
+    /// Start a ledger from the initial state
+    pub fn new(physics: &PlasmaPhysics, temperature: &Array2<f64>) -> Self {
+        Self {
+            entries: Vec::new(),
+            totals: LedgerEntry { torch_input: vec![0.0; physics.torches.len()], ..Default::default() },
+            previous: temperature.clone(),
+        }
+    }
+
+    /// Book the sources and boundary losses of a completed time step
+    ///
+    /// `temperature` is the field after the step and `latent_heat` the energy
+    /// spent on evaporation during the step.
+    pub fn record_step(
+        &mut self,
+        mesh: &CylindricalMesh,
+        physics: &PlasmaPhysics,
+        solver: &HeatSolver,
+        temperature: &Array2<f64>,
+        dt: f64,
+        latent_heat: f64,
+    ) {
+        self.totals.latent_heat += latent_heat;
+
+        for i in 1..mesh.nr - 1 {
+            for j in 1..mesh.nz - 1 {
+                let (r, z) = (mesh.r_coords[i], mesh.z_coords[j]);
+                let energy_per_source = mesh.get_cell_volume(i, j) * dt;
+
+                for (input, torch) in self.totals.torch_input.iter_mut().zip(&physics.torches) {
+                    *input += torch.calculate_heat_flux(r, z) * energy_per_source;
+                }
+                if let Some(ref source) = solver.volumetric_heat_source {
+                    self.totals.coupled_sources += source[[i, j]] * energy_per_source;
+                }
+
+                // Heat stored at the capacity the solver used, evaluated at the start of the step
+                let t_old = self.previous[[i, j]];
+                let capacity = physics.get_density(t_old) * physics.get_specific_heat(t_old) * solver.capacity_multiplier_at(i, j);
+                self.totals.sensible_heat += capacity * mesh.get_cell_volume(i, j) * (temperature[[i, j]] - t_old);
+            }
+        }
+        self.previous.assign(temperature);
+
+        let boundaries = physics.boundary_conditions.resolve();
+        for i in 0..mesh.nr {
+            for j in 0..mesh.nz {
+                self.book_surface_loss(i, j, mesh, physics, solver, temperature, &boundaries, dt);
+            }
+        }
+    }
+
+    /// Book the loss through one surface node
+    #[allow(clippy::too_many_arguments)]
+    fn book_surface_loss(
+        &mut self,
+        i: usize,
+        j: usize,
+        mesh: &CylindricalMesh,
+        physics: &PlasmaPhysics,
+        solver: &HeatSolver,
+        temperature: &Array2<f64>,
+        boundaries: &BoundarySet,
+        dt: f64,
+    ) {
+        let (name, spec, interior, distance, area, coordinate): (_, &BoundarySpec, _, _, _, _) = match mesh.get_boundary_type(i, j) {
+            BoundaryType::OuterWall => {
+                ("outer wall", &boundaries.outer_wall, (i - 1, j), mesh.dr, mesh.get_cell_area_radial(i, j), mesh.z_coords[j])
+            }
+            BoundaryType::Bottom => {
+                ("bottom", &boundaries.bottom, (i, j + 1), mesh.dz, mesh.get_cell_area_axial(i, j), mesh.r_coords[i])
+            }
+            BoundaryType::Top => {
+                ("top", &boundaries.top, (i, j - 1), mesh.dz, mesh.get_cell_area_axial(i, j), mesh.r_coords[i])
+            }
+            BoundaryType::Axis | BoundaryType::Interior => return,
+        };
+        // Corners exchange heat only with edge nodes, not with the charge
+        if mesh.get_boundary_type(interior.0, interior.1) != BoundaryType::Interior {
+            return;
+        }
+
+        let t_surface = temperature[[i, j]];
+        let axial_ratio = match mesh.get_boundary_type(i, j) {
+            BoundaryType::Top | BoundaryType::Bottom => {
+                solver.axial_conductivity_ratio.as_ref().map_or(1.0, |ratio| ratio[[i, j]])
+            }
+            _ => 1.0,
+        };
+        let k = physics.get_thermal_conductivity(t_surface) * solver.conductivity_multiplier_at(i, j) * axial_ratio;
+        let energy = k * (temperature[interior] - t_surface) / distance * area * dt;
+
+        let segment = spec.segment_index_at(coordinate);
+        let index = match self.totals.boundary_losses.iter().position(|loss| loss.boundary == name && loss.segment == segment) {
+            Some(index) => index,
+            None => {
+                self.totals.boundary_losses.push(BoundaryLoss {
+                    boundary: name.to_string(),
+                    segment,
+                    convection: 0.0,
+                    radiation: 0.0,
+                    other: 0.0,
+                });
+                self.totals.boundary_losses.len() - 1
+            }
+        };
+        let loss = &mut self.totals.boundary_losses[index];
+
+        match spec.condition_at(coordinate).loss_fractions(t_surface) {
+            Some((convective, radiative)) => {
+                loss.convection += convective * energy;
+                loss.radiation += radiative * energy;
+            }
+            None => loss.other += energy,
+        }
+    }
+
+    /// Close the balance at `time` and append it to the time series
+    pub fn snapshot(&mut self, time: f64) -> &LedgerEntry {
+        let mut entry = self.totals.clone();
+        entry.time = time;
+        entry.residual = entry.total_input() - entry.total_loss() - entry.sensible_heat - entry.latent_heat;
+
+        self.entries.push(entry);
+        self.entries.last().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::boundary::BoundaryCondition;
+    use crate::simulation::materials::MaterialLibrary;
+    use crate::simulation::physics::{BoundaryConditions, PlasmaTorch};
+    use crate::simulation::solver::SolverMethod;
+
+    fn physics(boundary_conditions: BoundaryConditions) -> PlasmaPhysics {
+        let torches = vec![
+            PlasmaTorch::new((0.5, 1.5), 100.0, 0.8, 0.1).unwrap(),
+            PlasmaTorch::new((0.5, 0.5), 50.0, 0.8, 0.1).unwrap(),
+        ];
+        let material = MaterialLibrary::get_material("Carbon Steel").unwrap();
+        PlasmaPhysics::new(torches, material, boundary_conditions).unwrap()
+    }
+
+    #[test]
+    fn test_torch_input_per_torch() {
+        let mesh = CylindricalMesh::new(1.0, 2.0, 21, 41).unwrap();
+        let physics = physics(BoundaryConditions::default());
+        let solver = HeatSolver::new(SolverMethod::ForwardEuler);
+        let temperature = mesh.create_temperature_array(300.0);
+
+        let mut ledger = EnergyLedger::new(&physics, &temperature);
+        ledger.record_step(&mesh, &physics, &solver, &temperature, 1.0, 0.0);
+        let entry = ledger.snapshot(1.0);
+
+        // Torches at mirrored positions deposit energy in proportion to their power
+        assert_eq!(entry.torch_input.len(), 2);
+        assert!(entry.torch_input[1] > 0.0);
+        assert!((entry.torch_input[0] / entry.torch_input[1] - 2.0).abs() < 1e-9);
+
+        // Nothing changed yet, so the whole input is unbalanced
+        assert_eq!(entry.sensible_heat, 0.0);
+        assert!((entry.residual - entry.total_input()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_boundary_losses_split_by_mechanism() {
+        let boundary_conditions = BoundaryConditions {
+            top: Some(BoundarySpec::uniform(BoundaryCondition::Convection {
+                heat_transfer_coefficient: 20.0,
+                ambient_temperature: 300.0,
+            })),
+            ..Default::default()
+        };
+        let mesh = CylindricalMesh::new(1.0, 2.0, 11, 11).unwrap();
+        let physics = physics(boundary_conditions);
+        let solver = HeatSolver::new(SolverMethod::ForwardEuler);
+
+        // Hot interior, cooler surfaces
+        let mut temperature = mesh.create_temperature_array(1000.0);
+        for ((i, j), t) in temperature.indexed_iter_mut() {
+            if mesh.get_boundary_type(i, j) != BoundaryType::Interior {
+                *t = 900.0;
+            }
+        }
+
+        let mut ledger = EnergyLedger::new(&physics, &temperature);
+        ledger.record_step(&mesh, &physics, &solver, &temperature, 0.5, 0.0);
+        let entry = ledger.snapshot(0.5);
+
+        let find = |name: &str| entry.boundary_losses.iter().find(|loss| loss.boundary == name).unwrap();
+        let wall = find("outer wall");
+        assert!(wall.convection > 0.0 && wall.radiation > 0.0);
+        let top = find("top");
+        assert!(top.convection > 0.0);
+        assert_eq!(top.radiation, 0.0);
+
+        // The default adiabatic bottom has no ambient to exchange with
+        let bottom = find("bottom");
+        assert_eq!(bottom.convection + bottom.radiation, 0.0);
+    }
+
+    #[test]
+    fn test_boundary_losses_use_conductivity_multiplier() {
+        let mesh = CylindricalMesh::new(1.0, 2.0, 11, 11).unwrap();
+        let physics = physics(BoundaryConditions::default());
+        let mut temperature = mesh.create_temperature_array(1000.0);
+        for ((i, j), t) in temperature.indexed_iter_mut() {
+            if mesh.get_boundary_type(i, j) != BoundaryType::Interior {
+                *t = 900.0;
+            }
+        }
+
+        let wall_loss = |solver: &HeatSolver| {
+            let mut ledger = EnergyLedger::new(&physics, &temperature);
+            ledger.record_step(&mesh, &physics, solver, &temperature, 0.5, 0.0);
+            let entry = ledger.snapshot(0.5);
+            entry.boundary_losses.iter().find(|loss| loss.boundary == "outer wall").unwrap().total()
+        };
+        let mut solver = HeatSolver::new(SolverMethod::ForwardEuler);
+        let base = wall_loss(&solver);
+
+        // A more conductive surface region (e.g. a steel shell) conducts more heat out
+        solver.conductivity_multiplier = Some(mesh.create_temperature_array(2.0));
+        assert!((wall_loss(&solver) / base - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_balance_closes_for_uniform_source_in_insulated_charge() {
+        let boundary_conditions = BoundaryConditions {
+            outer_wall: Some(BoundarySpec::uniform(BoundaryCondition::Adiabatic)),
+            ..Default::default()
+        };
+        let mesh = CylindricalMesh::new(1.0, 2.0, 11, 21).unwrap();
+        // A narrow torch in the corner deposits nothing on the interior nodes
+        let torch = PlasmaTorch::new((1.0, 2.0), 1.0, 0.1, 0.01).unwrap();
+        let material = MaterialLibrary::get_material("Carbon Steel").unwrap();
+        let physics = PlasmaPhysics::new(vec![torch], material, boundary_conditions).unwrap();
+        let mut solver = HeatSolver::new(SolverMethod::ForwardEuler);
+        let dt = 0.5 * solver.calculate_stable_timestep(&mesh, &physics);
+
+        let run = |solver: &mut HeatSolver, steps: usize| {
+            let mut temperature = mesh.create_temperature_array(600.0);
+            let mut ledger = EnergyLedger::new(&physics, &temperature);
+            for step in 0..steps {
+                solver.solve_time_step(&mut temperature, &mesh, &physics, dt).unwrap();
+                ledger.record_step(&mesh, &physics, solver, &temperature, dt, 0.0);
+                ledger.snapshot((step + 1) as f64 * dt);
+            }
+            ledger.entries.last().unwrap().clone()
+        };
+
+        // Without sources nothing is stored or lost
+        let entry = run(&mut solver, 5);
+        assert!(entry.total_input() < 1e-6);
+        assert!(entry.sensible_heat.abs() < 1e-6 && entry.residual.abs() < 1e-6);
+
+        // A uniform source is stored completely, with temperature-dependent properties
+        solver.volumetric_heat_source = Some(Array2::from_elem((mesh.nr, mesh.nz), 1.0e7));
+        let entry = run(&mut solver, 20);
+        assert!(entry.coupled_sources > 0.0);
+        assert!(entry.total_loss().abs() < 1e-6 * entry.total_input());
+        assert!(entry.residual.abs() < 1e-6 * entry.total_input());
+    }
+}
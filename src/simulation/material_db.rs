@@ -0,0 +1,309 @@
+//! Material database files
+//!
+//! Materials are defined in JSON or TOML files holding a `materials` list with
+//! the full [`Material`] schema, including formula and table properties:
+//!
+//! ```toml
+//! [[materials]]
+//! id = "slag_a"
+//! name = "Slag A"
+//! density = 2900.0
+//! emissivity = 0.85
+//! thermal_conductivity = { Table = [[300.0, 1.2], [1800.0, 2.4]] }
+//! specific_heat = { Formula = "900.0 + 0.1 * (T - 273.15)" }
+//! ```
+//!
+//! The bundled default database is compiled into the library. User
+//! directories listed in the `PLASMA_MATERIALS_PATH` environment variable
+//! are loaded on first use, and further files or directories can be added at
+//! runtime. A material with the same name or ID as an existing one replaces
+//! it, so user files can override bundled definitions.
+
+use super::materials::Material;
+use crate::errors::{Result, SimulationError};
+use std::path::Path;
+use std::sync::{OnceLock, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Environment variable listing user material directories
+pub const MATERIALS_PATH_VAR: &str = "PLASMA_MATERIALS_PATH";
+
+/// Bundled default material database
+const BUNDLED_DATABASE: &str = include_str!("../../data/materials.json");
+
+/// Contents of a material database file
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct MaterialFile {
+    pub materials: Vec<Material>,
+}
+
+/// Collection of materials with lookups by name or ID
+#[derive(Debug, Clone, Default)]
+pub struct MaterialDatabase {
+    materials: Vec<Material>,
+}
+
+impl MaterialDatabase {
+    /// Create an empty database
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the bundled default database
+    pub fn bundled() -> Result<Self> {
+        let mut database = Self::new();
+        database.load_str(BUNDLED_DATABASE, "json", "bundled database")?;
+        Ok(database)
+    }
+
+    /// Check a material definition before it is added
+    fn validate_material(material: &Material) -> Result<()> {
+        material.validate().map_err(|error| SimulationError::MaterialError {
+            material: material.name.clone(),
+            property: "definition".to_string(),
+            details: error.to_string(),
+        })?;
+        if let Some(ref id) = material.id {
+            crate::errors::validation::validate_non_empty_string(id, "material ID")?;
+        }
+        Ok(())
+    }
+
+    /// Add a validated material, replacing any material with the same name or ID
+    fn replace(&mut self, material: Material) {
+        self.materials.retain(|existing| {
+            existing.name != material.name && (material.id.is_none() || existing.id != material.id)
+        });
+        self.materials.push(material);
+    }
+
+    /// Add a material after validation, replacing any material with the same name or ID
+    pub fn insert(&mut self, material: Material) -> Result<()> {
+        Self::validate_material(&material)?;
+        self.replace(material);
+        Ok(())
+    }
+
+    /// Add materials in order, all or none
+    ///
+    /// Every material is validated before the first one is added, so an
+    /// invalid entry leaves the database unchanged. Returns the number of
+    /// materials added.
+    pub fn extend(&mut self, materials: Vec<Material>) -> Result<usize> {
+        materials.iter().try_for_each(Self::validate_material)?;
+        let count = materials.len();
+        for material in materials {
+            self.replace(material);
+        }
+        Ok(count)
+    }
+
+    /// Parse the materials of a JSON or TOML string without adding them
+    pub fn parse_str(contents: &str, format: &str, source: &str) -> Result<Vec<Material>> {
+        let parse_error = |details: String| SimulationError::MaterialError {
+            material: source.to_string(),
+            property: "database file".to_string(),
+            details,
+        };
+
+        let file: MaterialFile = match format {
+            "json" => serde_json::from_str(contents).map_err(|error| parse_error(error.to_string()))?,
+            "toml" => toml::from_str(contents).map_err(|error| parse_error(error.to_string()))?,
+            other => return Err(parse_error(format!("Unsupported file format '{}', expected json or toml", other))),
+        };
+        Ok(file.materials)
+    }
+
+    /// Read the materials of a `.json` or `.toml` file without adding them
+    pub fn read_file(path: &Path) -> Result<Vec<Material>> {
+        let format = path.extension().and_then(|extension| extension.to_str()).unwrap_or("").to_lowercase();
+        let contents = std::fs::read_to_string(path)?;
+        Self::parse_str(&contents, &format, &path.display().to_string())
+    }
+
+    /// Read the materials of every `.json` and `.toml` file in a directory,
+    /// in file name order, without adding them
+    pub fn read_directory(directory: &Path) -> Result<Vec<Material>> {
+        let mut paths: Vec<_> = std::fs::read_dir(directory)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file()
+                    && matches!(path.extension().and_then(|extension| extension.to_str()), Some("json" | "toml"))
+            })
+            .collect();
+        paths.sort();
+
+        let mut materials = Vec::new();
+        for path in paths {
+            materials.extend(Self::read_file(&path)?);
+        }
+        Ok(materials)
+    }
+
+    /// Parse materials from a JSON or TOML string and add them, all or none
+    ///
+    /// Returns the number of materials added.
+    pub fn load_str(&mut self, contents: &str, format: &str, source: &str) -> Result<usize> {
+        self.extend(Self::parse_str(contents, format, source)?)
+    }
+
+    /// Load a `.json` or `.toml` material file, all or none
+    pub fn load_file(&mut self, path: &Path) -> Result<usize> {
+        self.extend(Self::read_file(path)?)
+    }
+
+    /// Load every `.json` and `.toml` file in a directory, all or none
+    pub fn load_directory(&mut self, directory: &Path) -> Result<usize> {
+        self.extend(Self::read_directory(directory)?)
+    }
+
+    /// Look up a material by name or ID
+    pub fn get(&self, key: &str) -> Option<&Material> {
+        self.materials
+            .iter()
+            .rev()
+            .find(|material| material.name == key || material.id.as_deref() == Some(key))
+    }
+
+    /// Check whether a material with this name or ID exists
+    pub fn contains(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Material names in insertion order
+    pub fn names(&self) -> Vec<String> {
+        self.materials.iter().map(|material| material.name.clone()).collect()
+    }
+
+    /// All materials in insertion order
+    pub fn materials(&self) -> &[Material] {
+        &self.materials
+    }
+}
+
+/// Process-wide database: the bundled materials plus the user directories
+/// from `PLASMA_MATERIALS_PATH`
+fn global() -> &'static RwLock<MaterialDatabase> {
+    static DATABASE: OnceLock<RwLock<MaterialDatabase>> = OnceLock::new();
+    DATABASE.get_or_init(|| {
+        let mut database = MaterialDatabase::bundled().expect("bundled material database is valid");
+
+        if let Some(paths) = std::env::var_os(MATERIALS_PATH_VAR) {
+            for directory in std::env::split_paths(&paths) {
+                match database.load_directory(&directory) {
+                    Ok(count) => log::info!("Loaded {} materials from {}", count, directory.display()),
+                    Err(error) => log::warn!("Skipping material directory {}: {}", directory.display(), error),
+                }
+            }
+        }
+
+        RwLock::new(database)
+    })
+}
+
+/// Read access to the process-wide material database
+pub fn database() -> RwLockReadGuard<'static, MaterialDatabase> {
+    global().read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Write access to the process-wide material database
+pub fn database_mut() -> RwLockWriteGuard<'static, MaterialDatabase> {
+    global().write().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::materials::Property;
+
+    const TOML_ALLOYS: &str = r#"
+[[materials]]
+id = "alloy_x"
+name = "Alloy X"
+density = 8100.0
+emissivity = 0.6
+melting_point = 1650.0
+thermal_conductivity = { Table = [[300.0, 12.0], [1300.0, 28.0]] }
+specific_heat = { Formula = "450.0 + 0.2 * (T - 273.15)" }
+"#;
+
+    #[test]
+    fn test_bundled_database() {
+        let database = MaterialDatabase::bundled().unwrap();
+        assert_eq!(database.names().len(), 11);
+
+        let steel = database.get("Carbon Steel").unwrap();
+        assert_eq!(database.get("carbon_steel").unwrap().name, steel.name);
+        assert!(steel.elastic.is_some());
+        assert!(database.get("Medical Waste").unwrap().reactions.len() == 2);
+        assert!(database.get("Wood").unwrap().reactions.is_empty());
+        assert!(database.get("Graphite").unwrap().is_anisotropic());
+        assert!(database.get("Unobtainium").is_none());
+    }
+
+    #[test]
+    fn test_load_toml_and_json() {
+        let mut database = MaterialDatabase::new();
+        assert_eq!(database.load_str(TOML_ALLOYS, "toml", "alloys.toml").unwrap(), 1);
+
+        let alloy = database.get("alloy_x").unwrap();
+        assert_eq!(alloy.name, "Alloy X");
+        assert!(matches!(alloy.thermal_conductivity, Property::Table(ref table) if table.len() == 2));
+        assert_eq!(alloy.get_thermal_conductivity(800.0, None).unwrap(), 20.0);
+
+        // A later definition with the same ID replaces the earlier one
+        let json = r#"{"materials": [{"id": "alloy_x", "name": "Alloy X (rev 2)", "density": 8200.0,
+            "emissivity": 0.6, "thermal_conductivity": {"Constant": 15.0}, "specific_heat": {"Constant": 480.0}}]}"#;
+        database.load_str(json, "json", "alloys.json").unwrap();
+        assert_eq!(database.names(), vec!["Alloy X (rev 2)".to_string()]);
+        assert_eq!(database.get("alloy_x").unwrap().get_density(300.0, None).unwrap(), 8200.0);
+
+        assert!(database.load_str(TOML_ALLOYS, "yaml", "alloys.yaml").is_err());
+    }
+
+    #[test]
+    fn test_invalid_materials_are_rejected() {
+        let mut database = MaterialDatabase::new();
+        let negative_density = TOML_ALLOYS.replace("density = 8100.0", "density = -1.0");
+        assert!(database.load_str(&negative_density, "toml", "bad.toml").is_err());
+
+        let unsorted_table = TOML_ALLOYS.replace("[[300.0, 12.0], [1300.0, 28.0]]", "[[1300.0, 28.0], [300.0, 12.0]]");
+        assert!(database.load_str(&unsorted_table, "toml", "bad.toml").is_err());
+
+        assert!(database.load_str("materials = 3", "toml", "bad.toml").is_err());
+        assert!(database.names().is_empty());
+
+        let directory = std::env::temp_dir().join(format!("plasma_materials_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("alloys.toml"), TOML_ALLOYS).unwrap();
+        std::fs::write(directory.join("notes.txt"), "not a material file").unwrap();
+        assert_eq!(database.load_directory(&directory).unwrap(), 1);
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_failed_load_leaves_database_unchanged() {
+        let mut database = MaterialDatabase::new();
+        database.load_str(TOML_ALLOYS, "toml", "alloys.toml").unwrap();
+        let before = database.names();
+
+        // An invalid second entry rejects the whole file
+        let second = TOML_ALLOYS
+            .replace("alloy_x", "alloy_y")
+            .replace("Alloy X", "Alloy Y")
+            .replace("density = 8100.0", "density = -1.0");
+        let file = format!("{}{}", TOML_ALLOYS.replace("8100.0", "9000.0"), second);
+        assert!(database.load_str(&file, "toml", "mixed.toml").is_err());
+        assert_eq!(database.names(), before);
+        assert_eq!(database.get("alloy_x").unwrap().get_density(300.0, None).unwrap(), 8100.0);
+
+        // A bad file rejects the whole directory, including files before it
+        let directory = std::env::temp_dir().join(format!("plasma_materials_partial_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let good = TOML_ALLOYS.replace("Alloy X", "Alloy Z").replace("alloy_x", "alloy_z");
+        std::fs::write(directory.join("a_good.toml"), good).unwrap();
+        std::fs::write(directory.join("b_bad.json"), "{\"materials\": 3}").unwrap();
+        assert!(database.load_directory(&directory).is_err());
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(database.names(), before);
+    }
+}
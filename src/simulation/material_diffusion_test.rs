@@ -11,11 +11,20 @@ mod material_diffusion_tests {
     use crate::simulation::{
         solver::{HeatSolver, SolverMethod},
         mesh::CylindricalMesh,
-        materials::MaterialLibrary,
+        materials::{Material, MaterialLibrary},
         physics::{PlasmaPhysics, PlasmaTorch, BoundaryConditions},
+        property_table::FormulaTabulation,
     };
+    use crate::formula::engine::FormulaEngine;
     use ndarray::Array2;
     
+    /// Library material with its formulas tabulated, as the simulation engine runs it
+    fn tabulated_material(name: &str) -> Material {
+        let mut material = MaterialLibrary::get_material(name).unwrap();
+        material.tabulate_formulas(&FormulaTabulation::default(), &mut FormulaEngine::new());
+        material
+    }
+    
     /// Helper function to calculate the radial distance where temperature drops to a threshold
     fn calculate_heat_spread_distance(
         temperature: &Array2<f64>,
@@ -74,7 +83,7 @@ mod material_diffusion_tests {
     fn calculate_thermal_diffusivity(physics: &PlasmaPhysics, temperature: f64) -> f64 {
         let k = physics.get_thermal_conductivity(temperature);
         let cp = physics.get_specific_heat(temperature);
-        let rho = physics.get_density();
+        let rho = physics.get_density(temperature);
         k / (rho * cp)
     }
     
@@ -113,7 +122,7 @@ mod material_diffusion_tests {
         
         // ===== Test 1: Aluminum (fastest diffusion) =====
         println!("--- Material 1: Aluminum ---");
-        let aluminum = MaterialLibrary::get_material("Aluminum").unwrap();
+        let aluminum = tabulated_material("Aluminum");
         let torch_al = PlasmaTorch::new((torch_r, torch_z), torch_power, torch_efficiency, torch_sigma).unwrap();
         let physics_al = PlasmaPhysics::new(vec![torch_al], aluminum, bc.clone()).unwrap();
         
@@ -131,7 +140,7 @@ mod material_diffusion_tests {
         
         // ===== Test 2: Steel (medium diffusion) =====
         println!("--- Material 2: Carbon Steel ---");
-        let steel = MaterialLibrary::get_material("Carbon Steel").unwrap();
+        let steel = tabulated_material("Carbon Steel");
         let torch_steel = PlasmaTorch::new((torch_r, torch_z), torch_power, torch_efficiency, torch_sigma).unwrap();
         let physics_steel = PlasmaPhysics::new(vec![torch_steel], steel, bc.clone()).unwrap();
         
@@ -149,7 +158,7 @@ mod material_diffusion_tests {
         
         // ===== Test 3: Concrete (slowest diffusion) =====
         println!("--- Material 3: Concrete ---");
-        let concrete = MaterialLibrary::get_material("Concrete").unwrap();
+        let concrete = tabulated_material("Concrete");
         let torch_concrete = PlasmaTorch::new((torch_r, torch_z), torch_power, torch_efficiency, torch_sigma).unwrap();
         let physics_concrete = PlasmaPhysics::new(vec![torch_concrete], concrete, bc).unwrap();
         
@@ -275,7 +284,7 @@ mod material_diffusion_tests {
         let torch = PlasmaTorch::new((0.0, 1.0), 100.0, 0.8, 0.1).unwrap();
         
         // Test Aluminum
-        let aluminum = MaterialLibrary::get_material("Aluminum").unwrap();
+        let aluminum = tabulated_material("Aluminum");
         let physics_al = PlasmaPhysics::new(vec![torch.clone()], aluminum, bc.clone()).unwrap();
         let alpha_al = calculate_thermal_diffusivity(&physics_al, reference_temp);
         
@@ -284,7 +293,7 @@ mod material_diffusion_tests {
         println!("  Expected: ~9.7×10⁻⁵ m²/s");
         
         // Test Steel
-        let steel = MaterialLibrary::get_material("Carbon Steel").unwrap();
+        let steel = tabulated_material("Carbon Steel");
         let physics_steel = PlasmaPhysics::new(vec![torch.clone()], steel, bc.clone()).unwrap();
         let alpha_steel = calculate_thermal_diffusivity(&physics_steel, reference_temp);
         
@@ -293,7 +302,7 @@ mod material_diffusion_tests {
         println!("  Expected: ~1.2×10⁻⁵ m²/s");
         
         // Test Concrete
-        let concrete = MaterialLibrary::get_material("Concrete").unwrap();
+        let concrete = tabulated_material("Concrete");
         let physics_concrete = PlasmaPhysics::new(vec![torch], concrete, bc).unwrap();
         let alpha_concrete = calculate_thermal_diffusivity(&physics_concrete, reference_temp);
         
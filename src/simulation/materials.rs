@@ -5,13 +5,23 @@
 
 use crate::errors::{Result, SimulationError};
 use crate::formula::engine::FormulaEngine;
+use super::property_table::{
+    evaluate_points, evaluate_points_with_derivative, tabulate_formula, validate_points, Extrapolation, FormulaTabulation,
+    Interpolation, PropertyScope, PropertyTable,
+};
+use super::reactions::Reaction;
+use crate::units::Dimension;
 
 /// Material property types
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum Property {
     Constant(f64),
     Formula(String),  // Rhai formula string
-    Table(Vec<(f64, f64)>), // (temperature, value) pairs
+    Table(Vec<(f64, f64)>), // (temperature, value) pairs, linear and clamped
+    /// Table with selectable interpolation and extrapolation
+    Tabulated(PropertyTable),
+    /// Formula sampled into a table, evaluated directly outside the table range
+    FormulaTable { formula: String, table: PropertyTable },
 }
 
 impl Property {
@@ -29,53 +39,54 @@ impl Property {
                     })
                 }
             }
-            Property::Formula(formula) => {
-                match formula_engine {
-                    Some(engine) => engine.evaluate_formula(formula, temperature),
-                    None => Err(SimulationError::MaterialError {
-                        material: "unknown".to_string(),
-                        property: "property".to_string(),
-                        details: "Formula engine not available for formula evaluation".to_string(),
-                    })
-                }
+            Property::Formula(formula) => evaluate_formula(formula, temperature, formula_engine),
+            Property::Table(table) => {
+                evaluate_points(table, Interpolation::Linear, Extrapolation::Clamp, temperature)
             }
+            Property::Tabulated(table) => table.evaluate(temperature),
+            Property::FormulaTable { formula, table } => match (table.points.first(), table.points.last()) {
+                (Some(first), Some(last)) if (first.0..=last.0).contains(&temperature) => table.evaluate(temperature),
+                _ => evaluate_formula(formula, temperature, formula_engine),
+            },
+        }
+    }
+    
+    /// Evaluate property and its temperature derivative at given temperature
+    ///
+    /// Formulas are differentiated exactly with dual numbers and tables give
+    /// the slope of their interpolant, so implicit solvers can linearize
+    /// temperature-dependent properties.
+    pub fn evaluate_with_derivative(&self, temperature: f64, formula_engine: Option<&mut FormulaEngine>) -> Result<(f64, f64)> {
+        match self {
+            Property::Constant(_) => Ok((self.evaluate(temperature, None)?, 0.0)),
+            Property::Formula(formula) => evaluate_formula_with_derivative(formula, temperature, formula_engine),
             Property::Table(table) => {
-                if table.is_empty() {
-                    return Err(SimulationError::MaterialError {
-                        material: "unknown".to_string(),
-                        property: "property".to_string(),
-                        details: "Empty property table".to_string(),
-                    });
-                }
-                
-                // Linear interpolation
-                if table.len() == 1 {
-                    return Ok(table[0].1);
-                }
-                
-                // Find the appropriate interval
-                if temperature <= table[0].0 {
-                    return Ok(table[0].1);
-                }
-                
-                if temperature >= table[table.len() - 1].0 {
-                    return Ok(table[table.len() - 1].1);
-                }
-                
-                for i in 0..table.len() - 1 {
-                    if temperature >= table[i].0 && temperature <= table[i + 1].0 {
-                        let t1 = table[i].0;
-                        let t2 = table[i + 1].0;
-                        let v1 = table[i].1;
-                        let v2 = table[i + 1].1;
-                        
-                        let interpolated = v1 + (v2 - v1) * (temperature - t1) / (t2 - t1);
-                        return Ok(interpolated);
-                    }
-                }
-                
-                Ok(table[0].1) // Fallback
+                evaluate_points_with_derivative(table, Interpolation::Linear, Extrapolation::Clamp, temperature)
+            }
+            Property::Tabulated(table) => table.evaluate_with_derivative(temperature),
+            Property::FormulaTable { formula, table } => match (table.points.first(), table.points.last()) {
+                (Some(first), Some(last)) if (first.0..=last.0).contains(&temperature) => table.evaluate_with_derivative(temperature),
+                _ => evaluate_formula_with_derivative(formula, temperature, formula_engine),
+            },
+        }
+    }
+    
+    /// Check the unit annotation of a formula property against its dimension
+    pub fn check_dimension(&self, expected: Dimension, property_name: &str) -> Result<()> {
+        match self {
+            Property::Formula(formula) | Property::FormulaTable { formula, .. } => {
+                crate::units::check_formula_dimension(formula, expected, property_name)
             }
+            _ => Ok(()),
+        }
+    }
+    
+    /// Table points of a tabulated property
+    pub fn table_points(&self) -> Option<&[(f64, f64)]> {
+        match self {
+            Property::Table(points) => Some(points),
+            Property::Tabulated(table) => Some(&table.points),
+            _ => None,
         }
     }
     
@@ -110,60 +121,249 @@ impl Property {
                 // Formula validation will be done by the formula engine
                 Ok(())
             }
-            Property::Table(table) => {
-                if table.is_empty() {
-                    return Err(SimulationError::MaterialError {
-                        material: "unknown".to_string(),
-                        property: property_name.to_string(),
-                        details: "Empty property table".to_string(),
-                    });
-                }
-                
-                // Check for valid temperature ordering
-                for i in 1..table.len() {
-                    if table[i].0 <= table[i - 1].0 {
-                        return Err(SimulationError::MaterialError {
-                            material: "unknown".to_string(),
-                            property: property_name.to_string(),
-                            details: format!("Temperature values must be in ascending order at index {}", i),
-                        });
-                    }
-                }
-                
-                // Check for valid property values
-                for (i, (temp, value)) in table.iter().enumerate() {
-                    if !temp.is_finite() || !value.is_finite() {
-                        return Err(SimulationError::MaterialError {
-                            material: "unknown".to_string(),
-                            property: property_name.to_string(),
-                            details: format!("Non-finite values in table at index {}", i),
-                        });
-                    }
-                    if *value < 0.0 {
-                        return Err(SimulationError::MaterialError {
-                            material: "unknown".to_string(),
-                            property: property_name.to_string(),
-                            details: format!("Negative property value in table at index {}: {}", i, value),
-                        });
-                    }
-                }
-                
-                Ok(())
+            Property::Table(table) => validate_points(table, property_name),
+            Property::Tabulated(table) => table.validate(property_name),
+            Property::FormulaTable { formula, table } => {
+                Property::Formula(formula.clone()).validate(property_name)?;
+                table.validate(property_name)
+            }
+        }
+    }
+}
+
+fn evaluate_formula(formula: &str, temperature: f64, formula_engine: Option<&mut FormulaEngine>) -> Result<f64> {
+    match formula_engine {
+        Some(engine) => engine.evaluate_formula(formula, temperature),
+        None => Err(missing_formula_engine()),
+    }
+}
+
+fn evaluate_formula_with_derivative(
+    formula: &str,
+    temperature: f64,
+    formula_engine: Option<&mut FormulaEngine>,
+) -> Result<(f64, f64)> {
+    match formula_engine {
+        Some(engine) => engine.evaluate_with_derivative(formula, temperature),
+        None => Err(missing_formula_engine()),
+    }
+}
+
+fn missing_formula_engine() -> SimulationError {
+    SimulationError::MaterialError {
+        material: "unknown".to_string(),
+        property: "property".to_string(),
+        details: "Formula engine not available for formula evaluation".to_string(),
+    }
+}
+
+/// Linear thermoelastic properties
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ElasticProperties {
+    /// Young's modulus in Pa
+    pub youngs_modulus: f64,
+    /// Poisson ratio
+    pub poisson_ratio: f64,
+    /// Linear thermal expansion coefficient in 1/K
+    pub thermal_expansion: f64,
+}
+
+impl ElasticProperties {
+    /// Validate elastic constants
+    pub fn validate(&self) -> Result<()> {
+        crate::errors::validation::validate_positive(self.youngs_modulus, "Young's modulus")?;
+        crate::errors::validation::validate_range(self.poisson_ratio, 0.0, 0.499, "Poisson ratio")?;
+        crate::errors::validation::validate_positive(self.thermal_expansion, "thermal expansion coefficient")?;
+        Ok(())
+    }
+}
+
+/// Thermal properties of one phase
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PhaseSet {
+    pub thermal_conductivity: Property, // W/(m·K)
+    pub specific_heat: Property,        // J/(kg·K)
+}
+
+impl PhaseSet {
+    /// Validate both properties, prefixing names with the phase
+    pub fn validate(&self, phase: &str) -> Result<()> {
+        self.thermal_conductivity.validate(&format!("{}_thermal_conductivity", phase))?;
+        self.thermal_conductivity.check_dimension(Dimension::THERMAL_CONDUCTIVITY, &format!("{} thermal conductivity", phase))?;
+        self.specific_heat.validate(&format!("{}_specific_heat", phase))?;
+        self.specific_heat.check_dimension(Dimension::SPECIFIC_HEAT, &format!("{} specific heat", phase))?;
+        Ok(())
+    }
+}
+
+/// Liquid and gas property sets with their transition ranges
+///
+/// The material's own `thermal_conductivity` and `specific_heat` are the
+/// solid properties. Between solidus and liquidus (the mushy zone) the liquid
+/// fraction rises linearly, and likewise the vapour fraction across the
+/// vaporization range. Effective properties are the fraction-weighted sum of
+/// the phase properties.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PhaseProperties {
+    /// Temperature where melting starts in K
+    pub solidus: f64,
+    /// Temperature where the material is fully liquid in K
+    pub liquidus: f64,
+    pub liquid: PhaseSet,
+    /// (start, end) of vaporization in K, required with `gas`
+    #[serde(default)]
+    pub vaporization_range: Option<(f64, f64)>,
+    #[serde(default)]
+    pub gas: Option<PhaseSet>,
+}
+
+impl PhaseProperties {
+    /// Validate the transition temperatures and phase properties
+    pub fn validate(&self) -> Result<()> {
+        crate::errors::validation::validate_positive(self.solidus, "solidus temperature")?;
+        crate::errors::validation::validate_range(self.liquidus, self.solidus, f64::INFINITY, "liquidus temperature")?;
+        self.liquid.validate("liquid")?;
+
+        match (self.vaporization_range, &self.gas) {
+            (Some((start, end)), Some(gas)) => {
+                crate::errors::validation::validate_range(start, self.liquidus, f64::INFINITY, "vaporization start temperature")?;
+                crate::errors::validation::validate_range(end, start, f64::INFINITY, "vaporization end temperature")?;
+                gas.validate("gas")
             }
+            (None, None) => Ok(()),
+            _ => Err(SimulationError::MaterialError {
+                material: "unknown".to_string(),
+                property: "gas".to_string(),
+                details: "Gas properties and vaporization range must be given together".to_string(),
+            }),
         }
     }
+
+    /// Liquid and vapour mass fractions at temperature
+    pub fn fractions(&self, temperature: f64) -> (f64, f64) {
+        let vapour = match self.vaporization_range {
+            Some((start, end)) if self.gas.is_some() => transition_fraction(temperature, start, end),
+            _ => 0.0,
+        };
+        let melted = transition_fraction(temperature, self.solidus, self.liquidus);
+        (melted * (1.0 - vapour), vapour)
+    }
+
+    /// Temperature derivatives of the liquid and vapour fractions in 1/K
+    pub fn fraction_slopes(&self, temperature: f64) -> (f64, f64) {
+        let (vapour, vapour_slope) = match self.vaporization_range {
+            Some((start, end)) if self.gas.is_some() => {
+                (transition_fraction(temperature, start, end), transition_slope(temperature, start, end))
+            }
+            _ => (0.0, 0.0),
+        };
+        let melted = transition_fraction(temperature, self.solidus, self.liquidus);
+        let melted_slope = transition_slope(temperature, self.solidus, self.liquidus);
+        (melted_slope * (1.0 - vapour) - melted * vapour_slope, vapour_slope)
+    }
+}
+
+/// Read a property given either in full or as a plain number (a constant),
+/// so configurations from before density and emissivity became properties
+/// still load
+fn property_or_number<'de, D>(deserializer: D) -> std::result::Result<Property, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum PropertyOrNumber {
+        Number(f64),
+        Property(Property),
+    }
+    
+    Ok(match serde::Deserialize::deserialize(deserializer)? {
+        PropertyOrNumber::Number(value) => Property::Constant(value),
+        PropertyOrNumber::Property(property) => property,
+    })
+}
+
+/// Constant or tabulated values of a property (formulas are checked on evaluation)
+fn known_values(property: &Property) -> Vec<f64> {
+    match property {
+        Property::Constant(value) => vec![*value],
+        _ => property.table_points().map_or(Vec::new(), |points| points.iter().map(|&(_, value)| value).collect()),
+    }
+}
+
+fn validate_density(density: &Property) -> Result<()> {
+    density.validate("density")?;
+    for value in known_values(density) {
+        crate::errors::validation::validate_positive(value, "density")?;
+    }
+    Ok(())
+}
+
+fn validate_emissivity(emissivity: &Property) -> Result<()> {
+    emissivity.validate("emissivity")?;
+    for value in known_values(emissivity) {
+        crate::errors::validation::validate_range(value, 0.0, 1.0, "emissivity")?;
+    }
+    Ok(())
+}
+
+/// Fraction transformed across a linear transition range (a step if the range is empty)
+fn transition_fraction(temperature: f64, start: f64, end: f64) -> f64 {
+    if end > start {
+        ((temperature - start) / (end - start)).clamp(0.0, 1.0)
+    } else if temperature >= start {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Slope of [`transition_fraction`], zero outside the open transition range
+fn transition_slope(temperature: f64, start: f64, end: f64) -> f64 {
+    if end > start && temperature > start && temperature < end {
+        1.0 / (end - start)
+    } else {
+        0.0
+    }
 }
 
 /// Material definition
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Material {
+    /// Stable identifier for lookups independent of the display name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     pub name: String,
-    pub density: f64,                    // kg/m³
-    pub thermal_conductivity: Property,  // W/(m·K)
+    /// Density in kg/m³ (plain numbers are read as constants)
+    #[serde(deserialize_with = "property_or_number")]
+    pub density: Property,
+    pub thermal_conductivity: Property,  // W/(m·K), radial (or isotropic)
+    /// Axial thermal conductivity in W/(m·K) for anisotropic materials
+    /// (`thermal_conductivity` applies in both directions if not set)
+    #[serde(default)]
+    pub axial_thermal_conductivity: Option<Property>,
     pub specific_heat: Property,         // J/(kg·K)
-    pub emissivity: f64,                // 0.0 to 1.0
+    /// Surface emissivity, 0.0 to 1.0 (plain numbers are read as constants)
+    #[serde(deserialize_with = "property_or_number")]
+    pub emissivity: Property,
     pub melting_point: Option<f64>,     // K
     pub latent_heat_fusion: Option<f64>, // J/kg
+    /// Radiative absorption coefficient for participating media in 1/m
+    #[serde(default)]
+    pub absorption_coefficient: Option<f64>,
+    /// Electrical conductivity in S/m, used by Joule heating
+    #[serde(default)]
+    pub electrical_conductivity: Option<Property>,
+    /// Chemical reactions (pyrolysis, combustion, gasification)
+    #[serde(default)]
+    pub reactions: Vec<Reaction>,
+    /// Thermoelastic properties, used by the thermal stress post-processor
+    #[serde(default)]
+    pub elastic: Option<ElasticProperties>,
+    /// Liquid and gas properties blended in by phase fraction (the base
+    /// properties apply to all states if not set)
+    #[serde(default)]
+    pub phases: Option<PhaseProperties>,
 }
 
 impl Material {
@@ -175,13 +375,20 @@ impl Material {
         crate::errors::validation::validate_range(emissivity, 0.0, 1.0, "emissivity")?;
         
         Ok(Self {
+            id: None,
             name,
-            density,
+            density: Property::Constant(density),
             thermal_conductivity: Property::Constant(50.0), // Default value
+            axial_thermal_conductivity: None,
             specific_heat: Property::Constant(500.0),       // Default value
-            emissivity,
+            emissivity: Property::Constant(emissivity),
             melting_point: None,
             latent_heat_fusion: None,
+            absorption_coefficient: None,
+            electrical_conductivity: None,
+            reactions: Vec::new(),
+            elastic: None,
+            phases: None,
         })
     }
     
@@ -213,24 +420,257 @@ impl Material {
         specific_heat.validate("specific_heat")?;
         
         Ok(Self {
+            id: None,
             name,
-            density,
+            density: Property::Constant(density),
             thermal_conductivity,
+            axial_thermal_conductivity: None,
             specific_heat,
-            emissivity,
+            emissivity: Property::Constant(emissivity),
             melting_point,
             latent_heat_fusion,
+            absorption_coefficient: None,
+            electrical_conductivity: None,
+            reactions: Vec::new(),
+            elastic: None,
+            phases: None,
         })
     }
     
-    /// Get thermal conductivity at temperature
+    /// Set the radiative absorption coefficient (1/m) for participating media
+    pub fn with_absorption_coefficient(mut self, absorption_coefficient: f64) -> Result<Self> {
+        crate::errors::validation::validate_positive(absorption_coefficient, "absorption coefficient")?;
+        self.absorption_coefficient = Some(absorption_coefficient);
+        Ok(self)
+    }
+    
+    /// Set the electrical conductivity (S/m) used by Joule heating
+    pub fn with_electrical_conductivity(mut self, electrical_conductivity: Property) -> Result<Self> {
+        electrical_conductivity.validate("electrical_conductivity")?;
+        self.electrical_conductivity = Some(electrical_conductivity);
+        Ok(self)
+    }
+    
+    /// Add a chemical reaction
+    pub fn with_reaction(mut self, reaction: Reaction) -> Result<Self> {
+        reaction.validate()?;
+        self.reactions.push(reaction);
+        Ok(self)
+    }
+    
+    /// Set a separate axial thermal conductivity, making the material anisotropic
+    pub fn with_axial_thermal_conductivity(mut self, axial_thermal_conductivity: Property) -> Result<Self> {
+        axial_thermal_conductivity.validate("axial_thermal_conductivity")?;
+        self.axial_thermal_conductivity = Some(axial_thermal_conductivity);
+        Ok(self)
+    }
+    
+    /// Set the thermoelastic properties (Pa, -, 1/K)
+    pub fn with_elastic_properties(mut self, youngs_modulus: f64, poisson_ratio: f64, thermal_expansion: f64) -> Result<Self> {
+        let elastic = ElasticProperties { youngs_modulus, poisson_ratio, thermal_expansion };
+        elastic.validate()?;
+        self.elastic = Some(elastic);
+        Ok(self)
+    }
+    
+    /// Set a temperature-dependent density (kg/m³)
+    pub fn with_density(mut self, density: Property) -> Result<Self> {
+        validate_density(&density)?;
+        self.density = density;
+        Ok(self)
+    }
+    
+    /// Set a temperature-dependent emissivity
+    pub fn with_emissivity(mut self, emissivity: Property) -> Result<Self> {
+        validate_emissivity(&emissivity)?;
+        self.emissivity = emissivity;
+        Ok(self)
+    }
+    
+    /// Set liquid and gas property sets with their transition temperatures
+    pub fn with_phase_properties(mut self, phases: PhaseProperties) -> Result<Self> {
+        phases.validate()?;
+        self.phases = Some(phases);
+        Ok(self)
+    }
+    
+    /// Liquid and vapour fractions at temperature from the phase transition ranges
+    ///
+    /// Returns (0.0, 0.0) for materials without phase-specific properties.
+    pub fn phase_fractions(&self, temperature: f64) -> (f64, f64) {
+        self.phases.as_ref().map_or((0.0, 0.0), |phases| phases.fractions(temperature))
+    }
+    
+    /// Blend a solid property with the liquid and gas values by phase fraction
+    fn blend_phases(
+        &self,
+        name: &'static str,
+        solid: &Property,
+        phase_property: fn(&PhaseSet) -> &Property,
+        temperature: f64,
+        mut formula_engine: Option<&mut FormulaEngine>,
+    ) -> Result<f64> {
+        let _scope = PropertyScope::enter(&self.name, name);
+        let (liquid_fraction, vapour_fraction) = self.phase_fractions(temperature);
+        let solid_fraction = 1.0 - liquid_fraction - vapour_fraction;
+        let phases = match self.phases {
+            Some(ref phases) if solid_fraction < 1.0 => phases,
+            _ => return solid.evaluate(temperature, formula_engine),
+        };
+        
+        let mut value = 0.0;
+        if solid_fraction > 0.0 {
+            value += solid_fraction * solid.evaluate(temperature, formula_engine.as_deref_mut())?;
+        }
+        if liquid_fraction > 0.0 {
+            value += liquid_fraction * phase_property(&phases.liquid).evaluate(temperature, formula_engine.as_deref_mut())?;
+        }
+        if let Some(gas) = phases.gas.as_ref().filter(|_| vapour_fraction > 0.0) {
+            value += vapour_fraction * phase_property(gas).evaluate(temperature, formula_engine)?;
+        }
+        Ok(value)
+    }
+    
+    /// Blend a property and its temperature derivative over the phases present
+    ///
+    /// The derivative includes the change of the phase fractions across the
+    /// transition ranges.
+    fn blend_phases_with_derivative(
+        &self,
+        name: &'static str,
+        solid: &Property,
+        phase_property: fn(&PhaseSet) -> &Property,
+        temperature: f64,
+        mut formula_engine: Option<&mut FormulaEngine>,
+    ) -> Result<(f64, f64)> {
+        let _scope = PropertyScope::enter(&self.name, name);
+        let (liquid_fraction, vapour_fraction) = self.phase_fractions(temperature);
+        let solid_fraction = 1.0 - liquid_fraction - vapour_fraction;
+        let phases = match self.phases {
+            Some(ref phases) if solid_fraction < 1.0 => phases,
+            _ => return solid.evaluate_with_derivative(temperature, formula_engine),
+        };
+        let (liquid_slope, vapour_slope) = phases.fraction_slopes(temperature);
+        
+        let (mut value, mut derivative) = (0.0, 0.0);
+        let mut add = |fraction: f64, slope: f64, (phase_value, phase_derivative): (f64, f64)| {
+            value += fraction * phase_value;
+            derivative += slope * phase_value + fraction * phase_derivative;
+        };
+        if solid_fraction > 0.0 {
+            let solid_slope = -liquid_slope - vapour_slope;
+            add(solid_fraction, solid_slope, solid.evaluate_with_derivative(temperature, formula_engine.as_deref_mut())?);
+        }
+        if liquid_fraction > 0.0 {
+            let liquid = phase_property(&phases.liquid).evaluate_with_derivative(temperature, formula_engine.as_deref_mut())?;
+            add(liquid_fraction, liquid_slope, liquid);
+        }
+        if let Some(gas) = phases.gas.as_ref().filter(|_| vapour_fraction > 0.0) {
+            add(vapour_fraction, vapour_slope, phase_property(gas).evaluate_with_derivative(temperature, formula_engine)?);
+        }
+        Ok((value, derivative))
+    }
+    
+    /// Get density at temperature
+    pub fn get_density(&self, temperature: f64, formula_engine: Option<&mut FormulaEngine>) -> Result<f64> {
+        let density = {
+            let _scope = PropertyScope::enter(&self.name, "density");
+            self.density.evaluate(temperature, formula_engine)?
+        };
+        if density > 0.0 {
+            Ok(density)
+        } else {
+            Err(SimulationError::MaterialError {
+                material: self.name.clone(),
+                property: "density".to_string(),
+                details: format!("Non-positive density {} at {} K", density, temperature),
+            })
+        }
+    }
+    
+    /// Get emissivity at temperature
+    pub fn get_emissivity(&self, temperature: f64, formula_engine: Option<&mut FormulaEngine>) -> Result<f64> {
+        let emissivity = {
+            let _scope = PropertyScope::enter(&self.name, "emissivity");
+            self.emissivity.evaluate(temperature, formula_engine)?
+        };
+        if (0.0..=1.0).contains(&emissivity) {
+            Ok(emissivity)
+        } else {
+            Err(SimulationError::MaterialError {
+                material: self.name.clone(),
+                property: "emissivity".to_string(),
+                details: format!("Emissivity {} outside [0, 1] at {} K", emissivity, temperature),
+            })
+        }
+    }
+    
+    /// Get electrical conductivity at temperature
+    pub fn get_electrical_conductivity(&self, temperature: f64, formula_engine: Option<&mut FormulaEngine>) -> Result<f64> {
+        match &self.electrical_conductivity {
+            Some(property) => {
+                let _scope = PropertyScope::enter(&self.name, "electrical conductivity");
+                property.evaluate(temperature, formula_engine)
+            }
+            None => Err(SimulationError::MaterialError {
+                material: self.name.clone(),
+                property: "electrical_conductivity".to_string(),
+                details: "Electrical conductivity not defined".to_string(),
+            }),
+        }
+    }
+    
+    /// Get thermal conductivity at temperature, blended over the phases present
     pub fn get_thermal_conductivity(&self, temperature: f64, formula_engine: Option<&mut FormulaEngine>) -> Result<f64> {
-        self.thermal_conductivity.evaluate(temperature, formula_engine)
+        self.blend_phases("thermal conductivity", &self.thermal_conductivity, |phase| &phase.thermal_conductivity, temperature, formula_engine)
     }
     
-    /// Get specific heat at temperature
+    /// Get thermal conductivity and dk/dT at temperature, blended over the phases present
+    pub fn get_thermal_conductivity_with_derivative(
+        &self,
+        temperature: f64,
+        formula_engine: Option<&mut FormulaEngine>,
+    ) -> Result<(f64, f64)> {
+        self.blend_phases_with_derivative(
+            "thermal conductivity", &self.thermal_conductivity, |phase| &phase.thermal_conductivity, temperature, formula_engine,
+        )
+    }
+    
+    /// Get axial thermal conductivity at temperature
+    ///
+    /// Anisotropy applies to the solid only; liquid and gas are isotropic.
+    pub fn get_axial_thermal_conductivity(&self, temperature: f64, formula_engine: Option<&mut FormulaEngine>) -> Result<f64> {
+        let solid = self.axial_thermal_conductivity.as_ref().unwrap_or(&self.thermal_conductivity);
+        self.blend_phases("axial thermal conductivity", solid, |phase| &phase.thermal_conductivity, temperature, formula_engine)
+    }
+    
+    /// Ratio of axial to radial thermal conductivity at temperature
+    pub fn axial_conductivity_ratio(&self, temperature: f64, mut formula_engine: Option<&mut FormulaEngine>) -> Result<f64> {
+        if !self.is_anisotropic() {
+            return Ok(1.0);
+        }
+        let k_axial = self.get_axial_thermal_conductivity(temperature, formula_engine.as_deref_mut())?;
+        let k_radial = self.get_thermal_conductivity(temperature, formula_engine)?;
+        Ok(k_axial / k_radial)
+    }
+    
+    /// Check if radial and axial conductivities are given separately
+    pub fn is_anisotropic(&self) -> bool {
+        self.axial_thermal_conductivity.is_some()
+    }
+    
+    /// Get specific heat at temperature, blended over the phases present
     pub fn get_specific_heat(&self, temperature: f64, formula_engine: Option<&mut FormulaEngine>) -> Result<f64> {
-        self.specific_heat.evaluate(temperature, formula_engine)
+        self.blend_phases("specific heat", &self.specific_heat, |phase| &phase.specific_heat, temperature, formula_engine)
+    }
+    
+    /// Get specific heat and dcp/dT at temperature, blended over the phases present
+    pub fn get_specific_heat_with_derivative(
+        &self,
+        temperature: f64,
+        formula_engine: Option<&mut FormulaEngine>,
+    ) -> Result<(f64, f64)> {
+        self.blend_phases_with_derivative("specific heat", &self.specific_heat, |phase| &phase.specific_heat, temperature, formula_engine)
     }
     
     /// Calculate effective specific heat including phase change effects (placeholder)
@@ -242,8 +682,8 @@ impl Material {
     /// Validate all material properties
     pub fn validate(&self) -> Result<()> {
         crate::errors::validation::validate_non_empty_string(&self.name, "material name")?;
-        crate::errors::validation::validate_positive(self.density, "density")?;
-        crate::errors::validation::validate_range(self.emissivity, 0.0, 1.0, "emissivity")?;
+        validate_density(&self.density)?;
+        validate_emissivity(&self.emissivity)?;
         
         if let Some(mp) = self.melting_point {
             crate::errors::validation::validate_positive(mp, "melting point")?;
@@ -253,9 +693,49 @@ impl Material {
             crate::errors::validation::validate_positive(lhf, "latent heat of fusion")?;
         }
         
+        if let Some(kappa) = self.absorption_coefficient {
+            crate::errors::validation::validate_positive(kappa, "absorption coefficient")?;
+        }
+        
+        if let Some(ref sigma) = self.electrical_conductivity {
+            sigma.validate("electrical_conductivity")?;
+        }
+        
+        for reaction in &self.reactions {
+            reaction.validate()?;
+        }
+        
+        if let Some(ref elastic) = self.elastic {
+            elastic.validate()?;
+        }
+        
         self.thermal_conductivity.validate("thermal_conductivity")?;
+        if let Some(ref axial) = self.axial_thermal_conductivity {
+            axial.validate("axial_thermal_conductivity")?;
+        }
         self.specific_heat.validate("specific_heat")?;
         
+        if let Some(ref phases) = self.phases {
+            phases.validate()?;
+        }
+        
+        // Unit-annotated formulas must give the dimension of their property
+        let mut dimensions = vec![
+            (&self.density, Dimension::DENSITY, "density"),
+            (&self.thermal_conductivity, Dimension::THERMAL_CONDUCTIVITY, "thermal conductivity"),
+            (&self.specific_heat, Dimension::SPECIFIC_HEAT, "specific heat"),
+            (&self.emissivity, Dimension::DIMENSIONLESS, "emissivity"),
+        ];
+        if let Some(ref axial) = self.axial_thermal_conductivity {
+            dimensions.push((axial, Dimension::THERMAL_CONDUCTIVITY, "axial thermal conductivity"));
+        }
+        if let Some(ref sigma) = self.electrical_conductivity {
+            dimensions.push((sigma, Dimension::ELECTRICAL_CONDUCTIVITY, "electrical conductivity"));
+        }
+        for (property, dimension, name) in dimensions {
+            property.check_dimension(dimension, name)?;
+        }
+        
         Ok(())
     }
     
@@ -264,19 +744,37 @@ impl Material {
         self.melting_point.is_some() && self.latent_heat_fusion.is_some()
     }
     
+    /// Liquid fraction at temperature, varying linearly across a melting range
+    /// of width `mushy_range` centred on the melting point
+    /// 
+    /// Materials with phase properties use their solidus and liquidus instead,
+    /// counting vapour as fluid. Returns 0.0 for materials without a melting point.
+    pub fn liquid_fraction(&self, temperature: f64, mushy_range: f64) -> f64 {
+        if let Some(ref phases) = self.phases {
+            return transition_fraction(temperature, phases.solidus, phases.liquidus);
+        }
+        match self.melting_point {
+            Some(melting_point) if mushy_range > 0.0 => {
+                ((temperature - (melting_point - 0.5 * mushy_range)) / mushy_range).clamp(0.0, 1.0)
+            }
+            Some(melting_point) if temperature >= melting_point => 1.0,
+            _ => 0.0,
+        }
+    }
+    
     /// Get temperature range for property validity (if using tables)
     pub fn get_temperature_range(&self) -> (Option<f64>, Option<f64>) {
         let mut min_temp = None;
         let mut max_temp = None;
         
-        if let Property::Table(table) = &self.thermal_conductivity {
+        if let Some(table) = self.thermal_conductivity.table_points() {
             if !table.is_empty() {
                 min_temp = Some(table[0].0);
                 max_temp = Some(table[table.len() - 1].0);
             }
         }
         
-        if let Property::Table(table) = &self.specific_heat {
+        if let Some(table) = self.specific_heat.table_points() {
             if !table.is_empty() {
                 let table_min = table[0].0;
                 let table_max = table[table.len() - 1].0;
@@ -295,6 +793,54 @@ impl Material {
         
         (min_temp, max_temp)
     }
+    
+    /// Back formula properties by lookup tables within the tolerance
+    ///
+    /// Tabulated formulas become [`Property::FormulaTable`], so temperatures
+    /// outside the sampled range still evaluate the formula. Formulas that
+    /// fail somewhere in the sampled range, produce negative values or cannot
+    /// meet the tolerance within the point limit are kept and reported in the
+    /// log. Returns the number of properties tabulated.
+    pub fn tabulate_formulas(&mut self, settings: &FormulaTabulation, formula_engine: &mut FormulaEngine) -> usize {
+        let mut properties: Vec<(String, &mut Property)> = vec![
+            ("thermal_conductivity".to_string(), &mut self.thermal_conductivity),
+            ("specific_heat".to_string(), &mut self.specific_heat),
+            ("density".to_string(), &mut self.density),
+            ("emissivity".to_string(), &mut self.emissivity),
+        ];
+        properties.extend(self.axial_thermal_conductivity.as_mut().map(|p| ("axial_thermal_conductivity".to_string(), p)));
+        properties.extend(self.electrical_conductivity.as_mut().map(|p| ("electrical_conductivity".to_string(), p)));
+        if let Some(ref mut phases) = self.phases {
+            for (phase, set) in std::iter::once(("liquid", &mut phases.liquid)).chain(phases.gas.as_mut().map(|gas| ("gas", gas))) {
+                properties.push((format!("{}_thermal_conductivity", phase), &mut set.thermal_conductivity));
+                properties.push((format!("{}_specific_heat", phase), &mut set.specific_heat));
+            }
+        }
+        
+        let mut tabulated = 0;
+        for (name, property) in properties {
+            let Property::Formula(ref formula) = *property else { continue };
+            match tabulate_formula(formula, settings, formula_engine) {
+                Ok(result) if result.max_error > settings.tolerance => log::warn!(
+                    "Keeping formula for {} of {}: table error {:.2e} above tolerance {:.2e} with {} points",
+                    name, self.name, result.max_error, settings.tolerance, result.table.points.len()
+                ),
+                Ok(result) => match result.table.validate(&name) {
+                    Ok(()) => {
+                        log::debug!(
+                            "Tabulated {} of {} with {} points (max error {:.2e})",
+                            name, self.name, result.table.points.len(), result.max_error
+                        );
+                        *property = Property::FormulaTable { formula: formula.clone(), table: result.table };
+                        tabulated += 1;
+                    }
+                    Err(e) => log::warn!("Keeping formula for {} of {}: {}", name, self.name, e),
+                },
+                Err(e) => log::warn!("Keeping formula for {} of {}: {}", name, self.name, e),
+            }
+        }
+        tabulated
+    }
 }
 
 impl Default for Material {
@@ -303,189 +849,55 @@ impl Default for Material {
     }
 }
 
-/// Predefined materials library with comprehensive material properties
+/// Material library backed by the process-wide material database
+///
+/// Holds the bundled default materials plus any user material files; see
+/// [`super::material_db`] for the file format and search paths.
 pub struct MaterialLibrary;
 
 impl MaterialLibrary {
-    /// Get carbon steel material with temperature-dependent properties
-    pub fn carbon_steel() -> Result<Material> {
-        Material::with_properties(
-            "Carbon Steel".to_string(),
-            7850.0, // kg/m³
-            Property::Formula("50.0 * (1.0 - 0.0003 * (T - 273.15))".to_string()), // W/(m·K) - decreases with temperature
-            Property::Formula("460.0 + 0.27 * (T - 273.15)".to_string()), // J/(kg·K) - increases with temperature
-            0.8,
-            Some(1811.0), // K
-            Some(247000.0), // J/kg
-        )
-    }
-    
-    /// Get stainless steel material (316L)
-    pub fn stainless_steel() -> Result<Material> {
-        Material::with_properties(
-            "Stainless Steel".to_string(),
-            8000.0, // kg/m³
-            Property::Formula("16.0 + 0.012 * (T - 273.15)".to_string()), // W/(m·K) - increases with temperature
-            Property::Formula("500.0 + 0.15 * (T - 273.15)".to_string()), // J/(kg·K)
-            0.7,
-            Some(1673.0), // K
-            Some(247000.0), // J/kg
-        )
-    }
-    
-    /// Get aluminum material (pure aluminum)
-    pub fn aluminum() -> Result<Material> {
-        Material::with_properties(
-            "Aluminum".to_string(),
-            2700.0, // kg/m³
-            Property::Formula("237.0 * (1.0 - 0.0004 * (T - 273.15))".to_string()), // W/(m·K)
-            Property::Formula("900.0 + 0.2 * (T - 273.15)".to_string()), // J/(kg·K)
-            0.9,
-            Some(933.0), // K
-            Some(397000.0), // J/kg
-        )
-    }
-    
-    /// Get copper material
-    pub fn copper() -> Result<Material> {
-        Material::with_properties(
-            "Copper".to_string(),
-            8960.0, // kg/m³
-            Property::Formula("401.0 * (1.0 - 0.0006 * (T - 273.15))".to_string()), // W/(m·K)
-            Property::Constant(385.0), // J/(kg·K) - relatively constant
-            0.8,
-            Some(1358.0), // K
-            Some(205000.0), // J/kg
-        )
-    }
-    
-    /// Get iron material (pure iron)
-    pub fn iron() -> Result<Material> {
-        Material::with_properties(
-            "Iron".to_string(),
-            7874.0, // kg/m³
-            Property::Formula("80.0 * (1.0 - 0.0005 * (T - 273.15))".to_string()), // W/(m·K)
-            Property::Formula("449.0 + 0.3 * (T - 273.15)".to_string()), // J/(kg·K)
-            0.85,
-            Some(1811.0), // K
-            Some(247000.0), // J/kg
-        )
-    }
-    
-    /// Get graphite material
-    pub fn graphite() -> Result<Material> {
-        Material::with_properties(
-            "Graphite".to_string(),
-            2200.0, // kg/m³
-            Property::Formula("129.0 * (1.0 + 0.0002 * (T - 273.15))".to_string()), // W/(m·K)
-            Property::Formula("709.0 + 0.4 * (T - 273.15)".to_string()), // J/(kg·K)
-            0.9,
-            Some(3773.0), // K (sublimation point)
-            Some(59000.0), // J/kg (sublimation)
-        )
-    }
-    
-    /// Get concrete material
-    pub fn concrete() -> Result<Material> {
-        Material::with_properties(
-            "Concrete".to_string(),
-            2300.0, // kg/m³
-            Property::Constant(1.7), // W/(m·K) - relatively constant
-            Property::Constant(880.0), // J/(kg·K)
-            0.9,
-            None, // No melting point (decomposes)
-            None,
-        )
-    }
-    
-    /// Get glass material (soda-lime glass)
-    pub fn glass() -> Result<Material> {
-        Material::with_properties(
-            "Glass".to_string(),
-            2500.0, // kg/m³
-            Property::Constant(1.4), // W/(m·K)
-            Property::Formula("840.0 + 0.1 * (T - 273.15)".to_string()), // J/(kg·K)
-            0.9,
-            Some(1773.0), // K (softening point)
-            None, // No distinct melting point
-        )
-    }
-    
-    /// Get wood material (generic hardwood)
-    pub fn wood() -> Result<Material> {
-        Material::with_properties(
-            "Wood".to_string(),
-            600.0, // kg/m³
-            Property::Constant(0.16), // W/(m·K)
-            Property::Constant(1600.0), // J/(kg·K)
-            0.9,
-            None, // Decomposes before melting
-            None,
-        )
-    }
-    
-    /// Get ceramic material (alumina)
-    pub fn ceramic() -> Result<Material> {
-        Material::with_properties(
-            "Ceramic".to_string(),
-            3970.0, // kg/m³
-            Property::Formula("30.0 * (1.0 - 0.0003 * (T - 273.15))".to_string()), // W/(m·K)
-            Property::Formula("775.0 + 0.15 * (T - 273.15)".to_string()), // J/(kg·K)
-            0.8,
-            Some(2327.0), // K
-            Some(1070000.0), // J/kg
-        )
+    /// List all available material names
+    pub fn list_materials() -> Vec<String> {
+        super::material_db::database().names()
     }
     
-    /// List all available materials
-    pub fn list_materials() -> Vec<String> {
-        vec![
-            "Carbon Steel".to_string(),
-            "Stainless Steel".to_string(),
-            "Aluminum".to_string(),
-            "Copper".to_string(),
-            "Iron".to_string(),
-            "Graphite".to_string(),
-            "Concrete".to_string(),
-            "Glass".to_string(),
-            "Wood".to_string(),
-            "Ceramic".to_string(),
-        ]
-    }
-    
-    /// Get material by name
+    /// Get material by name or ID
     pub fn get_material(name: &str) -> Result<Material> {
-        match name {
-            "Carbon Steel" => Self::carbon_steel(),
-            "Stainless Steel" => Self::stainless_steel(),
-            "Aluminum" => Self::aluminum(),
-            "Copper" => Self::copper(),
-            "Iron" => Self::iron(),
-            "Graphite" => Self::graphite(),
-            "Concrete" => Self::concrete(),
-            "Glass" => Self::glass(),
-            "Wood" => Self::wood(),
-            "Ceramic" => Self::ceramic(),
-            _ => Err(SimulationError::MaterialError {
+        super::material_db::database()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| SimulationError::MaterialError {
                 material: name.to_string(),
                 property: "material".to_string(),
                 details: format!("Unknown material: {}", name),
-            }),
-        }
+            })
     }
     
     /// Get all materials as a vector
     pub fn get_all_materials() -> Result<Vec<Material>> {
-        let mut materials = Vec::new();
-        for name in Self::list_materials() {
-            materials.push(Self::get_material(&name)?);
-        }
-        Ok(materials)
+        Ok(super::material_db::database().materials().to_vec())
     }
     
-    /// Validate material name
+    /// Validate material name or ID
     pub fn is_valid_material(name: &str) -> bool {
-        Self::list_materials().contains(&name.to_string())
+        super::material_db::database().contains(name)
+    }
+    
+    /// Add a material, replacing any material with the same name or ID
+    pub fn add_material(material: Material) -> Result<()> {
+        super::material_db::database_mut().insert(material)
+    }
+    
+    /// Load a `.json` or `.toml` material file, returning the number of materials added
+    pub fn load_file(path: &std::path::Path) -> Result<usize> {
+        let materials = super::material_db::MaterialDatabase::read_file(path)?;
+        super::material_db::database_mut().extend(materials)
+    }
+    
+    /// Load every material file in a directory, returning the number of materials added
+    pub fn load_directory(directory: &std::path::Path) -> Result<usize> {
+        let materials = super::material_db::MaterialDatabase::read_directory(directory)?;
+        super::material_db::database_mut().extend(materials)
     }
 }
 
@@ -498,8 +910,8 @@ mod tests {
     fn test_material_creation() {
         let material = Material::new("Test".to_string(), 1000.0, 0.5).unwrap();
         assert_eq!(material.name, "Test");
-        assert_eq!(material.density, 1000.0);
-        assert_eq!(material.emissivity, 0.5);
+        assert_eq!(material.get_density(300.0, None).unwrap(), 1000.0);
+        assert_eq!(material.get_emissivity(300.0, None).unwrap(), 0.5);
     }
     
     #[test]
@@ -551,6 +963,36 @@ mod tests {
         assert!(prop.evaluate(100.0, None).is_err());
     }
     
+    #[test]
+    fn test_property_derivatives() {
+        let mut engine = FormulaEngine::new();
+        
+        assert_eq!(Property::Constant(45.0).evaluate_with_derivative(500.0, None).unwrap(), (45.0, 0.0));
+        
+        // k = 50 (1 - 3e-4 (T - 273.15)) has dk/dT = -0.015 everywhere
+        let formula = Property::Formula("50.0 * (1.0 - 0.0003 * (T - 273.15))".to_string());
+        let (value, derivative) = formula.evaluate_with_derivative(1000.0, Some(&mut engine)).unwrap();
+        assert_eq!(value, formula.evaluate(1000.0, Some(&mut engine)).unwrap());
+        assert!((derivative + 0.015).abs() < 1e-12);
+        assert!(formula.evaluate_with_derivative(1000.0, None).is_err());
+        
+        // Table slopes, zero where clamped
+        let table = Property::Table(vec![(300.0, 20.0), (500.0, 30.0), (700.0, 50.0)]);
+        assert_eq!(table.evaluate_with_derivative(400.0, None).unwrap(), (25.0, 0.05));
+        assert_eq!(table.evaluate_with_derivative(600.0, None).unwrap().1, 0.1);
+        assert_eq!(table.evaluate_with_derivative(900.0, None).unwrap(), (50.0, 0.0));
+        
+        // Cubic and log-linear slopes agree with finite differences
+        for interpolation in [Interpolation::MonotoneCubic, Interpolation::LogLinear] {
+            let points = vec![(300.0, 20.0), (500.0, 30.0), (700.0, 50.0), (900.0, 52.0)];
+            let property = Property::Tabulated(PropertyTable::new(points).with_interpolation(interpolation));
+            let (_, derivative) = property.evaluate_with_derivative(620.0, None).unwrap();
+            let h = 1e-4;
+            let numerical = (property.evaluate(620.0 + h, None).unwrap() - property.evaluate(620.0 - h, None).unwrap()) / (2.0 * h);
+            assert!((derivative - numerical).abs() < 1e-6);
+        }
+    }
+    
     #[test]
     fn test_property_validation() {
         // Valid constant
@@ -586,7 +1028,7 @@ mod tests {
         
         let steel = MaterialLibrary::get_material("Carbon Steel").unwrap();
         assert_eq!(steel.name, "Carbon Steel");
-        assert_eq!(steel.density, 7850.0);
+        assert_eq!(steel.get_density(300.0, None).unwrap(), 7850.0);
         assert!(steel.has_phase_change());
         
         // Test unknown material
@@ -638,6 +1080,163 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_liquid_fraction() {
+        let steel = MaterialLibrary::get_material("Carbon Steel").unwrap();
+        assert_eq!(steel.liquid_fraction(1700.0, 50.0), 0.0);
+        assert!((steel.liquid_fraction(1811.0, 50.0) - 0.5).abs() < 1e-12);
+        assert_eq!(steel.liquid_fraction(1900.0, 50.0), 1.0);
+        
+        let concrete = MaterialLibrary::get_material("Concrete").unwrap();
+        assert_eq!(concrete.liquid_fraction(5000.0, 50.0), 0.0);
+    }
+    
+    #[test]
+    fn test_anisotropic_conductivity() {
+        let mut engine = FormulaEngine::new();
+        let graphite = MaterialLibrary::get_material("Graphite").unwrap();
+        assert!(graphite.is_anisotropic());
+        
+        let ratio = graphite.axial_conductivity_ratio(1000.0, Some(&mut engine)).unwrap();
+        assert!((ratio - 190.0 / 129.0).abs() < 1e-9);
+        
+        // Isotropic materials use the same conductivity in both directions
+        let steel = MaterialLibrary::get_material("Carbon Steel").unwrap();
+        assert!(!steel.is_anisotropic());
+        assert_eq!(steel.axial_conductivity_ratio(1000.0, None).unwrap(), 1.0);
+        let k = steel.get_thermal_conductivity(500.0, Some(&mut engine)).unwrap();
+        assert_eq!(steel.get_axial_thermal_conductivity(500.0, Some(&mut engine)).unwrap(), k);
+        
+        let layered = Material::new("Layered".to_string(), 2000.0, 0.8).unwrap()
+            .with_axial_thermal_conductivity(Property::Table(vec![(300.0, 1.0), (1300.0, 3.0)]))
+            .unwrap();
+        assert_eq!(layered.axial_conductivity_ratio(800.0, None).unwrap(), 2.0 / 50.0);
+        assert!(layered.clone().with_axial_thermal_conductivity(Property::Constant(-1.0)).is_err());
+    }
+    
+    fn molten_steel_phases() -> PhaseProperties {
+        PhaseProperties {
+            solidus: 1750.0,
+            liquidus: 1800.0,
+            liquid: PhaseSet { thermal_conductivity: Property::Constant(30.0), specific_heat: Property::Constant(820.0) },
+            vaporization_range: Some((3100.0, 3200.0)),
+            gas: Some(PhaseSet { thermal_conductivity: Property::Constant(0.1), specific_heat: Property::Constant(450.0) }),
+        }
+    }
+
+    #[test]
+    fn test_phase_property_blend() {
+        let steel = Material::new("Phased Steel".to_string(), 7850.0, 0.8).unwrap()
+            .with_phase_properties(molten_steel_phases())
+            .unwrap();
+
+        // Solid below the solidus, liquid above the liquidus, gas past vaporization
+        assert_eq!(steel.get_thermal_conductivity(1500.0, None).unwrap(), 50.0);
+        assert_eq!(steel.get_thermal_conductivity(2000.0, None).unwrap(), 30.0);
+        assert_eq!(steel.get_specific_heat(3500.0, None).unwrap(), 450.0);
+
+        // The mushy zone blends by liquid fraction
+        assert_eq!(steel.phase_fractions(1775.0), (0.5, 0.0));
+        assert!((steel.get_thermal_conductivity(1775.0, None).unwrap() - 40.0).abs() < 1e-12);
+        assert!((steel.get_specific_heat(1775.0, None).unwrap() - 660.0).abs() < 1e-12);
+        assert_eq!(steel.liquid_fraction(1775.0, 500.0), 0.5);
+
+        // Partial vaporization splits the fluid between liquid and gas
+        let (liquid, vapour) = steel.phase_fractions(3125.0);
+        assert!((liquid - 0.75).abs() < 1e-12 && (vapour - 0.25).abs() < 1e-12);
+        assert!((steel.get_thermal_conductivity(3125.0, None).unwrap() - (0.75 * 30.0 + 0.25 * 0.1)).abs() < 1e-12);
+
+        // Materials without phase data keep a single property set
+        let plain = Material::new("Plain".to_string(), 7850.0, 0.8).unwrap();
+        assert_eq!(plain.phase_fractions(5000.0), (0.0, 0.0));
+        assert_eq!(plain.get_thermal_conductivity(5000.0, None).unwrap(), 50.0);
+    }
+
+    #[test]
+    fn test_phase_blend_derivatives() {
+        let mut engine = FormulaEngine::new();
+        let mut steel = Material::new("Phased Steel".to_string(), 7850.0, 0.8).unwrap()
+            .with_phase_properties(molten_steel_phases())
+            .unwrap();
+        steel.specific_heat = Property::Formula("460.0 + 0.27 * (T - 273.15)".to_string());
+
+        // Across the mushy zone dk/dT is the slope of the blend, (30 - 50) / 50 K
+        let (k, dk) = steel.get_thermal_conductivity_with_derivative(1775.0, Some(&mut engine)).unwrap();
+        assert!((k - 40.0).abs() < 1e-12);
+        assert!((dk + 0.4).abs() < 1e-12);
+        assert_eq!(steel.get_thermal_conductivity_with_derivative(1500.0, None).unwrap(), (50.0, 0.0));
+
+        // Derivatives match central differences inside the melting and vaporization ranges
+        let h = 1e-3;
+        for t in [1500.0, 1760.0, 1790.0, 2500.0, 3150.0] {
+            let (cp, dcp) = steel.get_specific_heat_with_derivative(t, Some(&mut engine)).unwrap();
+            assert_eq!(cp, steel.get_specific_heat(t, Some(&mut engine)).unwrap());
+            let numerical = (steel.get_specific_heat(t + h, Some(&mut engine)).unwrap()
+                - steel.get_specific_heat(t - h, Some(&mut engine)).unwrap()) / (2.0 * h);
+            assert!((dcp - numerical).abs() < 1e-6 * numerical.abs().max(1.0), "dcp/dT at {} K", t);
+
+            let (_, dk) = steel.get_thermal_conductivity_with_derivative(t, None).unwrap();
+            let numerical = (steel.get_thermal_conductivity(t + h, None).unwrap()
+                - steel.get_thermal_conductivity(t - h, None).unwrap()) / (2.0 * h);
+            assert!((dk - numerical).abs() < 1e-6, "dk/dT at {} K", t);
+        }
+    }
+
+    #[test]
+    fn test_phase_property_validation() {
+        let material = Material::new("Test".to_string(), 1000.0, 0.5).unwrap();
+
+        let inverted = PhaseProperties { liquidus: 1700.0, ..molten_steel_phases() };
+        assert!(material.clone().with_phase_properties(inverted).is_err());
+
+        let gas_without_range = PhaseProperties { vaporization_range: None, ..molten_steel_phases() };
+        assert!(material.clone().with_phase_properties(gas_without_range).is_err());
+
+        let boiling_while_solid = PhaseProperties { vaporization_range: Some((1600.0, 3200.0)), ..molten_steel_phases() };
+        assert!(material.clone().with_phase_properties(boiling_while_solid).is_err());
+
+        // An isothermal melt is a step between the solid and liquid sets
+        let pure = PhaseProperties { solidus: 1800.0, liquidus: 1800.0, vaporization_range: None, gas: None, ..molten_steel_phases() };
+        let pure_iron = material.with_phase_properties(pure).unwrap();
+        assert_eq!(pure_iron.phase_fractions(1799.9), (0.0, 0.0));
+        assert_eq!(pure_iron.phase_fractions(1800.0), (1.0, 0.0));
+
+        // Phase data round-trips through material files
+        let json = serde_json::to_string(&pure_iron).unwrap();
+        let parsed: Material = serde_json::from_str(&json).unwrap();
+        assert!(parsed.validate().is_ok());
+        assert_eq!(parsed.phases.unwrap().liquidus, 1800.0);
+    }
+
+    #[test]
+    fn test_temperature_dependent_density_and_emissivity() {
+        let mut engine = FormulaEngine::new();
+        let slag = Material::new("Slag".to_string(), 2900.0, 0.85).unwrap()
+            .with_density(Property::Formula("2900.0 - 0.2 * (T - 300.0)".to_string())).unwrap()
+            .with_emissivity(Property::Table(vec![(300.0, 0.6), (1800.0, 0.9)])).unwrap();
+        assert_eq!(slag.get_density(1300.0, Some(&mut engine)).unwrap(), 2700.0);
+        assert!((slag.get_emissivity(1050.0, None).unwrap() - 0.75).abs() < 1e-12);
+        
+        // Emissivities above one and non-positive densities are rejected
+        let material = Material::new("Test".to_string(), 1000.0, 0.5).unwrap();
+        assert!(material.clone().with_emissivity(Property::Table(vec![(300.0, 0.5), (900.0, 1.2)])).is_err());
+        assert!(material.clone().with_density(Property::Constant(0.0)).is_err());
+        let shrinking = material.with_emissivity(Property::Formula("T / 1000.0".to_string())).unwrap();
+        assert!(shrinking.get_emissivity(1500.0, Some(&mut engine)).is_err());
+        
+        // Configurations storing plain numbers still load, and properties round-trip
+        let legacy = r#"{"name": "Legacy", "density": 7000.0, "emissivity": 0.7,
+            "thermal_conductivity": {"Constant": 40.0}, "specific_heat": {"Constant": 450.0},
+            "melting_point": null, "latent_heat_fusion": null}"#;
+        let legacy: Material = serde_json::from_str(legacy).unwrap();
+        assert!(matches!(legacy.density, Property::Constant(rho) if rho == 7000.0));
+        assert!(matches!(legacy.emissivity, Property::Constant(e) if e == 0.7));
+        
+        let parsed: Material = serde_json::from_str(&serde_json::to_string(&slag).unwrap()).unwrap();
+        assert!(matches!(parsed.density, Property::Formula(_)));
+        assert_eq!(parsed.emissivity.table_points().unwrap().len(), 2);
+    }
+    
     #[test]
     fn test_material_validation_comprehensive() {
         let material = MaterialLibrary::get_material("Carbon Steel").unwrap();
@@ -645,13 +1244,20 @@ mod tests {
         
         // Test material with invalid properties
         let invalid_material = Material {
+            id: None,
             name: "".to_string(), // Invalid empty name
-            density: -1000.0,     // Invalid negative density
+            density: Property::Constant(-1000.0), // Invalid negative density
             thermal_conductivity: Property::Constant(50.0),
+            axial_thermal_conductivity: None,
             specific_heat: Property::Constant(500.0),
-            emissivity: 1.5,      // Invalid emissivity > 1.0
+            emissivity: Property::Constant(1.5),  // Invalid emissivity > 1.0
             melting_point: Some(-100.0), // Invalid negative melting point
             latent_heat_fusion: Some(-1000.0), // Invalid negative latent heat
+            absorption_coefficient: None,
+            electrical_conductivity: None,
+            reactions: Vec::new(),
+            elastic: None,
+            phases: None,
         };
         
         assert!(invalid_material.validate().is_err());
@@ -31,7 +31,8 @@
 //! println!("Min temp: {}, Max temp: {}", stats.min_temperature, stats.max_temperature);
 //! 
 //! // Export to CSV
-//! analyzer.export_statistics_csv("metrics.csv").unwrap();
+//! let path = std::env::temp_dir().join("metrics.csv");
+//! analyzer.export_statistics_csv(path.to_str().unwrap()).unwrap();
 //! ```
 
 use crate::errors::Result;
@@ -7,13 +7,30 @@
 //! 
 //! # Module Organization
 //! 
+//! - [`boundary`] - Configurable boundary conditions for each boundary segment
+//! - [`composite`] - Mixture and composite materials from constituent fractions
+//! - [`control`] - Closed-loop PID torch power control
+//! - [`convection`] - Effective natural convection in molten regions
+//! - [`elliptic`] - Steady elliptic field solver shared by coupled physics models
+//! - [`heat_sources`] - Formula-defined volumetric heat sources
+//! - [`joule`] - Joule heating from the electric potential of transferred arcs
+//! - [`ledger`] - Energy balance ledger per torch and per boundary segment
+//! - [`material_db`] - Material database files (bundled and user-defined)
 //! - [`materials`] - Material properties and databases
 //! - [`mesh`] - Mesh generation and management for cylindrical geometries
+//! - [`moisture`] - Moisture evaporation in wet charge material
+//! - [`p1`] - P1 radiation model for participating media
 //! - [`metrics`] - Performance metrics and data export functionality
 //! - [`parametric`] - Parametric studies and optimization workflows
 //! - [`physics`] - Core physics models (heat transfer, plasma torches, radiation)
+//! - [`property_table`] - Tabulated properties with interpolation and extrapolation policies
+//! - [`probes`] - Virtual thermocouples recording temperature histories
+//! - [`regions`] - Material regions and thermal contact between them
+//! - [`reactions`] - Chemical reaction heat sources with Arrhenius kinetics
+//! - [`radiation`] - Surface-to-surface radiation exchange inside the cavity
 //! - [`solver`] - Numerical solvers for the simulation equations
 //! - [`state`] - Simulation state management and threading
+//! - [`stress`] - Thermoelastic stress post-processing
 //! - [`validation`] - Tools for validating simulation results
 //! - [`visualization`] - Data preparation for 3D visualization
 //! 
@@ -22,18 +39,36 @@
 //! The main entry point for simulations is the `SimulationEngine` struct.
 
 use crate::errors::{Result, SimulationError};
+use crate::formula::engine::FormulaEngine;
 use ndarray::Array2;
 use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
 use std::time::Instant;
 
 // Core simulation modules
+pub mod boundary;
+pub mod composite;
+pub mod control;
+pub mod convection;
+pub mod elliptic;
+pub mod heat_sources;
+pub mod joule;
+pub mod ledger;
+pub mod material_db;
 pub mod materials;
 pub mod mesh;
 pub mod metrics;
+pub mod moisture;
+pub mod p1;
 pub mod parametric;
 pub mod physics;
+pub mod probes;
+pub mod property_table;
+pub mod radiation;
+pub mod reactions;
+pub mod regions;
 pub mod solver;
 pub mod state;
+pub mod stress;
 pub mod validation;
 pub mod visualization;
 
@@ -50,14 +85,31 @@ mod material_diffusion_test;
 // Re-export key types for convenience
 pub use mesh::{CylindricalMesh, MeshPreset};
 pub use physics::{PlasmaTorch, PlasmaPhysics, BoundaryConditions};
+pub use boundary::{BoundaryCondition, BoundarySpec, CoolantReport};
+pub use composite::{CompositeBuilder, FractionBasis, MixingRule};
+pub use radiation::{EnclosureRadiation, EnclosureRadiationConfig};
+pub use p1::{P1Radiation, P1RadiationConfig};
+pub use joule::{Electrode, ElectrodeLocation, JouleHeating, JouleHeatingConfig};
+pub use control::{ControlVariable, ControllerConfig, ControllerHistory, PidController, PidGains, Setpoint};
+pub use convection::{NaturalConvection, NaturalConvectionConfig};
+pub use heat_sources::{FormulaHeatSource, FormulaSources};
+pub use reactions::{Reaction, ReactionHistory, ReactionKind, ReactionModel};
+pub use moisture::{DryingHistory, MoistureConfig, MoistureModel};
+pub use property_table::{Extrapolation, FormulaTabulation, Interpolation, PropertyTable, RangeWarning};
+pub use probes::{Probe, ProbeHistory, ProbeRecorder};
+pub use regions::{ContactInterface, InterfaceReport, MaterialRegion, RegionMap};
+pub use stress::{StressField, StressSummary, ThermalStressAnalysis, ThermalStressConfig};
 pub use solver::{HeatSolver, SolverMethod};
-pub use materials::{Material, MaterialLibrary};
+pub use ledger::{BoundaryLoss, EnergyLedger, LedgerEntry};
+pub use materials::{Material, MaterialLibrary, PhaseProperties, PhaseSet, Property};
 pub use state::{SimulationState, SimulationStatus, SimulationStateManager};
 
 /// Geometry configuration for the furnace
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct GeometryConfig {
+    #[serde(deserialize_with = "crate::units::meters")]
     pub radius: f64,        // meters
+    #[serde(deserialize_with = "crate::units::meters")]
     pub height: f64,        // meters
 }
 
@@ -86,12 +138,44 @@ impl Default for MeshConfig {
     }
 }
 
+impl MeshConfig {
+    /// Mesh resolution (nr, nz), the custom resolution taking precedence over the preset
+    pub fn resolution(&self) -> (usize, usize) {
+        self.custom_resolution.unwrap_or_else(|| self.preset.resolution())
+    }
+}
+
 /// Physics configuration
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PhysicsConfig {
+    #[serde(deserialize_with = "crate::units::kelvin")]
     pub initial_temperature: f64,  // K
+    #[serde(deserialize_with = "crate::units::kelvin")]
     pub ambient_temperature: f64,  // K
+    #[serde(deserialize_with = "crate::units::seconds")]
     pub simulation_time: f64,      // seconds
+    /// Surface-to-surface radiation exchange inside the cavity (disabled if not set)
+    #[serde(default)]
+    pub enclosure_radiation: Option<EnclosureRadiationConfig>,
+    /// P1 radiation in participating media (disabled if not set)
+    #[serde(default)]
+    pub p1_radiation: Option<P1RadiationConfig>,
+    /// Joule heating from current through the melt (transferred arc, disabled if not set)
+    #[serde(default)]
+    pub joule_heating: Option<JouleHeatingConfig>,
+    /// Natural convection enhancement in molten regions (disabled if not set)
+    #[serde(default)]
+    pub natural_convection: Option<NaturalConvectionConfig>,
+    /// Moisture evaporation in wet charge material (dry charge if not set)
+    #[serde(default)]
+    pub moisture: Option<MoistureConfig>,
+    /// Formula volumetric heat sources added to the torch heating
+    #[serde(default)]
+    pub heat_sources: Vec<FormulaHeatSource>,
+    /// Initial temperature field T0(r, z) in K as a formula in the standard
+    /// variables, with `T` bound to `initial_temperature` (uniform if not set)
+    #[serde(default)]
+    pub initial_temperature_formula: Option<String>,
 }
 
 impl Default for PhysicsConfig {
@@ -100,6 +184,13 @@ impl Default for PhysicsConfig {
             initial_temperature: 298.15,  // 25°C
             ambient_temperature: 298.15,  // 25°C
             simulation_time: 60.0,        // 1 minute
+            enclosure_radiation: None,
+            p1_radiation: None,
+            joule_heating: None,
+            natural_convection: None,
+            moisture: None,
+            heat_sources: Vec::new(),
+            initial_temperature_formula: None,
         }
     }
 }
@@ -109,7 +200,16 @@ impl Default for PhysicsConfig {
 pub struct SolverConfig {
     pub method: SolverMethod,
     pub cfl_factor: f64,
+    #[serde(deserialize_with = "crate::units::seconds")]
     pub max_time_step: f64,
+    /// Replace formula material properties by lookup tables at initialization;
+    /// formulas are evaluated directly when unset
+    #[serde(default = "default_formula_tabulation")]
+    pub formula_tabulation: Option<FormulaTabulation>,
+}
+
+fn default_formula_tabulation() -> Option<FormulaTabulation> {
+    Some(FormulaTabulation::default())
 }
 
 impl Default for SolverConfig {
@@ -118,6 +218,31 @@ impl Default for SolverConfig {
             method: SolverMethod::ForwardEuler,
             cfl_factor: 0.5,
             max_time_step: 0.1,
+            formula_tabulation: default_formula_tabulation(),
+        }
+    }
+}
+
+/// Boundary condition configuration
+/// 
+/// Unset boundaries keep the legacy behaviour: convection plus radiation on
+/// the outer wall and adiabatic top and bottom surfaces.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BoundaryConfig {
+    #[serde(deserialize_with = "crate::units::heat_transfer_coefficient")]
+    pub convection_coefficient: f64,         // W/(m²·K)
+    pub outer_wall: Option<BoundarySpec>,
+    pub top: Option<BoundarySpec>,
+    pub bottom: Option<BoundarySpec>,
+}
+
+impl Default for BoundaryConfig {
+    fn default() -> Self {
+        Self {
+            convection_coefficient: 10.0,
+            outer_wall: None,
+            top: None,
+            bottom: None,
         }
     }
 }
@@ -126,8 +251,10 @@ impl Default for SolverConfig {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct TorchConfig {
     pub position: (f64, f64),  // (r, z) in meters
+    #[serde(deserialize_with = "crate::units::kilowatts")]
     pub power: f64,            // kW
     pub efficiency: f64,       // 0.0 to 1.0
+    #[serde(deserialize_with = "crate::units::meters")]
     pub sigma: f64,            // Gaussian spread parameter
 }
 
@@ -146,12 +273,20 @@ impl Default for TorchConfig {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct MaterialConfig {
     pub material_name: String,
+    /// Regions of other materials; later regions take precedence where they overlap
+    #[serde(default)]
+    pub regions: Vec<MaterialRegion>,
+    /// Contact conductances between touching regions (perfect contact if not listed)
+    #[serde(default)]
+    pub interfaces: Vec<ContactInterface>,
 }
 
 impl Default for MaterialConfig {
     fn default() -> Self {
         Self {
             material_name: "Carbon Steel".to_string(),
+            regions: Vec::new(),
+            interfaces: Vec::new(),
         }
     }
 }
@@ -173,6 +308,15 @@ pub struct SimulationConfig {
     pub torches: Vec<TorchConfig>,
     /// Material configuration
     pub material: MaterialConfig,
+    /// Boundary conditions
+    #[serde(default)]
+    pub boundaries: BoundaryConfig,
+    /// Virtual thermocouples recorded at every time step
+    #[serde(default)]
+    pub probes: Vec<Probe>,
+    /// Closed-loop torch power control (fixed torch powers if not set)
+    #[serde(default)]
+    pub controller: Option<ControllerConfig>,
 }
 
 /// Simulation metadata
@@ -199,6 +343,9 @@ impl Default for SimulationConfig {
             solver: SolverConfig::default(),
             torches: vec![TorchConfig::default()],
             material: MaterialConfig::default(),
+            boundaries: BoundaryConfig::default(),
+            probes: Vec::new(),
+            controller: None,
         }
     }
 }
@@ -304,6 +451,33 @@ pub struct SimulationResults {
     pub avg_temperature: f64,
     /// Time-series data for animation (optional, can be large)
     pub time_series_data: Option<Vec<TimeStepData>>,
+    /// Joule heating power at the end of the simulation in W (transferred arc only)
+    #[serde(default)]
+    pub joule_heating_power: Option<f64>,
+    /// Coolant outlet temperature and heat removed by each cooled jacket at the end of the simulation
+    #[serde(default)]
+    pub coolant_circuits: Vec<CoolantReport>,
+    /// Heat flow and temperature jump across each contact interface at the end of the simulation
+    #[serde(default)]
+    pub interface_reports: Vec<InterfaceReport>,
+    /// Degree of conversion of material reactions over time (empty without reactions)
+    #[serde(default)]
+    pub reaction_history: ReactionHistory,
+    /// Drying front position and drying energy over time (empty for dry charge)
+    #[serde(default)]
+    pub drying_history: DryingHistory,
+    /// Temperature history of each probe at every time step
+    #[serde(default)]
+    pub probe_histories: Vec<ProbeHistory>,
+    /// Setpoint, measurement and torch power at each controller update
+    #[serde(default)]
+    pub controller_history: ControllerHistory,
+    /// Energy balance per torch and per boundary segment over time
+    #[serde(default)]
+    pub energy_ledger: EnergyLedger,
+    /// Property tables queried outside their measured temperature range
+    #[serde(default)]
+    pub property_warnings: Vec<RangeWarning>,
 }
 
 /// Main simulation engine that orchestrates mesh, physics, and solver
@@ -312,6 +486,27 @@ pub struct SimulationEngine {
     mesh: Option<CylindricalMesh>,
     physics: Option<PlasmaPhysics>,
     solver: Option<HeatSolver>,
+    enclosure_radiation: Option<EnclosureRadiation>,
+    p1_radiation: Option<P1Radiation>,
+    joule_heating: Option<JouleHeating>,
+    natural_convection: Option<NaturalConvection>,
+    reactions: Option<ReactionModel>,
+    formula_sources: Option<FormulaSources>,
+    /// Reaction progress recorded at the storage interval
+    reaction_history: ReactionHistory,
+    moisture: Option<MoistureModel>,
+    /// Drying progress recorded at the storage interval
+    drying_history: DryingHistory,
+    regions: Option<RegionMap>,
+    /// Probe temperatures recorded at every time step
+    probes: Option<ProbeRecorder>,
+    controller: Option<PidController>,
+    /// Energy balance recorded every step and closed at the storage interval
+    energy_ledger: Option<EnergyLedger>,
+    /// Axial to radial conductivity ratio at each node (anisotropic materials only)
+    axial_conductivity_ratio: Option<Array2<f64>>,
+    /// Formula engine for material properties evaluated by the engine
+    formula_engine: FormulaEngine,
     temperature_field: Option<Array2<f64>>,
     state_manager: Option<SimulationStateManager>,
     cancellation_token: Arc<AtomicBool>,
@@ -338,6 +533,21 @@ impl SimulationEngine {
             mesh: None,
             physics: None,
             solver: None,
+            enclosure_radiation: None,
+            p1_radiation: None,
+            joule_heating: None,
+            natural_convection: None,
+            reactions: None,
+            formula_sources: None,
+            reaction_history: ReactionHistory::default(),
+            moisture: None,
+            drying_history: DryingHistory::default(),
+            regions: None,
+            probes: None,
+            controller: None,
+            energy_ledger: None,
+            axial_conductivity_ratio: None,
+            formula_engine: FormulaEngine::new(),
             temperature_field: None,
             state_manager: None,
             cancellation_token: Arc::new(AtomicBool::new(false)),
@@ -362,6 +572,9 @@ impl SimulationEngine {
         // Validate solver parameters
         crate::errors::validation::validate_range(config.solver.cfl_factor, 0.0, 1.0, "CFL factor")?;
         crate::errors::validation::validate_positive(config.solver.max_time_step, "maximum time step")?;
+        if let Some(ref tabulation) = config.solver.formula_tabulation {
+            tabulation.validate()?;
+        }
         
         // Validate torches
         if config.torches.is_empty() {
@@ -394,6 +607,44 @@ impl SimulationEngine {
             crate::errors::validation::validate_range(torch.sigma, 0.01, 1.0, &format!("torch[{}] sigma", i))?;
         }
         
+        // Validate boundary conditions
+        crate::errors::validation::validate_range(
+            config.boundaries.convection_coefficient, 0.0, 1.0e6, "convection coefficient"
+        )?;
+        for (name, spec) in [
+            ("outer wall", &config.boundaries.outer_wall),
+            ("top", &config.boundaries.top),
+            ("bottom", &config.boundaries.bottom),
+        ] {
+            if let Some(spec) = spec {
+                spec.validate(name)?;
+            }
+        }
+        
+        if let Some(ref radiation) = config.physics.enclosure_radiation {
+            radiation.validate()?;
+        }
+        if let Some(ref p1) = config.physics.p1_radiation {
+            p1.validate()?;
+        }
+        if let Some(ref joule) = config.physics.joule_heating {
+            joule.validate()?;
+        }
+        if let Some(ref convection) = config.physics.natural_convection {
+            convection.validate()?;
+        }
+        let mut formula_engine = FormulaEngine::new();
+        for source in &config.physics.heat_sources {
+            source.validate(&mut formula_engine)?;
+        }
+        if let Some(ref formula) = config.physics.initial_temperature_formula {
+            formula_engine.compile(formula)?;
+            crate::units::check_formula_dimension(formula, crate::units::Dimension::TEMPERATURE, "initial temperature")?;
+        }
+        if let Some(ref moisture) = config.physics.moisture {
+            moisture.validate()?;
+        }
+        
         // Validate material
         if !MaterialLibrary::is_valid_material(&config.material.material_name) {
             return Err(SimulationError::MaterialError {
@@ -402,6 +653,11 @@ impl SimulationEngine {
                 details: "Unknown material name".to_string(),
             });
         }
+        regions::validate_regions(&config.material.regions, &config.material.interfaces)?;
+        probes::validate_probes(&config.probes, config.geometry.radius, config.geometry.height)?;
+        if let Some(ref controller) = config.controller {
+            controller.validate(&config.torches, &config.probes)?;
+        }
         
         Ok(())
     }
@@ -410,11 +666,11 @@ impl SimulationEngine {
     pub fn initialize(&mut self) -> Result<()> {
         log::info!("Initializing simulation: {}", self.config.metadata.name);
         
+        // Discard out-of-range table queries left over from earlier work on this thread
+        property_table::take_range_warnings();
+        
         // Create mesh
-        let (nr, nz) = match self.config.mesh.custom_resolution {
-            Some(resolution) => resolution,
-            None => self.config.mesh.preset.resolution(),
-        };
+        let (nr, nz) = self.config.mesh.resolution();
         
         self.mesh = Some(CylindricalMesh::new(
             self.config.geometry.radius,
@@ -424,7 +680,11 @@ impl SimulationEngine {
         )?);
         
         // Create physics model
-        let material = MaterialLibrary::get_material(&self.config.material.material_name)?;
+        let mut material = MaterialLibrary::get_material(&self.config.material.material_name)?;
+        if let Some(ref tabulation) = self.config.solver.formula_tabulation {
+            material.tabulate_formulas(tabulation, &mut self.formula_engine);
+        }
+        let emissivity = material.get_emissivity(self.config.physics.initial_temperature, Some(&mut self.formula_engine))?;
         let mut torches = Vec::new();
         
         for torch_config in &self.config.torches {
@@ -440,12 +700,17 @@ impl SimulationEngine {
         let boundary_conditions = BoundaryConditions {
             axis_symmetry: true,
             outer_wall_temperature: None,
-            convection_coefficient: 10.0,
+            convection_coefficient: self.config.boundaries.convection_coefficient,
             ambient_temperature: self.config.physics.ambient_temperature,
-            emissivity: material.emissivity,
+            emissivity,
+            outer_wall: self.config.boundaries.outer_wall.clone(),
+            top: self.config.boundaries.top.clone(),
+            bottom: self.config.boundaries.bottom.clone(),
         };
         
-        self.physics = Some(PlasmaPhysics::new(torches, material, boundary_conditions)?);
+        let physics = PlasmaPhysics::new(torches, material, boundary_conditions)?;
+        physics.check_properties(self.config.physics.initial_temperature)?;
+        self.physics = Some(physics);
         
         // Create solver
         self.solver = Some(HeatSolver::with_cfl_factor(
@@ -453,14 +718,167 @@ impl SimulationEngine {
             self.config.solver.cfl_factor,
         )?);
         
-        // Initialize temperature field
+        // Resolve material regions; the solver switches to face conductances
+        self.regions = match (&self.mesh, &self.physics) {
+            (Some(mesh), Some(physics)) if !self.config.material.regions.is_empty() => Some(RegionMap::new(
+                &physics.material,
+                &self.config.material.regions,
+                &self.config.material.interfaces,
+                mesh,
+            )?),
+            _ => None,
+        };
+        if let (Some(ref mut regions), Some(ref tabulation)) = (&mut self.regions, &self.config.solver.formula_tabulation) {
+            for material in &mut regions.materials {
+                material.tabulate_formulas(tabulation, &mut self.formula_engine);
+            }
+        }
+        if let (Some(ref regions), Some(ref mut solver)) = (&self.regions, &mut self.solver) {
+            solver.contact_faces = Some(regions.contact_faces.clone());
+        }
+        
+        // Track the conductivity anisotropy if any material needs it
+        self.axial_conductivity_ratio = match (&self.mesh, &self.physics) {
+            (Some(mesh), Some(physics)) => {
+                let anisotropic = physics.material.is_anisotropic()
+                    || self.regions.iter().flat_map(|regions| &regions.materials).any(|material| material.is_anisotropic());
+                anisotropic.then(|| Array2::ones((mesh.nr, mesh.nz)))
+            }
+            _ => None,
+        };
+        
+        // Create enclosure radiation model
+        self.enclosure_radiation = match (&self.config.physics.enclosure_radiation, &self.mesh) {
+            (Some(radiation_config), Some(mesh)) => Some(EnclosureRadiation::new(mesh, radiation_config.clone())?),
+            _ => None,
+        };
+        
+        // Create P1 radiation model
+        self.p1_radiation = match (&self.config.physics.p1_radiation, &self.mesh, &self.physics) {
+            (Some(p1_config), Some(mesh), Some(physics)) => Some(P1Radiation::new(
+                p1_config.clone(),
+                &physics.material,
+                mesh,
+                self.config.physics.initial_temperature,
+            )?),
+            _ => None,
+        };
+        
+        // Create Joule heating model
+        self.joule_heating = match (&self.config.physics.joule_heating, &self.mesh, &self.physics) {
+            (Some(joule_config), Some(mesh), Some(physics)) => {
+                if physics.material.electrical_conductivity.is_none() {
+                    return Err(SimulationError::ConfigurationError {
+                        component: "Joule heating".to_string(),
+                        issue: format!("No electrical conductivity set for material '{}'", physics.material.name),
+                    });
+                }
+                Some(JouleHeating::new(joule_config.clone(), mesh)?)
+            }
+            _ => None,
+        };
+        
+        // Create natural convection model
+        self.natural_convection = match (&self.config.physics.natural_convection, &self.mesh, &self.physics) {
+            (Some(convection_config), Some(mesh), Some(physics)) => {
+                Some(NaturalConvection::new(convection_config.clone(), mesh, &physics.material)?)
+            }
+            _ => None,
+        };
+        
+        // Create reaction model for materials that carry reactions
+        self.reactions = match (&self.mesh, &self.physics) {
+            (Some(mesh), Some(physics)) if !physics.material.reactions.is_empty() => {
+                Some(ReactionModel::new(physics.material.reactions.clone(), mesh)?)
+            }
+            _ => None,
+        };
+        self.reaction_history = ReactionHistory {
+            names: self.reactions.iter().flat_map(|model| model.reactions.iter().map(|r| r.name.clone())).collect(),
+            snapshots: Vec::new(),
+        };
+        
+        // Create moisture model for wet charge
+        self.moisture = match (&self.config.physics.moisture, &self.mesh, &self.physics) {
+            (Some(moisture_config), Some(mesh), Some(physics)) => {
+                let mut moisture = MoistureModel::new(moisture_config.clone(), mesh, physics)?;
+                if let Some(ref regions) = self.regions {
+                    moisture.confine_to_charge(regions);
+                }
+                Some(moisture)
+            }
+            _ => None,
+        };
+        self.drying_history = DryingHistory::default();
+        
+        // Compile formula heat sources
+        self.formula_sources = match &self.mesh {
+            Some(mesh) if !self.config.physics.heat_sources.is_empty() => Some(FormulaSources::new(
+                self.config.physics.heat_sources.clone(),
+                mesh,
+                &mut self.formula_engine,
+            )?),
+            _ => None,
+        };
+        
+        // Initialize temperature field, uniform or from T0(r, z)
         if let Some(ref mesh) = self.mesh {
-            self.temperature_field = Some(mesh.create_temperature_array(self.config.physics.initial_temperature));
+            let initial_temperature = self.config.physics.initial_temperature;
+            let mut field = mesh.create_temperature_array(initial_temperature);
+            if let (Some(formula), Some(physics)) = (&self.config.physics.initial_temperature_formula, &self.physics) {
+                let compiled = self.formula_engine.compile(formula)?;
+                for ((i, j), value) in field.indexed_iter_mut() {
+                    let context = physics.formula_context(mesh.r_coords[i], mesh.z_coords[j], initial_temperature, 0.0);
+                    *value = self.formula_engine.evaluate_in_context(&compiled, &context)?;
+                    if *value <= 0.0 {
+                        return Err(SimulationError::InvalidParameter {
+                            parameter: "initial temperature formula".to_string(),
+                            value: format!("{} K at r = {} m, z = {} m", value, context.r, context.z),
+                            range: "> 0 K".to_string(),
+                        });
+                    }
+                }
+            }
+            self.temperature_field = Some(field);
         }
         
+        // Locate probes and record their initial temperatures
+        self.probes = match (&self.mesh, &self.temperature_field) {
+            (Some(mesh), Some(temp_field)) if !self.config.probes.is_empty() => {
+                let mut recorder = ProbeRecorder::new(&self.config.probes, mesh)?;
+                recorder.record(0.0, temp_field);
+                Some(recorder)
+            }
+            _ => None,
+        };
+        
+        // Start the controller from the common power of the controlled torches
+        self.controller = match (&self.config.controller, &self.physics) {
+            (Some(controller_config), Some(physics)) => {
+                let torches = controller_config.controlled_torches(physics.torches.len());
+                let initial_power = torches.iter().map(|&k| physics.torches[k].power).sum::<f64>() / torches.len() as f64;
+                Some(PidController::new(controller_config.clone(), initial_power))
+            }
+            _ => None,
+        };
+        
         // Initialize state manager
         self.state_manager = Some(SimulationStateManager::new(self.config.clone()));
         
+        // Region and drying property multipliers at the initial field, so the
+        // initial energy snapshots use the heat capacity of later steps
+        if let (Some(physics), Some(temp_field)) = (&self.physics, &self.temperature_field) {
+            if let Some(ref mut moisture) = self.moisture {
+                moisture.update_properties(physics, temp_field)?;
+            }
+            if let Some(ref mut regions) = self.regions {
+                regions.update_properties(physics, temp_field)?;
+            }
+        }
+        if self.solver.is_some() {
+            self.assemble_property_multipliers();
+        }
+        
         // Initialize energy monitor
         if let (Some(ref mesh), Some(ref physics), Some(ref temp_field)) = 
             (&self.mesh, &self.physics, &self.temperature_field) {
@@ -468,6 +886,11 @@ impl SimulationEngine {
             self.energy_monitor.set_initial_energy(initial_energy);
         }
         
+        self.energy_ledger = match (&self.physics, &self.temperature_field) {
+            (Some(physics), Some(temp_field)) => Some(EnergyLedger::new(physics, temp_field)),
+            _ => None,
+        };
+        
         log::info!("Simulation initialized successfully");
         Ok(())
     }
@@ -521,6 +944,168 @@ impl SimulationEngine {
         }
     }
     
+    /// Fail on the first material property that could not be evaluated
+    fn check_property_errors(&self) -> Result<()> {
+        match self.physics.as_ref().and_then(|physics| physics.take_property_error()) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+    
+    /// Update coupled physics models whose update interval divides the step
+    /// count and pass their surface fluxes and volumetric sources to the solver
+    fn update_coupled_physics(&mut self, time_step: usize, time: f64) -> Result<()> {
+        let mesh = self.mesh.as_ref().unwrap();
+        let physics = self.physics.as_ref().unwrap();
+        let temperature_field = self.temperature_field.as_ref().unwrap();
+        let solver = self.solver.as_mut().unwrap();
+        
+        // Surface-to-surface radiation exchange inside the cavity
+        if let Some(ref radiation) = self.enclosure_radiation {
+            if time_step.is_multiple_of(radiation.config.update_interval) {
+                // Material emissivity at the mean field temperature
+                let emissivity = radiation.config.surface_emissivity.unwrap_or_else(|| {
+                    physics.get_emissivity(temperature_field.mean().unwrap_or(self.config.physics.initial_temperature))
+                });
+                
+                let flux = radiation.calculate_surface_flux(mesh, temperature_field, emissivity)?;
+                solver.surface_heat_flux = Some(flux);
+            }
+        }
+        
+        // Volumetric sources are summed over all active models
+        let mut sources_changed = false;
+        
+        if let Some(ref mut p1) = self.p1_radiation {
+            if time_step.is_multiple_of(p1.config.update_interval) {
+                p1.update(mesh, temperature_field)?;
+                sources_changed = true;
+            }
+        }
+        
+        if let Some(ref mut joule) = self.joule_heating {
+            if time_step.is_multiple_of(joule.config.update_interval) {
+                joule.update(mesh, &physics.material, temperature_field)?;
+                sources_changed = true;
+            }
+        }
+        
+        // Natural convection, drying and region properties follow the field every step
+        if let Some(ref mut convection) = self.natural_convection {
+            convection.update(mesh, physics, temperature_field);
+        }
+        if let Some(ref mut moisture) = self.moisture {
+            moisture.update_properties(physics, temperature_field)?;
+        }
+        if let Some(ref mut regions) = self.regions {
+            regions.update_properties(physics, temperature_field)?;
+        }
+        if let Some(ref mut sources) = self.formula_sources {
+            sources.update(mesh, physics, self.regions.as_ref(), temperature_field, time, &mut self.formula_engine)?;
+            sources_changed = true;
+        }
+        if let Some(ref mut ratio) = self.axial_conductivity_ratio {
+            for ((i, j), value) in ratio.indexed_iter_mut() {
+                let material = self.regions.as_ref().map_or(&physics.material, |regions| regions.material_at(i, j));
+                *value = material.axial_conductivity_ratio(temperature_field[[i, j]], Some(&mut self.formula_engine))?;
+            }
+            self.solver.as_mut().unwrap().axial_conductivity_ratio = Some(ratio.clone());
+        }
+        self.assemble_property_multipliers();
+        
+        if sources_changed {
+            self.assemble_volumetric_sources();
+        }
+        
+        Ok(())
+    }
+    
+    /// Append the current energy balance to the ledger time series
+    fn close_energy_ledger(&mut self, time: f64) {
+        if let Some(ref mut ledger) = self.energy_ledger {
+            ledger.snapshot(time);
+        }
+    }
+    
+    /// Run the power controller if its interval has elapsed and apply the new torch power
+    fn update_controller(&mut self, time: f64) {
+        if let Some(ref mut controller) = self.controller {
+            if !controller.is_due(time) {
+                return;
+            }
+            
+            let mesh = self.mesh.as_ref().unwrap();
+            let physics = self.physics.as_mut().unwrap();
+            let measurement = controller.measure(mesh, self.temperature_field.as_ref().unwrap(), self.probes.as_ref());
+            let power = controller.update(time, measurement);
+            
+            for k in controller.config.controlled_torches(physics.torches.len()) {
+                physics.torches[k].power = power;
+            }
+        }
+    }
+    
+    /// Advance reaction conversions over the time step and update their heat source
+    fn advance_reactions(&mut self, dt: f64) {
+        if let Some(ref mut reactions) = self.reactions {
+            let physics = self.physics.as_ref().unwrap();
+            let temperature_field = self.temperature_field.as_ref().unwrap();
+            
+            reactions.advance(temperature_field, |t| physics.get_density(t), dt);
+            self.assemble_volumetric_sources();
+        }
+    }
+    
+    /// Combine the property multipliers of all coupled models and pass them to the solver
+    fn assemble_property_multipliers(&mut self) {
+        let mut conductivity: Vec<&Array2<f64>> = Vec::new();
+        let mut capacity: Vec<&Array2<f64>> = Vec::new();
+        
+        if let Some(ref convection) = self.natural_convection {
+            conductivity.push(&convection.multiplier);
+        }
+        if let Some(moisture) = self.moisture.as_ref().filter(|moisture| moisture.config.dried_material.is_some()) {
+            conductivity.push(&moisture.conductivity_ratio);
+            capacity.push(&moisture.capacity_ratio);
+        }
+        if let Some(ref regions) = self.regions {
+            conductivity.push(&regions.conductivity_ratio);
+            capacity.push(&regions.capacity_ratio);
+        }
+        
+        let product = |factors: Vec<&Array2<f64>>| {
+            factors.into_iter().fold(None, |total: Option<Array2<f64>>, factor| match total {
+                Some(total) => Some(total * factor),
+                None => Some(factor.clone()),
+            })
+        };
+        
+        let solver = self.solver.as_mut().unwrap();
+        solver.conductivity_multiplier = product(conductivity);
+        solver.capacity_multiplier = product(capacity);
+    }
+    
+    /// Sum the volumetric heat sources of all coupled models and pass them to the solver
+    fn assemble_volumetric_sources(&mut self) {
+        let mesh = self.mesh.as_ref().unwrap();
+        let mut total = Array2::zeros((mesh.nr, mesh.nz));
+        
+        if let Some(ref p1) = self.p1_radiation {
+            total += &p1.heat_source;
+        }
+        if let Some(ref joule) = self.joule_heating {
+            total += &joule.heat_source;
+        }
+        if let Some(ref reactions) = self.reactions {
+            total += &reactions.heat_source;
+        }
+        if let Some(ref sources) = self.formula_sources {
+            total += &sources.heat_source;
+        }
+        
+        self.solver.as_mut().unwrap().volumetric_heat_source = Some(total);
+    }
+    
     /// Main simulation loop with progress tracking and cancellation support
     fn run_simulation_loop(&mut self) -> Result<(usize, f64)> {
         let mut current_time = 0.0;
@@ -539,6 +1124,10 @@ impl SimulationEngine {
                 });
             }
             
+            // Update coupled physics models (radiation, Joule heating, convection)
+            // before the time step so the stability limit sees their effect
+            self.update_coupled_physics(time_step, current_time)?;
+            
             // Calculate stable time step
             let stable_dt = {
                 let mesh = self.mesh.as_ref().unwrap();
@@ -557,6 +1146,9 @@ impl SimulationEngine {
                 solver.check_stability(dt, mesh, physics)?;
             }
             
+            // Advance chemical reactions over this step
+            self.advance_reactions(dt);
+            
             // Solve one time step
             {
                 let mesh = self.mesh.as_ref().unwrap();
@@ -564,8 +1156,9 @@ impl SimulationEngine {
                 let solver = self.solver.as_mut().unwrap();
                 let temperature_field = self.temperature_field.as_mut().unwrap();
                 
-                solver.solve_time_step(temperature_field, mesh, physics, dt)
-                    .map_err(|e| match e {
+                let step = solver.solve_time_step(temperature_field, mesh, physics, dt);
+                self.check_property_errors()?;
+                step.map_err(|e| match e {
                         SimulationError::NumericalInstability { .. } => {
                             SimulationError::NumericalInstability {
                                 step: time_step,
@@ -576,14 +1169,53 @@ impl SimulationEngine {
                     })?;
             }
             
+            // Evaporate moisture heated past the boiling point
+            let drying_energy = match self.moisture {
+                Some(ref mut moisture) => moisture.evaporate(
+                    self.mesh.as_ref().unwrap(),
+                    self.physics.as_ref().unwrap(),
+                    self.temperature_field.as_mut().unwrap(),
+                    self.solver.as_ref().and_then(|solver| solver.capacity_multiplier.as_ref()),
+                ),
+                None => 0.0,
+            };
+            
+            // Book the step in the energy ledger before the controller changes torch powers
+            if let Some(ref mut ledger) = self.energy_ledger {
+                ledger.record_step(
+                    self.mesh.as_ref().unwrap(),
+                    self.physics.as_ref().unwrap(),
+                    self.solver.as_ref().unwrap(),
+                    self.temperature_field.as_ref().unwrap(),
+                    dt,
+                    drying_energy,
+                );
+            }
+            
             // Update time and step counter
             current_time += dt;
             time_step += 1;
             
+            // Sample probes at full time resolution
+            if let Some(ref mut probes) = self.probes {
+                probes.record(current_time, self.temperature_field.as_ref().unwrap());
+            }
+            
+            // Adjust torch powers every control interval
+            self.update_controller(current_time);
+            
             // Store time step data for animation if interval has passed
             if current_time - self.last_stored_time >= self.storage_interval {
                 self.store_time_step_data(current_time, time_step);
                 self.last_stored_time = current_time;
+                
+                if let (Some(ref reactions), Some(ref mesh)) = (&self.reactions, &self.mesh) {
+                    self.reaction_history.snapshots.push(reactions.snapshot(mesh, current_time));
+                }
+                if let (Some(ref moisture), Some(ref mesh), Some(ref physics)) = (&self.moisture, &self.mesh, &self.physics) {
+                    self.drying_history.snapshots.push(moisture.snapshot(mesh, physics, current_time));
+                }
+                self.close_energy_ledger(current_time);
             }
             
             // Calculate energy and monitor conservation
@@ -594,11 +1226,13 @@ impl SimulationEngine {
                 
                 let energy_after = self.calculate_total_energy(mesh, physics, temperature_field);
                 let energy_input = self.calculate_energy_input(mesh, physics, dt);
-                let energy_loss = self.calculate_energy_loss(mesh, physics, temperature_field, dt);
+                let energy_loss = self.calculate_energy_loss(mesh, physics, temperature_field, dt) + drying_energy;
                 
                 self.energy_monitor.update(energy_after, energy_input, energy_loss);
             }
             
+            self.check_property_errors()?;
+            
             // Check energy conservation (warn if error > 10%)
             if self.energy_monitor.conservation_error > 0.1 {
                 log::warn!(
@@ -627,11 +1261,18 @@ impl SimulationEngine {
             }
         }
         
+        // Close the ledger at the final time if the last step was not stored
+        if self.energy_ledger.as_ref().is_some_and(|ledger| ledger.entries.last().is_none_or(|entry| entry.time < current_time)) {
+            self.close_energy_ledger(current_time);
+        }
+        
         log::info!("Simulation loop completed: {} steps, {:.3}s", time_step, current_time);
         Ok((time_step, current_time))
     }
     
     /// Calculate total thermal energy in the system
+    ///
+    /// Includes the solver's local heat capacity multiplier (regions, drying).
     fn calculate_total_energy(&self, mesh: &CylindricalMesh, physics: &PlasmaPhysics, temperature_field: &Array2<f64>) -> f64 {
         let mut total_energy = 0.0;
         
@@ -639,8 +1280,9 @@ impl SimulationEngine {
             for j in 0..mesh.nz {
                 let temperature = temperature_field[[i, j]];
                 let volume = mesh.get_cell_volume(i, j);
-                let density = physics.get_density();
-                let specific_heat = physics.get_specific_heat(temperature);
+                let density = physics.get_density(temperature);
+                let specific_heat = physics.get_specific_heat(temperature)
+                    * self.solver.as_ref().map_or(1.0, |solver| solver.capacity_multiplier_at(i, j));
                 
                 // Energy = ρ * V * cp * (T - T_ref)
                 let reference_temp = self.config.physics.ambient_temperature;
@@ -664,6 +1306,11 @@ impl SimulationEngine {
                 
                 // Energy input = Q * V * dt
                 total_input += heat_source * volume * dt;
+                
+                // Coupled volumetric sources (P1 radiation, Joule heating) applied by the solver
+                if let Some(source) = self.solver.as_ref().and_then(|s| s.volumetric_heat_source.as_ref()) {
+                    total_input += source[[i, j]] * volume * dt;
+                }
             }
         }
         
@@ -671,21 +1318,43 @@ impl SimulationEngine {
     }
     
     /// Calculate energy loss through boundaries during time step
+    /// 
+    /// The loss at each surface node is the conductive flux arriving from the
+    /// adjacent interior node, k * (T_interior - T_surface) / d, which equals the
+    /// heat leaving through the boundary condition. The conductivity includes
+    /// the solver's local multiplier and axial ratio, as in the solver.
     fn calculate_energy_loss(&self, mesh: &CylindricalMesh, physics: &PlasmaPhysics, temperature_field: &Array2<f64>, dt: f64) -> f64 {
+        use mesh::BoundaryType;
+        
         let mut total_loss = 0.0;
         
-        // Calculate losses at outer boundary
-        for j in 0..mesh.nz {
-            let i = mesh.nr - 1; // Outer boundary
-            let temperature = temperature_field[[i, j]];
-            let area = mesh.get_cell_area_radial(i, j);
-            
-            let q_conv = physics.calculate_convection_loss(temperature);
-            let q_rad = physics.calculate_radiation_loss(temperature, physics.material.emissivity);
-            let total_flux = q_conv + q_rad;
-            
-            // Energy loss = q * A * dt
-            total_loss += total_flux * area * dt;
+        for i in 0..mesh.nr {
+            for j in 0..mesh.nz {
+                let (interior, distance, area) = match mesh.get_boundary_type(i, j) {
+                    BoundaryType::OuterWall => ((i - 1, j), mesh.dr, mesh.get_cell_area_radial(i, j)),
+                    BoundaryType::Bottom => ((i, j + 1), mesh.dz, mesh.get_cell_area_axial(i, j)),
+                    BoundaryType::Top => ((i, j - 1), mesh.dz, mesh.get_cell_area_axial(i, j)),
+                    BoundaryType::Axis | BoundaryType::Interior => continue,
+                };
+                if mesh.get_boundary_type(interior.0, interior.1) != BoundaryType::Interior {
+                    continue;
+                }
+                
+                let temperature = temperature_field[[i, j]];
+                let axial_ratio = match mesh.get_boundary_type(i, j) {
+                    BoundaryType::Top | BoundaryType::Bottom => self.solver.as_ref()
+                        .and_then(|solver| solver.axial_conductivity_ratio.as_ref())
+                        .map_or(1.0, |ratio| ratio[[i, j]]),
+                    _ => 1.0,
+                };
+                let k = physics.get_thermal_conductivity(temperature)
+                    * self.solver.as_ref().map_or(1.0, |solver| solver.conductivity_multiplier_at(i, j))
+                    * axial_ratio;
+                let flux = k * (temperature_field[interior] - temperature) / distance;
+                
+                // Energy loss = q * A * dt
+                total_loss += flux * area * dt;
+            }
         }
         
         total_loss
@@ -744,6 +1413,18 @@ impl SimulationEngine {
             min_temperature,
             avg_temperature,
             time_series_data: Some(self.time_series_data.clone()),
+            joule_heating_power: self.joule_heating.as_ref().map(|joule| joule.total_power),
+            coolant_circuits: self.solver.as_ref().map_or_else(Vec::new, |solver| solver.coolant_reports.clone()),
+            interface_reports: match (&self.regions, &self.mesh) {
+                (Some(regions), Some(mesh)) => regions.interface_reports(mesh, temperature_field),
+                _ => Vec::new(),
+            },
+            reaction_history: self.reaction_history.clone(),
+            drying_history: self.drying_history.clone(),
+            probe_histories: self.probes.as_ref().map_or_else(Vec::new, |probes| probes.histories.clone()),
+            controller_history: self.controller.as_ref().map_or_else(Default::default, |controller| controller.history.clone()),
+            energy_ledger: self.energy_ledger.clone().unwrap_or_default(),
+            property_warnings: property_table::take_range_warnings(),
         })
     }
     
@@ -954,6 +1635,64 @@ mod tests {
         assert!(results.max_temperature >= results.min_temperature);
     }
     
+    #[test]
+    fn test_formula_properties_tabulated_at_initialize() {
+        let mut engine = SimulationEngine::new(SimulationConfig::default()).unwrap();
+        engine.initialize().unwrap();
+        let material = &engine.physics.as_ref().unwrap().material;
+        let tolerance = FormulaTabulation::default().tolerance;
+        
+        // Carbon steel conductivity and resistivity-based electrical conductivity are formulas
+        let library = MaterialLibrary::get_material("Carbon Steel").unwrap();
+        let mut formula_engine = FormulaEngine::new();
+        for (table, formula) in [
+            (&material.thermal_conductivity, &library.thermal_conductivity),
+            (material.electrical_conductivity.as_ref().unwrap(), library.electrical_conductivity.as_ref().unwrap()),
+        ] {
+            assert!(matches!(table, Property::FormulaTable { .. }));
+            for t in [300.0, 1111.0, 2500.0] {
+                let exact = formula.evaluate(t, Some(&mut formula_engine)).unwrap();
+                assert!((table.evaluate(t, None).unwrap() - exact).abs() <= tolerance * exact);
+            }
+            // Outside the sampled range the formula itself is evaluated
+            assert!(table.evaluate(8000.0, None).is_err());
+            let exact = formula.evaluate(8000.0, Some(&mut formula_engine)).unwrap();
+            assert_eq!(table.evaluate(8000.0, Some(&mut formula_engine)).unwrap(), exact);
+        }
+        
+        // Disabled tabulation keeps the formulas
+        let mut config = SimulationConfig::default();
+        config.solver.formula_tabulation = None;
+        let mut engine = SimulationEngine::new(config).unwrap();
+        engine.initialize().unwrap();
+        assert!(matches!(engine.physics.as_ref().unwrap().material.thermal_conductivity, Property::Formula(_)));
+        
+        // The solver conducts with the table rather than the formula behind it
+        let mut config = SimulationConfig::default();
+        config.mesh.custom_resolution = Some((11, 11));
+        config.torches[0].position = (0.05, 0.1);
+        config.torches[0].sigma = 0.01;
+        let mut engine = SimulationEngine::new(config).unwrap();
+        engine.initialize().unwrap();
+        let relaxation = |engine: &SimulationEngine| {
+            let mesh = engine.mesh.as_ref().unwrap();
+            let mut temperature = mesh.create_temperature_array(800.0);
+            for i in 0..mesh.nr {
+                temperature[[i, 5]] = 820.0;
+            }
+            let mut solver = HeatSolver::new(SolverMethod::ForwardEuler);
+            solver.solve_time_step(&mut temperature, mesh, engine.physics.as_ref().unwrap(), 1.0).unwrap();
+            820.0 - temperature[[5, 5]]
+        };
+        let tabulated = relaxation(&engine);
+        match engine.physics.as_mut().unwrap().material.thermal_conductivity {
+            Property::FormulaTable { ref mut table, .. } => table.points.iter_mut().for_each(|point| point.1 *= 2.0),
+            ref other => panic!("Expected a tabulated conductivity, got {:?}", other),
+        }
+        assert!(tabulated > 0.0);
+        assert!((relaxation(&engine) / tabulated - 2.0).abs() < 1e-9);
+    }
+    
     #[test]
     fn test_config_validation() {
         let mut config = SimulationConfig::default();
@@ -976,6 +1715,461 @@ mod tests {
         assert!(SimulationEngine::validate_config(&config).is_err());
     }
     
+    #[test]
+    fn test_boundary_config_validation() {
+        let mut config = SimulationConfig::default();
+        config.boundaries.top = Some(BoundarySpec::uniform(BoundaryCondition::FixedTemperature {
+            temperature: 350.0,
+        }));
+        assert!(SimulationEngine::validate_config(&config).is_ok());
+        
+        config.boundaries.bottom = Some(BoundarySpec::uniform(BoundaryCondition::FixedTemperature {
+            temperature: -10.0,
+        }));
+        assert!(SimulationEngine::validate_config(&config).is_err());
+    }
+    
+    #[test]
+    fn test_simulation_with_cooled_jacket() {
+        let mut config = SimulationConfig::default();
+        config.physics.simulation_time = 0.5;
+        config.physics.initial_temperature = 800.0;
+        config.mesh.custom_resolution = Some((20, 20));
+        config.boundaries.outer_wall = Some(BoundarySpec::uniform(BoundaryCondition::CooledJacket {
+            inlet_temperature: 300.0,
+            mass_flow_rate: 0.2,
+            specific_heat: 4180.0,
+            heat_transfer_coefficient: 1000.0,
+        }));
+        
+        let mut engine = SimulationEngine::new(config).unwrap();
+        let results = engine.run().unwrap();
+        
+        assert_eq!(results.coolant_circuits.len(), 1);
+        let circuit = &results.coolant_circuits[0];
+        assert_eq!(circuit.boundary, "outer wall");
+        assert!(circuit.outlet_temperature > circuit.inlet_temperature);
+        
+        let expected = 0.2 * 4180.0 * (circuit.outlet_temperature - circuit.inlet_temperature);
+        assert!((circuit.heat_removed - expected).abs() < 1e-6 * expected);
+        
+        // Coolant warms on its way up the wall
+        let nz = results.final_temperature_field[0].len();
+        let wall = &results.final_temperature_field[19];
+        assert!(wall[nz - 2] > wall[1]);
+    }
+    
+    #[test]
+    fn test_simulation_with_shell_contact() {
+        let mut config = SimulationConfig::default();
+        config.physics.simulation_time = 2.0;
+        config.physics.initial_temperature = 800.0;
+        config.mesh.custom_resolution = Some((20, 20));
+        config.material.material_name = "Ceramic".to_string();
+        config.material.regions = vec![MaterialRegion {
+            name: "shell".to_string(),
+            material_name: "Carbon Steel".to_string(),
+            r_range: (0.8, 1.0),
+            z_range: (0.0, 2.0),
+        }];
+        config.material.interfaces = vec![ContactInterface {
+            region_a: "base".to_string(),
+            region_b: "shell".to_string(),
+            conductance: 50.0,
+        }];
+        config.boundaries.outer_wall = Some(BoundarySpec::uniform(BoundaryCondition::FixedTemperature {
+            temperature: 300.0,
+        }));
+        
+        let mut engine = SimulationEngine::new(config).unwrap();
+        let results = engine.run().unwrap();
+        
+        // Heat leaves the lining through the contact into the cooled shell
+        assert_eq!(results.interface_reports.len(), 1);
+        let report = &results.interface_reports[0];
+        assert!(report.area > 0.0);
+        assert!(report.heat_flow > 0.0);
+        assert!(report.mean_temperature_jump > 0.0);
+        assert!(report.max_temperature_jump >= report.mean_temperature_jump);
+    }
+    
+    #[test]
+    fn test_simulation_fails_on_property_errors() {
+        let mut config = SimulationConfig::default();
+        config.physics.simulation_time = 1.0;
+        config.mesh.custom_resolution = Some((20, 20));
+        
+        let mut engine = SimulationEngine::new(config).unwrap();
+        engine.initialize().unwrap();
+        
+        // A density formula that breaks once the torch heats the charge stops the run
+        engine.physics.as_mut().unwrap().material.density =
+            Property::Formula("if T > 310.0 { sqrt(-1.0) } else { 7850.0 }".to_string());
+        let error = engine.run().unwrap_err();
+        assert!(matches!(error, SimulationError::FormulaError { .. }), "{}", error);
+    }
+    
+    #[test]
+    fn test_simulation_reports_table_extrapolation() {
+        let mut config = SimulationConfig::default();
+        config.physics.simulation_time = 0.5;
+        config.physics.initial_temperature = 800.0;
+        config.mesh.custom_resolution = Some((20, 20));
+        config.material.regions = vec![MaterialRegion {
+            name: "lining".to_string(),
+            material_name: "Ceramic".to_string(),
+            r_range: (0.8, 1.0),
+            z_range: (0.0, 2.0),
+        }];
+        
+        let mut engine = SimulationEngine::new(config).unwrap();
+        engine.initialize().unwrap();
+        
+        // Give the lining measured data without touching the shared material library,
+        let lining = &mut engine.regions.as_mut().unwrap().materials[1];
+        lining.name = "Tabulated Lining".to_string();
+        lining.thermal_conductivity = Property::Tabulated(
+            PropertyTable::new(vec![(300.0, 30.0), (600.0, 25.0)]).with_interpolation(Interpolation::MonotoneCubic),
+        );
+        // and the base charge a table that ends below the initial temperature
+        engine.physics.as_mut().unwrap().material.thermal_conductivity =
+            Property::Tabulated(PropertyTable::new(vec![(300.0, 50.0), (500.0, 45.0)]));
+        let results = engine.run().unwrap();
+        
+        let charge = results
+            .property_warnings
+            .iter()
+            .find(|warning| warning.material == "Carbon Steel" && warning.property == "thermal conductivity")
+            .unwrap();
+        assert_eq!(charge.table_range, (300.0, 500.0));
+        assert!(charge.min_queried > 500.0);
+        
+        // The lining runs hotter than its measured data
+        let warning = results
+            .property_warnings
+            .iter()
+            .find(|warning| warning.material == "Tabulated Lining" && warning.property == "thermal conductivity")
+            .unwrap();
+        assert_eq!(warning.table_range, (300.0, 600.0));
+        assert!(warning.max_queried > 600.0);
+        assert!(warning.count > 0);
+    }
+    
+    #[test]
+    fn test_simulation_with_cooled_roof() {
+        let mut config = SimulationConfig::default();
+        config.physics.simulation_time = 0.1;
+        config.physics.initial_temperature = 600.0;
+        config.boundaries.top = Some(BoundarySpec::uniform(BoundaryCondition::FixedTemperature {
+            temperature: 300.0,
+        }));
+        
+        let mut engine = SimulationEngine::new(config).unwrap();
+        let results = engine.run().unwrap();
+        
+        // Roof nodes (away from the axis and wall) are held at the fixed temperature
+        let nz = results.final_temperature_field[0].len();
+        assert!((results.final_temperature_field[10][nz - 1] - 300.0).abs() < 1e-10);
+    }
+    
+    #[test]
+    fn test_simulation_with_enclosure_radiation() {
+        let run = |radiation: Option<EnclosureRadiationConfig>| {
+            let mut config = SimulationConfig::default();
+            config.physics.simulation_time = 0.5;
+            config.physics.initial_temperature = 600.0;
+            config.physics.enclosure_radiation = radiation;
+            config.boundaries.outer_wall = Some(BoundarySpec::uniform(BoundaryCondition::Adiabatic));
+            config.boundaries.top = Some(BoundarySpec::uniform(BoundaryCondition::Adiabatic));
+            config.boundaries.bottom = Some(BoundarySpec::uniform(BoundaryCondition::FixedTemperature {
+                temperature: 1800.0,
+            }));
+            
+            let mut engine = SimulationEngine::new(config).unwrap();
+            engine.run().unwrap()
+        };
+        
+        let without = run(None);
+        let with = run(Some(EnclosureRadiationConfig {
+            update_interval: 1,
+            ..EnclosureRadiationConfig::default()
+        }));
+        
+        // The roof is heated by radiation from the hot melt surface
+        let nz = with.final_temperature_field[0].len();
+        let roof_with = with.final_temperature_field[10][nz - 1];
+        let roof_without = without.final_temperature_field[10][nz - 1];
+        assert!(roof_with > roof_without + 1.0);
+    }
+    
+    #[test]
+    fn test_simulation_with_p1_radiation() {
+        let mut config = SimulationConfig::default();
+        config.physics.simulation_time = 0.5;
+        config.physics.initial_temperature = 1500.0;
+        config.material.material_name = "Glass".to_string();
+        config.physics.p1_radiation = Some(P1RadiationConfig::default());
+        
+        let mut engine = SimulationEngine::new(config.clone()).unwrap();
+        let with = engine.run().unwrap();
+        
+        config.physics.p1_radiation = None;
+        let mut engine = SimulationEngine::new(config).unwrap();
+        let without = engine.run().unwrap();
+        
+        // Interior glass near the surface radiates to the cold surroundings
+        let nz = with.final_temperature_field[0].len();
+        let nr = with.final_temperature_field.len();
+        assert!(with.final_temperature_field[nr - 2][nz / 2] < without.final_temperature_field[nr - 2][nz / 2]);
+        
+        // Steel is opaque, so enabling P1 without an absorption coefficient fails
+        let mut config = SimulationConfig::default();
+        config.physics.p1_radiation = Some(P1RadiationConfig::default());
+        let mut engine = SimulationEngine::new(config).unwrap();
+        assert!(engine.initialize().is_err());
+    }
+    
+    #[test]
+    fn test_formula_initial_field_and_heat_source() {
+        let mut config = SimulationConfig::default();
+        config.physics.simulation_time = 0.5;
+        config.mesh.custom_resolution = Some((20, 20));
+        config.physics.initial_temperature_formula = Some("if z < 0.3 { 1200.0 } else { T }".to_string());
+        
+        let mut engine = SimulationEngine::new(config.clone()).unwrap();
+        engine.initialize().unwrap();
+        let field = engine.get_temperature_field().unwrap();
+        assert_eq!(field[[10, 0]], 1200.0);
+        assert_eq!(field[[10, 19]], 298.15);
+        let without_source = engine.run().unwrap();
+        
+        // A source confined to the upper outer corner heats only that region
+        config.physics.heat_sources = vec![FormulaHeatSource::new("corner", "if r > 0.7 && z > 1.5 { 5.0e7 } else { 0.0 }")];
+        let mut engine = SimulationEngine::new(config.clone()).unwrap();
+        let with_source = engine.run().unwrap();
+        assert!(with_source.final_temperature_field[17][18] > without_source.final_temperature_field[17][18] + 1.0);
+        assert!((with_source.final_temperature_field[2][2] - without_source.final_temperature_field[2][2]).abs() < 1e-6);
+        
+        // Malformed formulas are rejected when the configuration is validated
+        config.physics.heat_sources = vec![FormulaHeatSource::new("broken", "if r > { 1.0 }")];
+        assert!(SimulationEngine::new(config.clone()).is_err());
+        config.physics.heat_sources.clear();
+        config.physics.initial_temperature_formula = Some("T -".to_string());
+        assert!(SimulationEngine::new(config).is_err());
+    }
+    
+    #[test]
+    fn test_simulation_with_joule_heating() {
+        let mut config = SimulationConfig::default();
+        config.physics.simulation_time = 0.5;
+        config.mesh.custom_resolution = Some((20, 20));
+        config.physics.joule_heating = Some(JouleHeatingConfig {
+            update_interval: 1,
+            ..JouleHeatingConfig::default()
+        });
+        
+        let mut engine = SimulationEngine::new(config.clone()).unwrap();
+        let transferred = engine.run().unwrap();
+        
+        config.physics.joule_heating = None;
+        let mut engine = SimulationEngine::new(config).unwrap();
+        let non_transferred = engine.run().unwrap();
+        
+        assert!(transferred.joule_heating_power.unwrap() > 0.0);
+        assert!(non_transferred.joule_heating_power.is_none());
+        
+        // Current concentrates under the arc attachment on the axis
+        let nz = transferred.final_temperature_field[0].len();
+        assert!(transferred.final_temperature_field[1][nz - 2] > non_transferred.final_temperature_field[1][nz - 2]);
+    }
+    
+    #[test]
+    fn test_simulation_with_natural_convection() {
+        let mut config = SimulationConfig::default();
+        config.physics.simulation_time = 0.5;
+        config.physics.initial_temperature = 1900.0;
+        config.mesh.custom_resolution = Some((20, 20));
+        config.physics.natural_convection = Some(NaturalConvectionConfig::default());
+        
+        let mut engine = SimulationEngine::new(config).unwrap();
+        let results = engine.run().unwrap();
+        
+        let convection = engine.natural_convection.as_ref().unwrap();
+        assert!(convection.liquid_fraction.iter().any(|&f| f > 0.0));
+        assert!(convection.multiplier.iter().all(|&m| m >= 1.0));
+        assert!(results.max_temperature.is_finite());
+    }
+    
+    #[test]
+    fn test_simulation_with_reactions() {
+        let mut config = SimulationConfig::default();
+        config.physics.simulation_time = 1.0;
+        config.physics.initial_temperature = 700.0;
+        config.mesh.custom_resolution = Some((20, 20));
+        config.material.material_name = "Medical Waste".to_string();
+        
+        let mut engine = SimulationEngine::new(config).unwrap();
+        let results = engine.run().unwrap();
+        
+        let history = &results.reaction_history;
+        assert_eq!(history.names, vec!["pyrolysis".to_string(), "char combustion".to_string()]);
+        assert!(!history.snapshots.is_empty());
+        
+        // Decomposition progresses monotonically
+        let pyrolysis: Vec<f64> = history.snapshots.iter().map(|s| s.mean_conversion[0]).collect();
+        assert!(pyrolysis.windows(2).all(|w| w[1] >= w[0]));
+        assert!(*pyrolysis.last().unwrap() > 0.0);
+    }
+    
+    #[test]
+    fn test_simulation_with_moisture() {
+        let mut config = SimulationConfig::default();
+        config.physics.simulation_time = 2.0;
+        config.physics.initial_temperature = 360.0;
+        config.mesh.custom_resolution = Some((20, 20));
+        config.material.material_name = "Wood".to_string();
+        config.torches[0].power = 1000.0;
+        config.physics.moisture = Some(MoistureConfig {
+            dried_material: Some("Wood".to_string()),
+            ..MoistureConfig::default()
+        });
+        
+        let mut engine = SimulationEngine::new(config).unwrap();
+        let results = engine.run().unwrap();
+        
+        let snapshots = &results.drying_history.snapshots;
+        assert!(!snapshots.is_empty());
+        
+        // Drying energy and vapour release accumulate over time
+        assert!(snapshots.windows(2).all(|w| w[1].drying_energy >= w[0].drying_energy));
+        let last = snapshots.last().unwrap();
+        assert!(last.drying_energy > 0.0);
+        assert!(last.vapour_released > 0.0);
+    }
+    
+    #[test]
+    fn test_simulation_with_probes() {
+        let mut config = SimulationConfig::default();
+        config.physics.simulation_time = 2.0;
+        config.mesh.custom_resolution = Some((20, 20));
+        config.torches[0].power = 1000.0;
+        config.probes.push(Probe::new("near torch", 0.1, 1.0));
+        config.probes.push(Probe::new("wall", 1.0, 1.0));
+        
+        let mut engine = SimulationEngine::new(config).unwrap();
+        let results = engine.run().unwrap();
+        
+        // One sample per time step plus the initial state
+        assert_eq!(results.probe_histories.len(), 2);
+        for history in &results.probe_histories {
+            assert_eq!(history.times.len(), results.time_steps_completed + 1);
+            assert_eq!(history.temperatures[0], 298.15);
+            assert!(history.times.windows(2).all(|w| w[1] > w[0]));
+        }
+        assert!(results.time_steps_completed + 1 > results.time_series_data.as_ref().unwrap().len());
+        
+        let near = results.probe_histories[0].temperatures.last().unwrap();
+        let wall = results.probe_histories[1].temperatures.last().unwrap();
+        assert!(near > wall);
+        
+        let invalid = SimulationConfig {
+            probes: vec![Probe::new("outside", 2.0, 1.0)],
+            ..SimulationConfig::default()
+        };
+        assert!(SimulationEngine::new(invalid).is_err());
+    }
+    
+    #[test]
+    fn test_simulation_with_power_controller() {
+        let mut config = SimulationConfig::default();
+        config.physics.simulation_time = 3.0;
+        config.mesh.custom_resolution = Some((20, 20));
+        config.probes.push(Probe::new("tc1", 0.5, 1.0));
+        config.controller = Some(ControllerConfig {
+            setpoint: Setpoint::Constant(2000.0),
+            measurement: ControlVariable::Probe("tc1".to_string()),
+            gains: PidGains { kp: 5.0, ki: 1.0, kd: 0.0 },
+            max_power: 400.0,
+            control_interval: 0.5,
+            ..Default::default()
+        });
+        
+        let mut engine = SimulationEngine::new(config).unwrap();
+        let results = engine.run().unwrap();
+        
+        // Far below the setpoint the controller drives the torch to its limit
+        let samples = &results.controller_history.samples;
+        assert_eq!(samples.len(), 6);
+        assert!(samples.iter().all(|sample| sample.setpoint == 2000.0));
+        assert_eq!(samples.last().unwrap().power, 400.0);
+        assert!(samples.last().unwrap().saturated);
+        assert_eq!(engine.physics.as_ref().unwrap().torches[0].power, 400.0);
+        
+        let invalid = SimulationConfig {
+            controller: Some(ControllerConfig {
+                measurement: ControlVariable::Probe("missing".to_string()),
+                ..Default::default()
+            }),
+            ..SimulationConfig::default()
+        };
+        assert!(SimulationEngine::new(invalid).is_err());
+    }
+    
+    #[test]
+    fn test_simulation_energy_ledger() {
+        let mut config = SimulationConfig::default();
+        config.physics.simulation_time = 2.0;
+        config.mesh.custom_resolution = Some((20, 20));
+        config.torches.push(TorchConfig { position: (0.2, 0.5), power: 50.0, ..TorchConfig::default() });
+        
+        let mut engine = SimulationEngine::new(config).unwrap();
+        let results = engine.run().unwrap();
+        
+        let entries = &results.energy_ledger.entries;
+        assert!(!entries.is_empty());
+        let last = entries.last().unwrap();
+        assert!((last.time - results.final_time).abs() < 1e-9);
+        
+        // Inputs are tracked per torch and accumulate over time
+        assert_eq!(last.torch_input.len(), 2);
+        assert!(last.torch_input[0] > last.torch_input[1]);
+        assert!(entries.windows(2).all(|w| w[1].torch_input[0] > w[0].torch_input[0]));
+        
+        assert!(last.sensible_heat > 0.0);
+        assert!(last.boundary_losses.iter().any(|loss| loss.boundary == "outer wall"));
+    }
+    
+    #[test]
+    fn test_energy_ledger_closes() {
+        // Insulated charge with a narrow torch in the corner, which deposits
+        // nothing on the interior nodes
+        let mut config = SimulationConfig::default();
+        config.physics.simulation_time = 0.5;
+        config.physics.initial_temperature = 600.0;
+        config.mesh.custom_resolution = Some((20, 20));
+        config.torches = vec![TorchConfig { position: (1.0, 2.0), power: 1.0, efficiency: 0.1, sigma: 0.01 }];
+        config.boundaries.outer_wall = Some(BoundarySpec::uniform(BoundaryCondition::Adiabatic));
+        
+        // No sources: a charge with a steel shell away from ambient stores and loses nothing
+        let mut shelled = config.clone();
+        shelled.material.regions = vec![MaterialRegion {
+            name: "shell".to_string(),
+            material_name: "Carbon Steel".to_string(),
+            r_range: (0.8, 1.0),
+            z_range: (0.0, 2.0),
+        }];
+        let results = SimulationEngine::new(shelled).unwrap().run().unwrap();
+        let last = results.energy_ledger.entries.last().unwrap();
+        assert!(last.sensible_heat.abs() < 1e-6 && last.residual.abs() < 1e-6);
+        
+        // A constant source is stored completely
+        config.physics.heat_sources = vec![FormulaHeatSource::new("uniform", "1.0e6")];
+        let results = SimulationEngine::new(config).unwrap().run().unwrap();
+        let last = results.energy_ledger.entries.last().unwrap();
+        assert!(last.coupled_sources > 0.0);
+        assert!(last.residual.abs() < 1e-6 * last.total_input());
+    }
+    
     #[test]
     fn test_energy_monitor() {
         let mut monitor = EnergyMonitor::new();
@@ -1014,6 +2208,27 @@ mod tests {
         assert_eq!(geometry.height, 2.0);
     }
     
+    #[test]
+    fn test_config_quantity_strings() {
+        let torch: TorchConfig = serde_json::from_str(
+            r#"{"position": [0.0, 1.0], "power": "0.15 MW", "efficiency": 0.8, "sigma": "100 mm"}"#,
+        ).unwrap();
+        assert!((torch.power - 150.0).abs() < 1e-9);
+        assert!((torch.sigma - 0.1).abs() < 1e-12);
+        
+        let mut json = serde_json::to_value(SimulationConfig::default()).unwrap();
+        json["physics"]["initial_temperature"] = serde_json::json!("25 degC");
+        json["physics"]["simulation_time"] = serde_json::json!("2 min");
+        let config: SimulationConfig = serde_json::from_value(json.clone()).unwrap();
+        assert!((config.physics.initial_temperature - 298.15).abs() < 1e-9);
+        assert_eq!(config.physics.simulation_time, 120.0);
+        
+        json["geometry"]["radius"] = serde_json::json!("NaN");
+        assert!(serde_json::from_value::<SimulationConfig>(json.clone()).is_err());
+        json["geometry"]["radius"] = serde_json::json!("1 kW");
+        assert!(serde_json::from_value::<SimulationConfig>(json).is_err());
+    }
+    
     #[test]
     fn test_time_series_data_storage() {
         let mut config = SimulationConfig::default();
@@ -0,0 +1,373 @@
+//! Moisture evaporation in wet charge material
+//!
+//! Each cell carries a water content (kg/m³) that evaporates at the boiling
+//! temperature. Evaporation is treated isothermally: sensible heat above the
+//! evaporation temperature is first spent on latent heat, holding the cell at
+//! the evaporation temperature until it is dry. As moisture drops, the cell
+//! properties blend from the wet material towards the dried material.
+//!
+//! Only the charge (the base material) is wet; when material regions are
+//! set, cells of other regions such as a lining start and stay dry.
+
+use crate::errors::{Result, SimulationError};
+use crate::formula::engine::FormulaEngine;
+use ndarray::Array2;
+
+/// Moisture configuration
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MoistureConfig {
+    /// Initial moisture content as a mass fraction of the wet material
+    pub initial_moisture: f64,
+    /// Evaporation temperature in K
+    pub evaporation_temperature: f64,
+    /// Latent heat of vaporization in J/kg
+    pub latent_heat: f64,
+    /// Library material whose properties apply once the charge is dry
+    /// (properties stay those of the wet material if not set)
+    pub dried_material: Option<String>,
+}
+
+impl Default for MoistureConfig {
+    fn default() -> Self {
+        Self {
+            initial_moisture: 0.3,
+            evaporation_temperature: 373.15,
+            latent_heat: 2.257e6,
+            dried_material: None,
+        }
+    }
+}
+
+impl MoistureConfig {
+    /// Validate configuration parameters
+    pub fn validate(&self) -> Result<()> {
+        use crate::errors::validation::{validate_positive, validate_range};
+
+        validate_range(self.initial_moisture, 0.0, 0.95, "initial moisture content")?;
+        validate_positive(self.evaporation_temperature, "evaporation temperature")?;
+        validate_positive(self.latent_heat, "latent heat of vaporization")?;
+        if let Some(ref name) = self.dried_material {
+            if !super::materials::MaterialLibrary::is_valid_material(name) {
+                return Err(SimulationError::MaterialError {
+                    material: name.clone(),
+                    property: "dried material".to_string(),
+                    details: "Unknown material name".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Drying progress at one point in time
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DryingSnapshot {
+    pub time: f64,
+    /// Volume fraction of the charge that is dry (less than half the initial moisture left)
+    pub dried_fraction: f64,
+    /// Largest distance from the nearest torch to a dry cell in meters
+    pub front_distance: f64,
+    /// Cumulative energy spent on evaporation in J
+    pub drying_energy: f64,
+    /// Cumulative mass of water vapour released in kg
+    pub vapour_released: f64,
+}
+
+/// Time history of drying
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct DryingHistory {
+    pub snapshots: Vec<DryingSnapshot>,
+}
+
+/// Per-cell moisture state
+pub struct MoistureModel {
+    pub config: MoistureConfig,
+    /// Water content in kg/m³
+    pub water_content: Array2<f64>,
+    /// Initial water content in kg/m³
+    pub initial_water_content: f64,
+    /// Cells of the wet charge; other cells carry no moisture
+    pub charge: Array2<bool>,
+    /// Conductivity of each cell relative to the wet material
+    pub conductivity_ratio: Array2<f64>,
+    /// Volumetric heat capacity of each cell relative to the wet material
+    pub capacity_ratio: Array2<f64>,
+    /// Cumulative energy spent on evaporation in J
+    pub drying_energy: f64,
+    /// Cumulative mass of water vapour released in kg
+    pub vapour_released: f64,
+    dried_material: Option<super::materials::Material>,
+    formula_engine: FormulaEngine,
+}
+
+impl MoistureModel {
+    /// Create the model with uniform initial moisture
+    pub fn new(
+        config: MoistureConfig,
+        mesh: &super::mesh::CylindricalMesh,
+        physics: &super::physics::PlasmaPhysics,
+    ) -> Result<Self> {
+        config.validate()?;
+
+        let dried_material = match config.dried_material {
+            Some(ref name) => Some(super::materials::MaterialLibrary::get_material(name)?),
+            None => None,
+        };
+        // Moisture is a fraction of the charge mass as loaded, at ambient temperature
+        let initial_water_content =
+            physics.get_density(physics.boundary_conditions.ambient_temperature) * config.initial_moisture;
+        let shape = (mesh.nr, mesh.nz);
+
+        Ok(Self {
+            config,
+            water_content: Array2::from_elem(shape, initial_water_content),
+            initial_water_content,
+            charge: Array2::from_elem(shape, true),
+            conductivity_ratio: Array2::ones(shape),
+            capacity_ratio: Array2::ones(shape),
+            drying_energy: 0.0,
+            vapour_released: 0.0,
+            dried_material,
+            formula_engine: FormulaEngine::new(),
+        })
+    }
+
+    /// Confine moisture to the base-material region
+    pub fn confine_to_charge(&mut self, regions: &super::regions::RegionMap) {
+        for ((i, j), &region) in regions.index.indexed_iter() {
+            if region != 0 {
+                self.charge[[i, j]] = false;
+                self.water_content[[i, j]] = 0.0;
+            }
+        }
+    }
+
+    /// Fraction of the initial moisture remaining in a cell
+    pub fn moisture_ratio(&self, i: usize, j: usize) -> f64 {
+        if self.initial_water_content > 0.0 {
+            self.water_content[[i, j]] / self.initial_water_content
+        } else {
+            0.0
+        }
+    }
+
+    /// Update the property ratios blending wet and dried material by dryness
+    pub fn update_properties(
+        &mut self,
+        physics: &super::physics::PlasmaPhysics,
+        temperature: &Array2<f64>,
+    ) -> Result<()> {
+        let dried = match self.dried_material {
+            Some(ref material) => material,
+            None => return Ok(()),
+        };
+
+        for ((i, j), &t) in temperature.indexed_iter() {
+            if !self.charge[[i, j]] {
+                continue;
+            }
+            let dryness = 1.0 - self.moisture_ratio(i, j);
+
+            let k_wet = physics.get_thermal_conductivity(t);
+            let c_wet = physics.get_density(t) * physics.get_specific_heat(t);
+            let k_dry = dried.get_thermal_conductivity(t, Some(&mut self.formula_engine))?;
+            let c_dry = dried.get_density(t, Some(&mut self.formula_engine))? * dried.get_specific_heat(t, Some(&mut self.formula_engine))?;
+
+            self.conductivity_ratio[[i, j]] = 1.0 + dryness * (k_dry / k_wet - 1.0);
+            self.capacity_ratio[[i, j]] = 1.0 + dryness * (c_dry / c_wet - 1.0);
+        }
+
+        Ok(())
+    }
+
+    /// Evaporate moisture in cells above the evaporation temperature
+    ///
+    /// `capacity_multiplier` is the solver's combined heat capacity multiplier
+    /// (drying and regions), so the sensible heat released matches the heat
+    /// the solver stored. Returns the energy spent on evaporation during this
+    /// step in J.
+    pub fn evaporate(
+        &mut self,
+        mesh: &super::mesh::CylindricalMesh,
+        physics: &super::physics::PlasmaPhysics,
+        temperature: &mut Array2<f64>,
+        capacity_multiplier: Option<&Array2<f64>>,
+    ) -> f64 {
+        let t_evap = self.config.evaporation_temperature;
+        let latent_heat = self.config.latent_heat;
+        let mut step_energy = 0.0;
+
+        for ((i, j), t) in temperature.indexed_iter_mut() {
+            let water = self.water_content[[i, j]];
+            if water <= 0.0 || *t <= t_evap {
+                continue;
+            }
+
+            // Sensible heat above the evaporation temperature (J/m³)
+            let capacity = physics.get_density(*t)
+                * physics.get_specific_heat(*t)
+                * capacity_multiplier.map_or(1.0, |multiplier| multiplier[[i, j]]);
+            let available = capacity * (*t - t_evap);
+            let required = water * latent_heat;
+
+            let used = available.min(required);
+            let evaporated = used / latent_heat;
+            self.water_content[[i, j]] = (water - evaporated).max(0.0);
+            *t = t_evap + (available - used) / capacity;
+
+            let volume = mesh.get_cell_volume(i, j);
+            step_energy += used * volume;
+            self.vapour_released += evaporated * volume;
+        }
+
+        self.drying_energy += step_energy;
+        step_energy
+    }
+
+    /// Record the current drying progress
+    pub fn snapshot(
+        &self,
+        mesh: &super::mesh::CylindricalMesh,
+        physics: &super::physics::PlasmaPhysics,
+        time: f64,
+    ) -> DryingSnapshot {
+        let mut total_volume = 0.0;
+        let mut dried_volume = 0.0;
+        let mut front_distance: f64 = 0.0;
+
+        for i in 0..mesh.nr {
+            for j in 0..mesh.nz {
+                if !self.charge[[i, j]] {
+                    continue;
+                }
+                let volume = mesh.get_cell_volume(i, j);
+                total_volume += volume;
+
+                if self.moisture_ratio(i, j) < 0.5 {
+                    dried_volume += volume;
+
+                    let (r, z) = (mesh.r_coords[i], mesh.z_coords[j]);
+                    let distance = physics
+                        .torches
+                        .iter()
+                        .map(|torch| ((r - torch.position.0).powi(2) + (z - torch.position.1).powi(2)).sqrt())
+                        .fold(f64::INFINITY, f64::min);
+                    front_distance = front_distance.max(distance);
+                }
+            }
+        }
+
+        DryingSnapshot {
+            time,
+            dried_fraction: dried_volume / total_volume,
+            front_distance,
+            drying_energy: self.drying_energy,
+            vapour_released: self.vapour_released,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::materials::MaterialLibrary;
+    use crate::simulation::mesh::CylindricalMesh;
+    use crate::simulation::physics::{BoundaryConditions, PlasmaPhysics, PlasmaTorch};
+
+    fn physics() -> PlasmaPhysics {
+        let torch = PlasmaTorch::new((0.0, 1.0), 100.0, 0.8, 0.1).unwrap();
+        let material = MaterialLibrary::get_material("Medical Waste").unwrap();
+        PlasmaPhysics::new(vec![torch], material, BoundaryConditions::default()).unwrap()
+    }
+
+    #[test]
+    fn test_evaporation_holds_boiling_temperature() {
+        let mesh = CylindricalMesh::new(0.5, 1.0, 10, 10).unwrap();
+        let physics = physics();
+        let mut model = MoistureModel::new(MoistureConfig::default(), &mesh, &physics).unwrap();
+
+        // A small overshoot is fully absorbed by evaporation
+        let mut temperature = mesh.create_temperature_array(300.0);
+        temperature[[2, 2]] = 383.15;
+        let energy = model.evaporate(&mesh, &physics, &mut temperature, None);
+
+        assert!((temperature[[2, 2]] - 373.15).abs() < 1e-9);
+        assert!(energy > 0.0);
+        assert!(model.water_content[[2, 2]] < model.initial_water_content);
+        assert_eq!(model.water_content[[5, 5]], model.initial_water_content);
+
+        let expected_vapour = energy / model.config.latent_heat;
+        assert!((model.vapour_released - expected_vapour).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_large_overshoot_dries_cell_completely() {
+        let mesh = CylindricalMesh::new(0.5, 1.0, 10, 10).unwrap();
+        let physics = physics();
+        let mut model = MoistureModel::new(MoistureConfig::default(), &mesh, &physics).unwrap();
+
+        let mut temperature = mesh.create_temperature_array(300.0);
+        temperature[[2, 2]] = 1.0e5;
+        model.evaporate(&mesh, &physics, &mut temperature, None);
+
+        assert_eq!(model.water_content[[2, 2]], 0.0);
+        assert!(temperature[[2, 2]] > 373.15);
+
+        let snapshot = model.snapshot(&mesh, &physics, 1.0);
+        assert!(snapshot.dried_fraction > 0.0);
+        assert!(snapshot.front_distance > 0.0);
+    }
+
+    #[test]
+    fn test_moisture_confined_to_charge() {
+        use crate::simulation::regions::{MaterialRegion, RegionMap};
+
+        let mesh = CylindricalMesh::new(0.5, 1.0, 10, 10).unwrap();
+        let physics = physics();
+        let lining = MaterialRegion {
+            name: "lining".to_string(),
+            material_name: "Carbon Steel".to_string(),
+            r_range: (0.4, 0.5),
+            z_range: (0.0, 1.0),
+        };
+        let regions = RegionMap::new(&physics.material, &[lining], &[], &mesh).unwrap();
+        let config = MoistureConfig { dried_material: Some("Wood".to_string()), ..Default::default() };
+        let mut model = MoistureModel::new(config, &mesh, &physics).unwrap();
+        model.confine_to_charge(&regions);
+
+        // The lining starts dry, keeps its own properties and does not evaporate
+        let mut temperature = mesh.create_temperature_array(400.0);
+        model.update_properties(&physics, &temperature).unwrap();
+        assert_eq!(model.water_content[[9, 5]], 0.0);
+        assert_eq!(model.conductivity_ratio[[9, 5]], 1.0);
+        assert!(model.water_content[[2, 5]] > 0.0);
+
+        // Evaporation uses the combined capacity multiplier
+        let multiplier = Array2::from_elem((10, 10), 2.0);
+        model.evaporate(&mesh, &physics, &mut temperature, Some(&multiplier));
+        let evaporated = model.initial_water_content - model.water_content[[2, 5]];
+        let capacity = 2.0 * physics.get_density(400.0) * physics.get_specific_heat(400.0);
+        assert!((evaporated * model.config.latent_heat - capacity * (400.0 - 373.15)).abs() < 1e-6 * capacity);
+        assert_eq!(model.snapshot(&mesh, &physics, 1.0).dried_fraction, 0.0);
+    }
+
+    #[test]
+    fn test_dried_properties_switch_in() {
+        let mesh = CylindricalMesh::new(0.5, 1.0, 10, 10).unwrap();
+        let physics = physics();
+        let config = MoistureConfig { dried_material: Some("Wood".to_string()), ..Default::default() };
+        let mut model = MoistureModel::new(config, &mesh, &physics).unwrap();
+        model.water_content[[3, 3]] = 0.0;
+
+        let temperature = mesh.create_temperature_array(400.0);
+        model.update_properties(&physics, &temperature).unwrap();
+
+        assert_eq!(model.conductivity_ratio[[5, 5]], 1.0);
+        let wood = MaterialLibrary::get_material("Wood").unwrap();
+        let expected = wood.get_thermal_conductivity(400.0, None).unwrap() / physics.get_thermal_conductivity(400.0);
+        assert!((model.conductivity_ratio[[3, 3]] - expected).abs() < 1e-12);
+
+        let invalid = MoistureConfig { dried_material: Some("Unobtainium".to_string()), ..Default::default() };
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -0,0 +1,211 @@
+//! P1 radiation model for participating media
+//!
+//! The P1 approximation replaces the radiative transfer equation by an
+//! elliptic equation for the incident radiation G:
+//!
+//! ∇·(Γ ∇G) - κ G + 4 κ σ T⁴ = 0,  Γ = 1 / (3 (κ + σ_s))
+//!
+//! with Marshak conditions at the walls. The divergence of the radiative flux,
+//! -∇·q_r = κ (G - 4 σ T⁴), enters the energy equation as a heat source.
+
+use super::elliptic::{self, EllipticBoundary, EllipticSolverSettings};
+use crate::errors::{Result, SimulationError};
+use ndarray::Array2;
+
+/// Stefan-Boltzmann constant in W/(m²·K⁴)
+const STEFAN_BOLTZMANN: f64 = 5.67e-8;
+
+/// P1 radiation configuration
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct P1RadiationConfig {
+    /// Absorption coefficient override in 1/m (material value if not set)
+    pub absorption_coefficient: Option<f64>,
+    /// Scattering coefficient in 1/m
+    pub scattering_coefficient: f64,
+    /// Wall emissivity for the Marshak conditions (material emissivity if not set)
+    pub wall_emissivity: Option<f64>,
+    /// Solve the incident radiation equation every N time steps
+    pub update_interval: usize,
+    /// SOR settings for the incident radiation solve
+    #[serde(default)]
+    pub solver: EllipticSolverSettings,
+}
+
+impl Default for P1RadiationConfig {
+    fn default() -> Self {
+        Self {
+            absorption_coefficient: None,
+            scattering_coefficient: 0.0,
+            wall_emissivity: None,
+            update_interval: 1,
+            solver: EllipticSolverSettings::default(),
+        }
+    }
+}
+
+impl P1RadiationConfig {
+    /// Validate configuration parameters
+    pub fn validate(&self) -> Result<()> {
+        use crate::errors::validation::{validate_positive, validate_range};
+
+        if let Some(kappa) = self.absorption_coefficient {
+            validate_positive(kappa, "P1 absorption coefficient")?;
+        }
+        validate_range(self.scattering_coefficient, 0.0, f64::MAX, "P1 scattering coefficient")?;
+        if let Some(emissivity) = self.wall_emissivity {
+            validate_range(emissivity, 0.0, 1.0, "P1 wall emissivity")?;
+        }
+        if self.update_interval == 0 {
+            return Err(SimulationError::InvalidParameter {
+                parameter: "P1 update interval".to_string(),
+                value: "0".to_string(),
+                range: "≥ 1 step".to_string(),
+            });
+        }
+        self.solver.validate("P1 solver")
+    }
+}
+
+/// P1 radiation model state
+#[derive(Debug, Clone)]
+pub struct P1Radiation {
+    pub config: P1RadiationConfig,
+    /// Absorption coefficient in 1/m
+    pub absorption_coefficient: f64,
+    /// Wall emissivity used in the Marshak conditions
+    pub wall_emissivity: f64,
+    /// Incident radiation G in W/m²
+    pub incident_radiation: Array2<f64>,
+    /// Radiative heat source -∇·q_r in W/m³
+    pub heat_source: Array2<f64>,
+}
+
+impl P1Radiation {
+    /// Create the model, resolving coefficients from the configuration and material
+    pub fn new(
+        config: P1RadiationConfig,
+        material: &super::materials::Material,
+        mesh: &super::mesh::CylindricalMesh,
+        initial_temperature: f64,
+    ) -> Result<Self> {
+        config.validate()?;
+
+        let absorption_coefficient = config
+            .absorption_coefficient
+            .or(material.absorption_coefficient)
+            .ok_or_else(|| SimulationError::ConfigurationError {
+                component: "P1 radiation".to_string(),
+                issue: format!("No absorption coefficient set for material '{}'", material.name),
+            })?;
+        let wall_emissivity = match config.wall_emissivity {
+            Some(emissivity) => emissivity,
+            None => material.get_emissivity(initial_temperature, Some(&mut crate::formula::engine::FormulaEngine::new()))?,
+        };
+
+        let shape = (mesh.nr, mesh.nz);
+        Ok(Self {
+            config,
+            absorption_coefficient,
+            wall_emissivity,
+            incident_radiation: Array2::from_elem(shape, 4.0 * STEFAN_BOLTZMANN * initial_temperature.powi(4)),
+            heat_source: Array2::zeros(shape),
+        })
+    }
+
+    /// Solve for the incident radiation and update the radiative heat source
+    pub fn update(
+        &mut self,
+        mesh: &super::mesh::CylindricalMesh,
+        temperature: &Array2<f64>,
+    ) -> Result<elliptic::EllipticReport> {
+        let kappa = self.absorption_coefficient;
+        let gamma = 1.0 / (3.0 * (kappa + self.config.scattering_coefficient));
+        let emission = temperature.mapv(|t| 4.0 * STEFAN_BOLTZMANN * t.powi(4));
+
+        let diffusion = Array2::from_elem(temperature.dim(), gamma);
+        let absorption = Array2::from_elem(temperature.dim(), kappa);
+        let source = emission.mapv(|e| kappa * e);
+
+        // Marshak condition: inflow = ε / (2 (2 - ε)) (4σT_w⁴ - G)
+        let epsilon = self.wall_emissivity;
+        let coefficient = epsilon / (2.0 * (2.0 - epsilon));
+
+        let report = elliptic::solve(
+            mesh,
+            &diffusion,
+            &absorption,
+            &source,
+            |_, i, j| EllipticBoundary::Robin { coefficient, reference: emission[[i, j]] },
+            &self.config.solver,
+            &mut self.incident_radiation,
+        )?;
+
+        if !report.converged {
+            log::warn!(
+                "P1 radiation solve did not converge in {} iterations (residual {:.2e})",
+                report.iterations,
+                report.residual
+            );
+        }
+
+        self.heat_source = &self.incident_radiation - &emission;
+        self.heat_source.mapv_inplace(|q| kappa * q);
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::materials::MaterialLibrary;
+    use crate::simulation::mesh::CylindricalMesh;
+
+    #[test]
+    fn test_p1_requires_absorption_coefficient() {
+        let mesh = CylindricalMesh::new(0.5, 1.0, 10, 10).unwrap();
+        let steel = MaterialLibrary::get_material("Carbon Steel").unwrap();
+        assert!(P1Radiation::new(P1RadiationConfig::default(), &steel, &mesh, 300.0).is_err());
+
+        let glass = MaterialLibrary::get_material("Glass").unwrap();
+        let model = P1Radiation::new(P1RadiationConfig::default(), &glass, &mesh, 300.0).unwrap();
+        assert_eq!(model.absorption_coefficient, 300.0);
+    }
+
+    #[test]
+    fn test_p1_equilibrium_has_no_source() {
+        // Black walls at the medium temperature: G = 4σT⁴ and no net exchange
+        let mesh = CylindricalMesh::new(0.5, 1.0, 10, 10).unwrap();
+        let glass = MaterialLibrary::get_material("Glass").unwrap();
+        let config = P1RadiationConfig { wall_emissivity: Some(1.0), absorption_coefficient: Some(5.0), ..Default::default() };
+        let mut model = P1Radiation::new(config, &glass, &mesh, 1000.0).unwrap();
+
+        let temperature = mesh.create_temperature_array(1000.0);
+        model.update(&mesh, &temperature).unwrap();
+
+        let emission = 4.0 * STEFAN_BOLTZMANN * 1000.0_f64.powi(4);
+        assert!(model.heat_source.iter().all(|q| q.abs() < 1e-6 * 5.0 * emission));
+    }
+
+    #[test]
+    fn test_p1_hot_core_loses_energy_to_cold_walls() {
+        let mesh = CylindricalMesh::new(0.5, 1.0, 10, 20).unwrap();
+        let glass = MaterialLibrary::get_material("Glass").unwrap();
+        let config = P1RadiationConfig { absorption_coefficient: Some(2.0), ..Default::default() };
+        let mut model = P1Radiation::new(config, &glass, &mesh, 2000.0).unwrap();
+
+        let mut temperature = mesh.create_temperature_array(2000.0);
+        for i in 0..mesh.nr {
+            temperature[[i, 0]] = 500.0;
+            temperature[[i, mesh.nz - 1]] = 500.0;
+        }
+        for j in 0..mesh.nz {
+            temperature[[mesh.nr - 1, j]] = 500.0;
+        }
+        model.update(&mesh, &temperature).unwrap();
+
+        // The hot interior emits more than it absorbs, the cold surface layer gains
+        assert!(model.heat_source[[0, mesh.nz / 2]] < 0.0);
+        assert!(model.heat_source[[mesh.nr - 1, mesh.nz / 2]] > 0.0);
+    }
+}
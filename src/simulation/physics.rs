@@ -4,7 +4,16 @@
 //! heat sources, radiation, convection, and material interactions.
 
 use crate::errors::{Result, SimulationError};
+use crate::formula::engine::{FormulaContext, FormulaEngine};
+use super::boundary::{BoundaryCondition, BoundarySet, BoundarySpec};
+use std::cell::RefCell;
 use std::f64::consts::PI;
+use std::sync::Mutex;
+
+thread_local! {
+    /// Formula engine for material properties evaluated through `&PlasmaPhysics`
+    static FORMULA_ENGINE: RefCell<FormulaEngine> = RefCell::new(FormulaEngine::new());
+}
 
 /// Plasma torch configuration with 3D positioning and Gaussian heat distribution
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -121,11 +130,11 @@ impl PlasmaTorch {
         heat_flux
     }
     
-    /// Calculate heat flux with view factor for radiative exchange (future enhancement)
-    pub fn calculate_heat_flux_with_view_factor(&self, r: f64, z: f64, _view_factor: f64) -> f64 {
-        // For now, just return the basic heat flux
-        // View factor calculations will be implemented in future tasks
-        self.calculate_heat_flux(r, z)
+    /// Calculate heat flux reaching a surface that sees the torch with the given view factor
+    ///
+    /// The view factor is clamped to [0, 1].
+    pub fn calculate_heat_flux_with_view_factor(&self, r: f64, z: f64, view_factor: f64) -> f64 {
+        self.calculate_heat_flux(r, z) * view_factor.clamp(0.0, 1.0)
     }
     
     /// Get effective radius of heat source (3σ rule - 99.7% of heat within this radius)
@@ -197,6 +206,10 @@ pub struct TorchInfo {
 }
 
 /// Boundary conditions configuration
+/// 
+/// The scalar fields describe the legacy outer wall (fixed temperature or
+/// convection plus radiation). The optional per-boundary specifications
+/// override them; top and bottom default to adiabatic.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct BoundaryConditions {
     pub axis_symmetry: bool,
@@ -204,6 +217,47 @@ pub struct BoundaryConditions {
     pub convection_coefficient: f64,
     pub ambient_temperature: f64,
     pub emissivity: f64,
+    /// Outer wall specification (overrides the legacy fields)
+    #[serde(default)]
+    pub outer_wall: Option<BoundarySpec>,
+    /// Top surface specification (adiabatic if not set)
+    #[serde(default)]
+    pub top: Option<BoundarySpec>,
+    /// Bottom surface specification (adiabatic if not set)
+    #[serde(default)]
+    pub bottom: Option<BoundarySpec>,
+}
+
+impl BoundaryConditions {
+    /// Resolve the effective specification for every non-axis boundary
+    pub fn resolve(&self) -> BoundarySet {
+        let outer_wall = match (&self.outer_wall, self.outer_wall_temperature) {
+            (Some(spec), _) => spec.clone(),
+            (None, Some(temperature)) => {
+                BoundarySpec::uniform(BoundaryCondition::FixedTemperature { temperature })
+            }
+            (None, None) => BoundarySpec::uniform(BoundaryCondition::Combined {
+                heat_transfer_coefficient: self.convection_coefficient,
+                emissivity: self.emissivity,
+                ambient_temperature: self.ambient_temperature,
+            }),
+        };
+        
+        BoundarySet {
+            outer_wall,
+            top: self.top.clone().unwrap_or_else(|| BoundarySpec::uniform(BoundaryCondition::Adiabatic)),
+            bottom: self.bottom.clone().unwrap_or_else(|| BoundarySpec::uniform(BoundaryCondition::Adiabatic)),
+        }
+    }
+    
+    /// Validate all boundary specifications
+    pub fn validate(&self) -> Result<()> {
+        let resolved = self.resolve();
+        resolved.outer_wall.validate("outer wall")?;
+        resolved.top.validate("top")?;
+        resolved.bottom.validate("bottom")?;
+        Ok(())
+    }
 }
 
 impl Default for BoundaryConditions {
@@ -214,6 +268,9 @@ impl Default for BoundaryConditions {
             convection_coefficient: 10.0, // W/(m²·K)
             ambient_temperature: 298.15,  // K (25°C)
             emissivity: 0.8,
+            outer_wall: None,
+            top: None,
+            bottom: None,
         }
     }
 }
@@ -223,6 +280,8 @@ pub struct PlasmaPhysics {
     pub torches: Vec<PlasmaTorch>,
     pub material: super::materials::Material,
     pub boundary_conditions: BoundaryConditions,
+    /// First material property evaluation error, see [`Self::take_property_error`]
+    property_error: Mutex<Option<SimulationError>>,
 }
 
 impl PlasmaPhysics {
@@ -245,6 +304,7 @@ impl PlasmaPhysics {
             torches,
             material,
             boundary_conditions,
+            property_error: Mutex::new(None),
         })
     }
     
@@ -316,6 +376,15 @@ impl PlasmaPhysics {
         Some(max_index)
     }
     
+    /// Standard formula variables at a position, with the liquid fraction of the base material
+    pub fn formula_context(&self, r: f64, z: f64, temperature: f64, time: f64) -> FormulaContext {
+        FormulaContext::new(temperature)
+            .at(r, z)
+            .with_time(time)
+            .with_torch(self.get_dominant_torch_index(r, z))
+            .with_liquid_fraction(self.material.liquid_fraction(temperature, 0.0))
+    }
+    
     /// Calculate radiation loss using Stefan-Boltzmann law
     /// 
     /// Implements: q_rad = ε * σ * (T⁴ - T_amb⁴)
@@ -323,13 +392,15 @@ impl PlasmaPhysics {
     /// 
     /// # Arguments
     /// * `temperature` - Surface temperature in K
-    /// * `emissivity` - Surface emissivity (0.0 to 1.0)
+    /// * `emissivity` - Surface emissivity (0.0 to 1.0), or `None` for the
+    ///   material emissivity at `temperature`
     /// 
     /// # Returns
     /// Radiation heat loss in W/m²
-    pub fn calculate_radiation_loss(&self, temperature: f64, emissivity: f64) -> f64 {
+    pub fn calculate_radiation_loss(&self, temperature: f64, emissivity: Option<f64>) -> f64 {
         const STEFAN_BOLTZMANN: f64 = 5.67e-8; // W/(m²·K⁴)
         
+        let emissivity = emissivity.unwrap_or_else(|| self.get_emissivity(temperature));
         let t_amb = self.boundary_conditions.ambient_temperature;
         let q_rad = emissivity * STEFAN_BOLTZMANN * 
                    (temperature.powi(4) - t_amb.powi(4));
@@ -355,34 +426,77 @@ impl PlasmaPhysics {
     }
     
     /// Calculate total boundary heat loss (convection + radiation)
-    pub fn calculate_total_boundary_loss(&self, temperature: f64, emissivity: f64) -> f64 {
+    pub fn calculate_total_boundary_loss(&self, temperature: f64, emissivity: Option<f64>) -> f64 {
         self.calculate_convection_loss(temperature) + 
         self.calculate_radiation_loss(temperature, emissivity)
     }
     
-    /// Get thermal conductivity at temperature
-    pub fn get_thermal_conductivity(&self, _temperature: f64) -> f64 {
-        // For now, use None for formula engine - will be properly integrated in formula engine task
-        // Use reasonable default values for steel
-        match &self.material.thermal_conductivity {
-            crate::simulation::materials::Property::Constant(k) => *k,
-            _ => 50.0, // Default thermal conductivity for steel (W/m·K)
-        }
+    /// Get thermal conductivity at temperature, blended over the phases present
+    pub fn get_thermal_conductivity(&self, temperature: f64) -> f64 {
+        self.evaluate_property(|material, engine| material.get_thermal_conductivity(temperature, Some(engine)))
     }
     
-    /// Get specific heat at temperature
-    pub fn get_specific_heat(&self, _temperature: f64) -> f64 {
-        // For now, use None for formula engine - will be properly integrated in formula engine task
-        // Use reasonable default values for steel
-        match &self.material.specific_heat {
-            crate::simulation::materials::Property::Constant(cp) => *cp,
-            _ => 500.0, // Default specific heat for steel (J/kg·K)
+    /// Get specific heat at temperature, blended over the phases present
+    pub fn get_specific_heat(&self, temperature: f64) -> f64 {
+        self.evaluate_property(|material, engine| material.get_specific_heat(temperature, Some(engine)))
+    }
+    
+    /// Get material density at temperature
+    pub fn get_density(&self, temperature: f64) -> f64 {
+        self.evaluate_property(|material, engine| material.get_density(temperature, Some(engine)))
+    }
+    
+    /// Get material emissivity at temperature
+    pub fn get_emissivity(&self, temperature: f64) -> f64 {
+        self.evaluate_property(|material, engine| material.get_emissivity(temperature, Some(engine)))
+    }
+    
+    /// Evaluate a material property with the shared formula engine
+    ///
+    /// A failed evaluation is logged once, kept for [`Self::take_property_error`]
+    /// and reads as NaN, so no substitute value enters the run.
+    fn evaluate_property(
+        &self,
+        evaluate: impl FnOnce(&super::materials::Material, &mut FormulaEngine) -> Result<f64>,
+    ) -> f64 {
+        match FORMULA_ENGINE.with(|engine| evaluate(&self.material, &mut engine.borrow_mut())) {
+            Ok(value) => value,
+            Err(error) => {
+                let mut slot = self.property_error.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                if slot.is_none() {
+                    log::error!("Material property evaluation failed for {}: {}", self.material.name, error);
+                    *slot = Some(error);
+                }
+                f64::NAN
+            }
         }
     }
     
-    /// Get material density
-    pub fn get_density(&self) -> f64 {
-        self.material.density
+    /// Take the first material property evaluation error since the last call
+    pub fn take_property_error(&self) -> Option<SimulationError> {
+        self.property_error.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take()
+    }
+    
+    /// Evaluate the material properties at a temperature and report the first failure
+    pub fn check_properties(&self, temperature: f64) -> Result<()> {
+        self.get_thermal_conductivity(temperature);
+        self.get_specific_heat(temperature);
+        self.get_density(temperature);
+        self.get_emissivity(temperature);
+        self.take_property_error().map_or(Ok(()), Err)
+    }
+    
+    /// Emissivity of the default outer wall condition at a surface temperature
+    /// 
+    /// The legacy convection plus radiation wall takes the material emissivity,
+    /// so it follows temperature-dependent emissivities. Returns `None` if the
+    /// outer wall has its own specification or a fixed temperature.
+    pub fn default_wall_emissivity(&self, t_surface: f64) -> Option<f64> {
+        let bc = &self.boundary_conditions;
+        if bc.outer_wall.is_some() || bc.outer_wall_temperature.is_some() {
+            return None;
+        }
+        Some(self.get_emissivity(t_surface))
     }
     
     /// Validate all torches against furnace geometry
@@ -640,15 +754,48 @@ mod tests {
         let physics = PlasmaPhysics::new(vec![torch], material, bc).unwrap();
         
         // Test radiation loss at different temperatures
-        let q_rad_low = physics.calculate_radiation_loss(400.0, 0.8);
-        let q_rad_high = physics.calculate_radiation_loss(800.0, 0.8);
+        let q_rad_low = physics.calculate_radiation_loss(400.0, Some(0.8));
+        let q_rad_high = physics.calculate_radiation_loss(800.0, Some(0.8));
         
         assert!(q_rad_low > 0.0);
         assert!(q_rad_high > q_rad_low); // Higher temperature should have higher radiation loss
         
         // Test at ambient temperature (should be near zero)
-        let q_rad_ambient = physics.calculate_radiation_loss(298.15, 0.8);
+        let q_rad_ambient = physics.calculate_radiation_loss(298.15, Some(0.8));
         assert!(q_rad_ambient.abs() < 1e-6);
+        
+        // Without an explicit emissivity the material value at the surface temperature applies
+        let glowing = MaterialLibrary::get_material("Carbon Steel").unwrap()
+            .with_emissivity(crate::simulation::materials::Property::Table(vec![(300.0, 0.4), (1300.0, 0.9)]))
+            .unwrap();
+        let torch = PlasmaTorch::new((0.0, 0.0), 100.0, 0.8, 0.05).unwrap();
+        let physics = PlasmaPhysics::new(vec![torch], glowing, BoundaryConditions::default()).unwrap();
+        assert_eq!(physics.get_emissivity(800.0), 0.65);
+        assert_eq!(physics.calculate_radiation_loss(800.0, None), physics.calculate_radiation_loss(800.0, Some(0.65)));
+    }
+    
+    #[test]
+    fn test_property_errors_are_reported() {
+        use crate::simulation::materials::Property;
+        
+        let torch = PlasmaTorch::new((0.0, 0.0), 100.0, 0.8, 0.05).unwrap();
+        let mut material = MaterialLibrary::get_material("Carbon Steel").unwrap();
+        material.density = Property::Formula("if T > 900.0 { sqrt(-1.0) } else { 7850.0 }".to_string());
+        let physics = PlasmaPhysics::new(vec![torch.clone()], material, BoundaryConditions::default()).unwrap();
+        
+        assert!(physics.check_properties(800.0).is_ok());
+        assert!(physics.get_density(1000.0).is_nan());
+        assert!(physics.get_density(1100.0).is_nan());
+        assert!(matches!(physics.take_property_error(), Some(SimulationError::FormulaError { .. })));
+        assert!(physics.take_property_error().is_none());
+        assert!(physics.check_properties(1000.0).is_err());
+        
+        // Emissivities must lie in [0, 1]
+        let mut material = MaterialLibrary::get_material("Carbon Steel").unwrap();
+        material.emissivity = Property::Formula("0.5 - T / 1000.0".to_string());
+        let physics = PlasmaPhysics::new(vec![torch], material, BoundaryConditions::default()).unwrap();
+        assert!(physics.check_properties(300.0).is_ok());
+        assert!(matches!(physics.check_properties(800.0), Err(SimulationError::MaterialError { .. })));
     }
     
     #[test]
@@ -679,7 +826,7 @@ mod tests {
         let physics = PlasmaPhysics::new(vec![torch], material, bc).unwrap();
         
         let temperature = 600.0;
-        let emissivity = 0.8;
+        let emissivity = Some(0.8);
         
         let q_total = physics.calculate_total_boundary_loss(temperature, emissivity);
         let q_conv = physics.calculate_convection_loss(temperature);
@@ -755,4 +902,28 @@ mod tests {
         assert_eq!(bc.emissivity, 0.8);
         assert_eq!(bc.convection_coefficient, 10.0);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_boundary_conditions_resolve() {
+        let mut bc = BoundaryConditions::default();
+        
+        // Legacy fields map to a combined outer wall and adiabatic top/bottom
+        let resolved = bc.resolve();
+        assert!(matches!(resolved.outer_wall.default, BoundaryCondition::Combined { .. }));
+        assert!(matches!(resolved.top.default, BoundaryCondition::Adiabatic));
+        assert!(matches!(resolved.bottom.default, BoundaryCondition::Adiabatic));
+        
+        // Fixed outer wall temperature is honoured
+        bc.outer_wall_temperature = Some(400.0);
+        let resolved = bc.resolve();
+        assert!(matches!(
+            resolved.outer_wall.default,
+            BoundaryCondition::FixedTemperature { temperature } if temperature == 400.0
+        ));
+        
+        // Explicit specification takes precedence
+        bc.outer_wall = Some(BoundarySpec::uniform(BoundaryCondition::Adiabatic));
+        assert!(matches!(bc.resolve().outer_wall.default, BoundaryCondition::Adiabatic));
+        assert!(bc.validate().is_ok());
+    }
+}
@@ -0,0 +1,614 @@
+//! Tabulated material properties
+//!
+//! Measured property data is given as (temperature, value) points. Between
+//! points the value is interpolated linearly, with a monotone cubic Hermite
+//! spline (PCHIP, which never overshoots the data) or linearly in log(value)
+//! for properties spanning orders of magnitude. Outside the table the value
+//! is clamped to the end points, extended along the end segments, or
+//! rejected with an error.
+//!
+//! Queries outside the measured range are collected per thread so a
+//! simulation can report where it relied on extrapolated data; see
+//! [`take_range_warnings`]. Material property getters name the material and
+//! property being evaluated with a [`PropertyScope`].
+//!
+//! Formula properties can be sampled into tables ahead of a run with
+//! [`tabulate_formula`], which refines the sampling until linear
+//! interpolation matches the formula within a relative tolerance.
+
+use crate::errors::{Result, SimulationError};
+use crate::formula::engine::FormulaEngine;
+use std::cell::{Cell, RefCell};
+
+/// Interpolation between table points
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum Interpolation {
+    #[default]
+    Linear,
+    /// Monotone piecewise cubic Hermite interpolation (PCHIP)
+    MonotoneCubic,
+    /// Linear interpolation of log(value); requires positive values
+    LogLinear,
+}
+
+/// Treatment of temperatures outside the table range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum Extrapolation {
+    /// Hold the end values
+    #[default]
+    Clamp,
+    /// Extend the first and last segments (limited to non-negative values)
+    Linear,
+    /// Fail the evaluation
+    Error,
+}
+
+/// Property table with interpolation and extrapolation policies
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PropertyTable {
+    /// (temperature in K, value) points in ascending temperature order
+    pub points: Vec<(f64, f64)>,
+    #[serde(default)]
+    pub interpolation: Interpolation,
+    #[serde(default)]
+    pub extrapolation: Extrapolation,
+}
+
+impl PropertyTable {
+    /// Create a linearly interpolated table clamped at its ends
+    pub fn new(points: Vec<(f64, f64)>) -> Self {
+        Self { points, interpolation: Interpolation::default(), extrapolation: Extrapolation::default() }
+    }
+
+    /// Set the interpolation method
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Set the extrapolation policy
+    pub fn with_extrapolation(mut self, extrapolation: Extrapolation) -> Self {
+        self.extrapolation = extrapolation;
+        self
+    }
+
+    /// Evaluate the table at a temperature
+    pub fn evaluate(&self, temperature: f64) -> Result<f64> {
+        evaluate_points(&self.points, self.interpolation, self.extrapolation, temperature)
+    }
+
+    /// Evaluate the table and its temperature derivative
+    pub fn evaluate_with_derivative(&self, temperature: f64) -> Result<(f64, f64)> {
+        evaluate_points_with_derivative(&self.points, self.interpolation, self.extrapolation, temperature)
+    }
+
+    /// Validate the table points for the interpolation method
+    pub fn validate(&self, property_name: &str) -> Result<()> {
+        validate_points(&self.points, property_name)?;
+        if self.interpolation == Interpolation::LogLinear {
+            if let Some(index) = self.points.iter().position(|&(_, value)| value <= 0.0) {
+                return Err(table_error(property_name, format!("Log-linear table needs positive values, got {} at index {}", self.points[index].1, index)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sampling settings for replacing formula properties by lookup tables
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FormulaTabulation {
+    /// Lowest tabulated temperature in K
+    pub min_temperature: f64,
+    /// Highest tabulated temperature in K
+    pub max_temperature: f64,
+    /// Evenly spaced points sampled before refinement
+    pub initial_points: usize,
+    /// Upper limit on the number of points after refinement
+    pub max_points: usize,
+    /// Allowed relative error between table and formula
+    pub tolerance: f64,
+}
+
+impl Default for FormulaTabulation {
+    fn default() -> Self {
+        Self {
+            min_temperature: 250.0,
+            max_temperature: 3000.0,
+            initial_points: 65,
+            max_points: 4097,
+            tolerance: 1.0e-4,
+        }
+    }
+}
+
+impl FormulaTabulation {
+    /// Validate the sampling settings
+    pub fn validate(&self) -> Result<()> {
+        crate::errors::validation::validate_positive(self.min_temperature, "tabulation minimum temperature")?;
+        crate::errors::validation::validate_range(self.max_temperature, self.min_temperature, f64::INFINITY, "tabulation maximum temperature")?;
+        if self.max_temperature == self.min_temperature {
+            return Err(SimulationError::InvalidParameter {
+                parameter: "tabulation temperature range".to_string(),
+                value: format!("[{}, {}]", self.min_temperature, self.max_temperature),
+                range: "non-empty range".to_string(),
+            });
+        }
+        if self.initial_points < 2 || self.max_points < self.initial_points {
+            return Err(SimulationError::InvalidParameter {
+                parameter: "tabulation points".to_string(),
+                value: format!("{} initial, {} maximum", self.initial_points, self.max_points),
+                range: "2 ≤ initial ≤ maximum".to_string(),
+            });
+        }
+        crate::errors::validation::validate_range(self.tolerance, 1.0e-12, 1.0, "tabulation tolerance")
+    }
+}
+
+/// Formula sampled into a table
+#[derive(Debug, Clone, PartialEq)]
+pub struct TabulatedFormula {
+    pub table: PropertyTable,
+    /// Largest relative error found at the segment midpoints
+    pub max_error: f64,
+}
+
+/// Sample a formula into a linearly interpolated table
+///
+/// Segments whose midpoint differs from the formula by more than the
+/// tolerance are split until they all pass or the point limit is reached;
+/// the caller decides what to do with a table whose `max_error` is still
+/// above the tolerance.
+pub fn tabulate_formula(formula: &str, settings: &FormulaTabulation, engine: &mut FormulaEngine) -> Result<TabulatedFormula> {
+    settings.validate()?;
+    let compiled = engine.compile(formula)?;
+    let no_variables = std::collections::HashMap::new();
+    let mut sample = |temperature: f64| -> Result<f64> {
+        let value = engine.evaluate_compiled(&compiled, temperature, &no_variables)?;
+        if value.is_finite() {
+            Ok(value)
+        } else {
+            Err(table_error("formula", format!("Formula '{}' is not finite at {} K", formula, temperature)))
+        }
+    };
+
+    let span = settings.max_temperature - settings.min_temperature;
+    let last = (settings.initial_points - 1) as f64;
+    let mut points = (0..settings.initial_points)
+        .map(|i| {
+            let temperature = settings.min_temperature + span * i as f64 / last;
+            sample(temperature).map(|value| (temperature, value))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let relative_error = |approximation: f64, exact: f64| (approximation - exact).abs() / exact.abs().max(f64::MIN_POSITIVE);
+    let mut max_error;
+    loop {
+        max_error = 0.0_f64;
+        let mut refined = Vec::with_capacity(points.len() * 2);
+        let mut splits = 0;
+        for segment in points.windows(2) {
+            let (a, b) = (segment[0], segment[1]);
+            let midpoint = 0.5 * (a.0 + b.0);
+            let exact = sample(midpoint)?;
+            let error = relative_error(0.5 * (a.1 + b.1), exact);
+            max_error = max_error.max(error);
+            refined.push(a);
+            if error > settings.tolerance {
+                refined.push((midpoint, exact));
+                splits += 1;
+            }
+        }
+        refined.push(points[points.len() - 1]);
+
+        if splits == 0 || refined.len() > settings.max_points {
+            break;
+        }
+        points = refined;
+    }
+
+    Ok(TabulatedFormula { table: PropertyTable::new(points), max_error })
+}
+
+/// Query outside the measured range of a table
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RangeWarning {
+    /// Material the table belongs to
+    pub material: String,
+    /// Property the table describes
+    pub property: String,
+    /// Temperature range covered by the table in K
+    pub table_range: (f64, f64),
+    /// Lowest temperature queried in K
+    pub min_queried: f64,
+    /// Highest temperature queried in K
+    pub max_queried: f64,
+    /// Number of out-of-range queries
+    pub count: usize,
+}
+
+thread_local! {
+    static RANGE_WARNINGS: RefCell<Vec<RangeWarning>> = const { RefCell::new(Vec::new()) };
+    /// Property being evaluated and the number of warnings when its scope opened
+    static PROPERTY_SCOPE: Cell<(&'static str, usize)> = const { Cell::new(("", 0)) };
+}
+
+/// Attributes out-of-range queries to a material property while alive
+///
+/// Queries are recorded under the property name and take the material name
+/// when the scope closes, so entering a scope copies no strings. Dropping the
+/// scope restores the enclosing one, also while unwinding.
+pub struct PropertyScope<'a> {
+    material: &'a str,
+    previous: (&'static str, usize),
+}
+
+impl<'a> PropertyScope<'a> {
+    /// Open a scope for `property` of `material`
+    pub fn enter(material: &'a str, property: &'static str) -> Self {
+        let start = RANGE_WARNINGS.with(|warnings| warnings.borrow().len());
+        let previous = PROPERTY_SCOPE.with(|scope| scope.replace((property, start)));
+        Self { material, previous }
+    }
+}
+
+impl Drop for PropertyScope<'_> {
+    fn drop(&mut self) {
+        let (_, start) = PROPERTY_SCOPE.with(|scope| scope.replace(self.previous));
+        RANGE_WARNINGS.with(|warnings| {
+            let mut warnings = warnings.borrow_mut();
+            if warnings.len() <= start {
+                return;
+            }
+            for mut warning in warnings.split_off(start) {
+                if warning.material.is_empty() {
+                    warning.material = self.material.to_string();
+                }
+                add_warning(&mut warnings, warning);
+            }
+        });
+    }
+}
+
+/// Take the out-of-range queries recorded on this thread since the last call
+pub fn take_range_warnings() -> Vec<RangeWarning> {
+    RANGE_WARNINGS.with(|warnings| std::mem::take(&mut *warnings.borrow_mut()))
+}
+
+fn record_out_of_range(table_range: (f64, f64), temperature: f64) {
+    let (property, start) = PROPERTY_SCOPE.with(Cell::get);
+    let query = |material: &str, property: &str| RangeWarning {
+        material: material.to_string(),
+        property: property.to_string(),
+        table_range,
+        min_queried: temperature,
+        max_queried: temperature,
+        count: 1,
+    };
+    RANGE_WARNINGS.with(|warnings| {
+        let mut warnings = warnings.borrow_mut();
+        if property.is_empty() {
+            add_warning(&mut warnings, query("unknown", "property"));
+            return;
+        }
+        // Unnamed until the scope closes
+        let start = start.min(warnings.len());
+        let pending = warnings[start..].iter_mut().find(|warning| {
+            warning.material.is_empty() && warning.property == property && warning.table_range == table_range
+        });
+        match pending {
+            Some(warning) => warning.include(temperature, 1),
+            None => warnings.push(query("", property)),
+        }
+    });
+}
+
+/// Merge a warning into those of the same material, property and table
+fn add_warning(warnings: &mut Vec<RangeWarning>, warning: RangeWarning) {
+    let existing = warnings.iter_mut().find(|existing| {
+        existing.material == warning.material
+            && existing.property == warning.property
+            && existing.table_range == warning.table_range
+    });
+    match existing {
+        Some(existing) => {
+            existing.include(warning.min_queried, warning.count);
+            existing.include(warning.max_queried, 0);
+        }
+        None => {
+            log::warn!(
+                "{} table of {} covering [{}, {}] K queried between {} and {} K, outside the measured range",
+                warning.property, warning.material, warning.table_range.0, warning.table_range.1,
+                warning.min_queried, warning.max_queried
+            );
+            warnings.push(warning);
+        }
+    }
+}
+
+impl RangeWarning {
+    fn include(&mut self, temperature: f64, count: usize) {
+        self.min_queried = self.min_queried.min(temperature);
+        self.max_queried = self.max_queried.max(temperature);
+        self.count += count;
+    }
+}
+
+fn table_error(property_name: &str, details: String) -> SimulationError {
+    SimulationError::MaterialError {
+        material: "unknown".to_string(),
+        property: property_name.to_string(),
+        details,
+    }
+}
+
+/// Validate table points: non-empty, finite, non-negative, strictly ascending temperatures
+pub fn validate_points(points: &[(f64, f64)], property_name: &str) -> Result<()> {
+    if points.is_empty() {
+        return Err(table_error(property_name, "Empty property table".to_string()));
+    }
+
+    for i in 1..points.len() {
+        if points[i].0 <= points[i - 1].0 {
+            return Err(table_error(property_name, format!("Temperature values must be in ascending order at index {}", i)));
+        }
+    }
+
+    for (i, (temp, value)) in points.iter().enumerate() {
+        if !temp.is_finite() || !value.is_finite() {
+            return Err(table_error(property_name, format!("Non-finite values in table at index {}", i)));
+        }
+        if *value < 0.0 {
+            return Err(table_error(property_name, format!("Negative property value in table at index {}: {}", i, value)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Evaluate table points at a temperature
+pub fn evaluate_points(
+    points: &[(f64, f64)],
+    interpolation: Interpolation,
+    extrapolation: Extrapolation,
+    temperature: f64,
+) -> Result<f64> {
+    evaluate_points_with_derivative(points, interpolation, extrapolation, temperature).map(|(value, _)| value)
+}
+
+/// Evaluate table points and the derivative of the interpolant at a temperature
+///
+/// The derivative is zero where the value is clamped, and one-sided at the
+/// table points for linear interpolation.
+pub fn evaluate_points_with_derivative(
+    points: &[(f64, f64)],
+    interpolation: Interpolation,
+    extrapolation: Extrapolation,
+    temperature: f64,
+) -> Result<(f64, f64)> {
+    if points.is_empty() {
+        return Err(table_error("property", "Empty property table".to_string()));
+    }
+
+    let (first, last) = (points[0], points[points.len() - 1]);
+    if temperature < first.0 || temperature > last.0 {
+        if extrapolation == Extrapolation::Error {
+            return Err(table_error(
+                "property",
+                format!("Temperature {} K outside table range [{}, {}] K", temperature, first.0, last.0),
+            ));
+        }
+        record_out_of_range((first.0, last.0), temperature);
+
+        if points.len() == 1 || extrapolation == Extrapolation::Clamp {
+            return Ok((if temperature < first.0 { first.1 } else { last.1 }, 0.0));
+        }
+
+        // Extend the end segment
+        let segment = if temperature < first.0 { 0 } else { points.len() - 2 };
+        let (value, derivative) = match interpolation {
+            Interpolation::LogLinear => log_linear(points[segment], points[segment + 1], temperature),
+            _ => linear(points[segment], points[segment + 1], temperature),
+        };
+        return Ok(if value > 0.0 { (value, derivative) } else { (0.0, 0.0) });
+    }
+
+    if points.len() == 1 {
+        return Ok((first.1, 0.0));
+    }
+
+    // Binary search for the segment containing the temperature
+    let segment = (points.partition_point(|&(t, _)| t <= temperature).max(1) - 1).min(points.len() - 2);
+    let (a, b) = (points[segment], points[segment + 1]);
+
+    Ok(match interpolation {
+        Interpolation::Linear => linear(a, b, temperature),
+        Interpolation::LogLinear => log_linear(a, b, temperature),
+        Interpolation::MonotoneCubic => {
+            let (d_a, d_b) = (pchip_slope(points, segment), pchip_slope(points, segment + 1));
+            let h = b.0 - a.0;
+            let s = (temperature - a.0) / h;
+            let (s2, s3) = (s * s, s * s * s);
+            // Hermite basis written relative to a.1 so flat segments stay exact
+            let value = a.1 + (3.0 * s2 - 2.0 * s3) * (b.1 - a.1) + (s3 - 2.0 * s2 + s) * h * d_a + (s3 - s2) * h * d_b;
+            let derivative = (6.0 * (s - s2) * (b.1 - a.1)) / h + (3.0 * s2 - 4.0 * s + 1.0) * d_a + (3.0 * s2 - 2.0 * s) * d_b;
+            (value, derivative)
+        }
+    })
+}
+
+/// Value and slope of the line through two points
+fn linear(a: (f64, f64), b: (f64, f64), temperature: f64) -> (f64, f64) {
+    let slope = (b.1 - a.1) / (b.0 - a.0);
+    (a.1 + slope * (temperature - a.0), slope)
+}
+
+/// Value and slope of the exponential through two points
+fn log_linear(a: (f64, f64), b: (f64, f64), temperature: f64) -> (f64, f64) {
+    let (log_value, log_slope) = linear((a.0, a.1.ln()), (b.0, b.1.ln()), temperature);
+    let value = log_value.exp();
+    (value, value * log_slope)
+}
+
+/// Derivative at point `k` of the Fritsch-Carlson monotone cubic
+fn pchip_slope(points: &[(f64, f64)], k: usize) -> f64 {
+    let n = points.len();
+    let h = |i: usize| points[i + 1].0 - points[i].0;
+    let delta = |i: usize| (points[i + 1].1 - points[i].1) / h(i);
+
+    if n == 2 {
+        return delta(0);
+    }
+
+    // One-sided three-point estimate at the ends, limited to preserve shape
+    let end_slope = |h0: f64, h1: f64, d0: f64, d1: f64| {
+        let slope = ((2.0 * h0 + h1) * d0 - h0 * d1) / (h0 + h1);
+        if slope.signum() != d0.signum() {
+            0.0
+        } else if d0.signum() != d1.signum() && slope.abs() > 3.0 * d0.abs() {
+            3.0 * d0
+        } else {
+            slope
+        }
+    };
+
+    if k == 0 {
+        return end_slope(h(0), h(1), delta(0), delta(1));
+    }
+    if k == n - 1 {
+        return end_slope(h(n - 2), h(n - 3), delta(n - 2), delta(n - 3));
+    }
+
+    let (d0, d1) = (delta(k - 1), delta(k));
+    if d0 * d1 <= 0.0 {
+        return 0.0;
+    }
+    let (w0, w1) = (2.0 * h(k) + h(k - 1), h(k) + 2.0 * h(k - 1));
+    (w0 + w1) / (w0 / d0 + w1 / d1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steps() -> Vec<(f64, f64)> {
+        vec![(300.0, 10.0), (500.0, 10.0), (700.0, 50.0), (900.0, 52.0), (1500.0, 60.0)]
+    }
+
+    #[test]
+    fn test_interpolation_methods() {
+        let linear = PropertyTable::new(steps());
+        assert_eq!(linear.evaluate(600.0).unwrap(), 30.0);
+        assert_eq!(linear.evaluate(900.0).unwrap(), 52.0);
+
+        // PCHIP passes through the points and stays monotone between them
+        let cubic = PropertyTable::new(steps()).with_interpolation(Interpolation::MonotoneCubic);
+        assert!((cubic.evaluate(700.0).unwrap() - 50.0).abs() < 1e-12);
+        let samples: Vec<f64> = (300..=1500).step_by(10).map(|t| cubic.evaluate(t as f64).unwrap()).collect();
+        assert!(samples.windows(2).all(|w| w[1] >= w[0] - 1e-12));
+        assert!(samples.iter().all(|&v| (10.0..=60.0).contains(&v)));
+
+        // Log-linear gives the geometric mean halfway between points
+        let log = PropertyTable::new(vec![(300.0, 1.0), (500.0, 100.0)]).with_interpolation(Interpolation::LogLinear);
+        assert!((log.evaluate(400.0).unwrap() - 10.0).abs() < 1e-9);
+        assert!(log.validate("conductivity").is_ok());
+        let zero = PropertyTable::new(vec![(300.0, 0.0), (500.0, 100.0)]).with_interpolation(Interpolation::LogLinear);
+        assert!(zero.validate("conductivity").is_err());
+    }
+
+    #[test]
+    fn test_extrapolation_policies() {
+        take_range_warnings();
+        let table = PropertyTable::new(vec![(300.0, 20.0), (1500.0, 32.0)]);
+
+        assert_eq!(table.evaluate(2500.0).unwrap(), 32.0);
+        let extended = table.clone().with_extrapolation(Extrapolation::Linear);
+        assert!((extended.evaluate(2500.0).unwrap() - 42.0).abs() < 1e-12);
+        assert_eq!(extended.evaluate(-10000.0).unwrap(), 0.0);
+        assert!(table.clone().with_extrapolation(Extrapolation::Error).evaluate(2500.0).is_err());
+
+        // Out-of-range queries are reported once per table with their extent
+        let warnings = take_range_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!((warnings[0].material.as_str(), warnings[0].property.as_str()), ("unknown", "property"));
+        assert_eq!(warnings[0].table_range, (300.0, 1500.0));
+        assert_eq!(warnings[0].count, 3);
+        assert_eq!((warnings[0].min_queried, warnings[0].max_queried), (-10000.0, 2500.0));
+        assert!(take_range_warnings().is_empty());
+
+        // Tables sharing a range are told apart by material and property
+        let query = |material: &str, property: &'static str, temperature: f64| {
+            let _scope = PropertyScope::enter(material, property);
+            table.evaluate(temperature).unwrap();
+        };
+        query("Alumina", "thermal conductivity", 2000.0);
+        query("Alumina", "specific heat", 2000.0);
+        query("Zirconia", "thermal conductivity", 2000.0);
+        query("Zirconia", "thermal conductivity", 2200.0);
+        let warnings = take_range_warnings();
+        assert_eq!(warnings.len(), 3);
+        assert_eq!((warnings[2].material.as_str(), warnings[2].property.as_str()), ("Zirconia", "thermal conductivity"));
+        assert_eq!((warnings[2].count, warnings[2].max_queried), (2, 2200.0));
+        
+        // Nested scopes hand the context back, also when unwinding
+        {
+            let _outer = PropertyScope::enter("Alumina", "density");
+            table.evaluate(2000.0).unwrap();
+            let unwound = std::panic::catch_unwind(|| {
+                let _inner = PropertyScope::enter("Zirconia", "emissivity");
+                table.evaluate(2100.0).unwrap();
+                panic!("evaluation failed");
+            });
+            assert!(unwound.is_err());
+            table.evaluate(2300.0).unwrap();
+        }
+        let warnings = take_range_warnings();
+        assert_eq!(warnings.len(), 2);
+        assert_eq!((warnings[0].material.as_str(), warnings[0].property.as_str()), ("Alumina", "density"));
+        assert_eq!((warnings[0].count, warnings[0].min_queried, warnings[0].max_queried), (2, 2000.0, 2300.0));
+        assert_eq!((warnings[1].material.as_str(), warnings[1].property.as_str()), ("Zirconia", "emissivity"));
+    }
+
+    #[test]
+    fn test_formula_tabulation() {
+        let mut engine = FormulaEngine::new();
+        let settings = FormulaTabulation { initial_points: 5, ..FormulaTabulation::default() };
+
+        // Linear formulas need no refinement
+        let linear = tabulate_formula("20.0 + 0.01 * T", &settings, &mut engine).unwrap();
+        assert_eq!(linear.table.points.len(), 5);
+        assert!(linear.max_error < 1e-12);
+
+        // Curved formulas are refined until the table meets the tolerance
+        let formula = "1.0e6 / (T + 100.0)";
+        let curved = tabulate_formula(formula, &settings, &mut engine).unwrap();
+        assert!(curved.max_error <= settings.tolerance);
+        assert!(curved.table.points.len() > 5);
+        for t in [260.0, 333.3, 1234.5, 2999.0] {
+            let exact = engine.evaluate_formula(formula, t).unwrap();
+            assert!((curved.table.evaluate(t).unwrap() - exact).abs() / exact < settings.tolerance);
+        }
+        // Refinement concentrates points where the curvature is largest
+        let low = curved.table.points.iter().filter(|&&(t, _)| t < 1000.0).count();
+        assert!(low > curved.table.points.len() / 2);
+
+        // The point limit caps refinement and the remaining error is reported
+        let capped = FormulaTabulation { max_points: 8, ..settings.clone() };
+        let result = tabulate_formula(formula, &capped, &mut engine).unwrap();
+        assert!(result.table.points.len() <= 8);
+        assert!(result.max_error > settings.tolerance);
+
+        assert!(tabulate_formula("sqrt(T - 1000.0)", &settings, &mut engine).is_err());
+        assert!(FormulaTabulation { initial_points: 1, ..settings }.validate().is_err());
+    }
+
+    #[test]
+    fn test_binary_search_matches_linear_scan() {
+        let points: Vec<(f64, f64)> = (0..1000).map(|i| (300.0 + i as f64, (i as f64).sqrt())).collect();
+        for t in [300.0, 300.5, 777.25, 1298.9, 1299.0] {
+            let i = points.iter().rposition(|&(x, _)| x <= t).unwrap().min(points.len() - 2);
+            let (expected, _) = linear(points[i], points[i + 1], t);
+            assert!((evaluate_points(&points, Interpolation::Linear, Extrapolation::Clamp, t).unwrap() - expected).abs() < 1e-12);
+        }
+        assert!(validate_points(&[(300.0, 1.0), (300.0, 2.0)], "k").is_err());
+    }
+}
@@ -0,0 +1,452 @@
+//! Surface-to-surface radiation exchange inside the furnace cavity
+//!
+//! This module models the furnace interior as a gray, diffuse enclosure
+//! bounded by the bottom (melt/hearth), the outer wall and the top (roof).
+//! Each boundary is split into patches (rings on the end surfaces, bands on
+//! the wall), view factors between patches are computed analytically for the
+//! axisymmetric geometry, and the radiosity system is solved to obtain the
+//! net radiative flux on every surface node.
+
+use crate::errors::{Result, SimulationError};
+use ndarray::Array2;
+use std::f64::consts::PI;
+
+/// Stefan-Boltzmann constant in W/(m²·K⁴)
+const STEFAN_BOLTZMANN: f64 = 5.67e-8;
+
+/// Enclosure radiation configuration
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct EnclosureRadiationConfig {
+    /// Solve the radiosity system every N time steps
+    pub update_interval: usize,
+    /// Maximum number of patches per boundary (nodes are grouped to fit)
+    pub max_patches_per_boundary: usize,
+    /// Surface emissivity override (material emissivity if not set)
+    pub surface_emissivity: Option<f64>,
+}
+
+impl Default for EnclosureRadiationConfig {
+    fn default() -> Self {
+        Self {
+            update_interval: 10,
+            max_patches_per_boundary: 40,
+            surface_emissivity: None,
+        }
+    }
+}
+
+impl EnclosureRadiationConfig {
+    /// Validate configuration parameters
+    pub fn validate(&self) -> Result<()> {
+        if self.update_interval == 0 {
+            return Err(SimulationError::InvalidParameter {
+                parameter: "radiation update interval".to_string(),
+                value: "0".to_string(),
+                range: "≥ 1 step".to_string(),
+            });
+        }
+        if self.max_patches_per_boundary == 0 {
+            return Err(SimulationError::InvalidParameter {
+                parameter: "radiation patches per boundary".to_string(),
+                value: "0".to_string(),
+                range: "≥ 1".to_string(),
+            });
+        }
+        if let Some(emissivity) = self.surface_emissivity {
+            crate::errors::validation::validate_range(emissivity, 0.0, 1.0, "radiation surface emissivity")?;
+        }
+        Ok(())
+    }
+}
+
+/// Boundary surface a patch lies on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum PatchSurface {
+    /// Ring on the bottom surface (z = 0)
+    Bottom,
+    /// Band on the outer wall (r = R)
+    Wall,
+    /// Ring on the top surface (z = H)
+    Top,
+}
+
+/// Surface patch of the enclosure
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SurfacePatch {
+    pub surface: PatchSurface,
+    /// Patch start along the surface in meters (r for rings, z for bands)
+    pub start: f64,
+    /// Patch end along the surface in meters
+    pub end: f64,
+    /// Patch area in m²
+    pub area: f64,
+    /// Mesh nodes (i, j) covered by this patch
+    pub nodes: Vec<(usize, usize)>,
+}
+
+/// Gray diffuse enclosure radiation model for the cylindrical cavity
+#[derive(Debug, Clone)]
+pub struct EnclosureRadiation {
+    pub patches: Vec<SurfacePatch>,
+    /// View factor matrix F[i][j] from patch i to patch j
+    pub view_factors: Array2<f64>,
+    pub config: EnclosureRadiationConfig,
+}
+
+impl EnclosureRadiation {
+    /// Build patches and view factors for a mesh
+    pub fn new(mesh: &super::mesh::CylindricalMesh, config: EnclosureRadiationConfig) -> Result<Self> {
+        config.validate()?;
+
+        let radius = mesh.radius;
+        let height = mesh.height;
+        let mut patches = Vec::new();
+
+        for surface in [PatchSurface::Bottom, PatchSurface::Wall, PatchSurface::Top] {
+            let (coords, spacing, extent) = match surface {
+                PatchSurface::Wall => (&mesh.z_coords, mesh.dz, height),
+                _ => (&mesh.r_coords, mesh.dr, radius),
+            };
+
+            let n = coords.len();
+            let per_patch = n.div_ceil(config.max_patches_per_boundary);
+
+            for first in (0..n).step_by(per_patch) {
+                let last = (first + per_patch).min(n) - 1;
+                let start = (coords[first] - spacing / 2.0).max(0.0);
+                let end = (coords[last] + spacing / 2.0).min(extent);
+
+                let nodes = (first..=last)
+                    .map(|k| match surface {
+                        PatchSurface::Bottom => (k, 0),
+                        PatchSurface::Top => (k, mesh.nz - 1),
+                        PatchSurface::Wall => (mesh.nr - 1, k),
+                    })
+                    .collect();
+
+                let area = match surface {
+                    PatchSurface::Wall => 2.0 * PI * radius * (end - start),
+                    _ => PI * (end * end - start * start),
+                };
+
+                patches.push(SurfacePatch { surface, start, end, area, nodes });
+            }
+        }
+
+        let view_factors = compute_view_factors(&patches, radius, height);
+
+        Ok(Self {
+            patches,
+            view_factors,
+            config,
+        })
+    }
+
+    /// Solve the radiosity system and return the net flux leaving each patch in W/m²
+    ///
+    /// Solves Σ_j [δ_ij - (1 - ε_i) F_ij] J_j = ε_i σ T_i⁴ for the radiosities J
+    /// and returns q_i = J_i - Σ_j F_ij J_j.
+    pub fn solve_net_fluxes(&self, patch_temperatures: &[f64], emissivity: f64) -> Result<Vec<f64>> {
+        let n = self.patches.len();
+        let mut matrix = Array2::zeros((n, n));
+        let mut rhs = vec![0.0; n];
+
+        for i in 0..n {
+            for j in 0..n {
+                let delta = if i == j { 1.0 } else { 0.0 };
+                matrix[[i, j]] = delta - (1.0 - emissivity) * self.view_factors[[i, j]];
+            }
+            rhs[i] = emissivity * STEFAN_BOLTZMANN * patch_temperatures[i].powi(4);
+        }
+
+        let radiosity = solve_dense(matrix, rhs)?;
+
+        Ok((0..n)
+            .map(|i| {
+                let irradiation: f64 = (0..n).map(|j| self.view_factors[[i, j]] * radiosity[j]).sum();
+                radiosity[i] - irradiation
+            })
+            .collect())
+    }
+
+    /// Compute the net radiative flux into the domain at every surface node in W/m²
+    ///
+    /// Patch temperatures are area-weighted means of their nodes. Nodes shared
+    /// by two patches (corners) receive the area-weighted mean flux.
+    pub fn calculate_surface_flux(
+        &self,
+        mesh: &super::mesh::CylindricalMesh,
+        temperature: &Array2<f64>,
+        emissivity: f64,
+    ) -> Result<Array2<f64>> {
+        let patch_temperatures: Vec<f64> = self
+            .patches
+            .iter()
+            .map(|patch| {
+                let (sum, weight) = patch.nodes.iter().fold((0.0, 0.0), |(sum, weight), &(i, j)| {
+                    let area = node_area(mesh, patch.surface, i, j);
+                    (sum + temperature[[i, j]] * area, weight + area)
+                });
+                if weight > 0.0 { sum / weight } else { temperature[patch.nodes[0]] }
+            })
+            .collect();
+
+        let net_fluxes = self.solve_net_fluxes(&patch_temperatures, emissivity)?;
+
+        let mut flux = Array2::zeros((mesh.nr, mesh.nz));
+        let mut weights = Array2::<f64>::zeros((mesh.nr, mesh.nz));
+        for (patch, q_net) in self.patches.iter().zip(net_fluxes) {
+            for &(i, j) in &patch.nodes {
+                let area = node_area(mesh, patch.surface, i, j);
+                // Net flux leaving the surface is a loss for the domain
+                flux[[i, j]] -= q_net * area;
+                weights[[i, j]] += area;
+            }
+        }
+
+        for (value, weight) in flux.iter_mut().zip(weights.iter()) {
+            if *weight > 0.0 {
+                *value /= weight;
+            }
+        }
+
+        Ok(flux)
+    }
+}
+
+/// Surface area of a node on a given boundary surface
+fn node_area(mesh: &super::mesh::CylindricalMesh, surface: PatchSurface, i: usize, j: usize) -> f64 {
+    match surface {
+        PatchSurface::Wall => mesh.get_cell_area_radial(i, j),
+        _ => mesh.get_cell_area_axial(i, j),
+    }
+}
+
+/// View factor between coaxial parallel disks
+///
+/// F from a disk of radius `a` to a disk of radius `b` at separation `h`:
+/// F = ½ [X - √(X² - 4 (b/a)²)], X = 1 + (1 + (b/h)²) / (a/h)²
+pub fn disk_to_disk_view_factor(a: f64, b: f64, h: f64) -> f64 {
+    if a <= 0.0 || b <= 0.0 {
+        return 0.0;
+    }
+    if h <= 1e-12 * a.max(b) {
+        // Coplanar limit: the smaller disk sees the larger one entirely
+        return if a <= b { 1.0 } else { (b / a).powi(2) };
+    }
+
+    let r1 = a / h;
+    let r2 = b / h;
+    let x = 1.0 + (1.0 + r2 * r2) / (r1 * r1);
+    0.5 * (x - (x * x - 4.0 * (r2 / r1).powi(2)).max(0.0).sqrt())
+}
+
+/// Exchange area A * F between coaxial parallel disks
+fn disk_exchange_area(a: f64, b: f64, h: f64) -> f64 {
+    PI * a * a * disk_to_disk_view_factor(a, b, h)
+}
+
+/// Exchange area between a ring [a1, a2] and the opposite end ring [b1, b2]
+fn ring_to_ring_exchange(a1: f64, a2: f64, b1: f64, b2: f64, h: f64) -> f64 {
+    disk_exchange_area(a2, b2, h) - disk_exchange_area(a2, b1, h) - disk_exchange_area(a1, b2, h)
+        + disk_exchange_area(a1, b1, h)
+}
+
+/// Exchange area between an end disk of radius `a` and the wall between the disk plane and distance `h`
+fn disk_to_wall_exchange(a: f64, radius: f64, h: f64) -> f64 {
+    if h <= 0.0 {
+        return 0.0;
+    }
+    PI * a * a - disk_exchange_area(a, radius, h)
+}
+
+/// Exchange area between an end ring [a1, a2] and a wall band at distances [d1, d2] from its plane
+fn ring_to_band_exchange(a1: f64, a2: f64, d1: f64, d2: f64, radius: f64) -> f64 {
+    let ring = |h: f64| disk_to_wall_exchange(a2, radius, h) - disk_to_wall_exchange(a1, radius, h);
+    ring(d2) - ring(d1)
+}
+
+/// Self exchange area of a wall segment of length `h`: A_w * F_ww
+fn wall_self_exchange(radius: f64, h: f64) -> f64 {
+    if h <= 0.0 {
+        return 0.0;
+    }
+    let wall_area = 2.0 * PI * radius * h;
+    let disk_area = PI * radius * radius;
+    wall_area - 2.0 * (disk_area - disk_exchange_area(radius, radius, h))
+}
+
+/// Exchange area between wall bands [z1, z2] and [w1, w2]
+fn band_to_band_exchange(z1: f64, z2: f64, w1: f64, w2: f64, radius: f64) -> f64 {
+    if (z1 - w1).abs() < 1e-12 && (z2 - w2).abs() < 1e-12 {
+        return wall_self_exchange(radius, z2 - z1);
+    }
+    // Order the bands so that the first lies below the second
+    let (lower, upper) = if z1 <= w1 { ((z1, z2), (w1, w2)) } else { ((w1, w2), (z1, z2)) };
+    let s = |h: f64| wall_self_exchange(radius, h);
+    0.5 * (s(upper.1 - lower.0) - s(upper.0 - lower.0) - s(upper.1 - lower.1) + s(upper.0 - lower.1))
+}
+
+/// Compute the view factor matrix for a set of patches
+fn compute_view_factors(patches: &[SurfacePatch], radius: f64, height: f64) -> Array2<f64> {
+    let n = patches.len();
+    let mut exchange = Array2::zeros((n, n));
+
+    for i in 0..n {
+        for j in i..n {
+            let (p, q) = (&patches[i], &patches[j]);
+            let g = match (p.surface, q.surface) {
+                (PatchSurface::Bottom, PatchSurface::Bottom) | (PatchSurface::Top, PatchSurface::Top) => 0.0,
+                (PatchSurface::Bottom, PatchSurface::Top) | (PatchSurface::Top, PatchSurface::Bottom) => {
+                    ring_to_ring_exchange(p.start, p.end, q.start, q.end, height)
+                }
+                (PatchSurface::Bottom, PatchSurface::Wall) => {
+                    ring_to_band_exchange(p.start, p.end, q.start, q.end, radius)
+                }
+                (PatchSurface::Wall, PatchSurface::Bottom) => {
+                    ring_to_band_exchange(q.start, q.end, p.start, p.end, radius)
+                }
+                (PatchSurface::Top, PatchSurface::Wall) => {
+                    ring_to_band_exchange(p.start, p.end, height - q.end, height - q.start, radius)
+                }
+                (PatchSurface::Wall, PatchSurface::Top) => {
+                    ring_to_band_exchange(q.start, q.end, height - p.end, height - p.start, radius)
+                }
+                (PatchSurface::Wall, PatchSurface::Wall) => {
+                    band_to_band_exchange(p.start, p.end, q.start, q.end, radius)
+                }
+            };
+            let g = g.max(0.0);
+            exchange[[i, j]] = g;
+            exchange[[j, i]] = g;
+        }
+    }
+
+    // F_ij = (A_i F_ij) / A_i. The analytic exchange areas already close to
+    // A_i; dividing by the row sum removes round-off so each row sums to one.
+    let mut view_factors = Array2::zeros((n, n));
+    for i in 0..n {
+        let row_sum: f64 = exchange.row(i).sum();
+        let scale = if row_sum > 0.0 { row_sum } else { patches[i].area };
+        for j in 0..n {
+            view_factors[[i, j]] = exchange[[i, j]] / scale;
+        }
+    }
+
+    view_factors
+}
+
+/// Solve a dense linear system using Gaussian elimination with partial pivoting
+fn solve_dense(mut matrix: Array2<f64>, mut rhs: Vec<f64>) -> Result<Vec<f64>> {
+    let n = rhs.len();
+
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&a, &b| matrix[[a, col]].abs().partial_cmp(&matrix[[b, col]].abs()).unwrap_or(std::cmp::Ordering::Equal))
+            .unwrap_or(col);
+
+        if matrix[[pivot, col]].abs() < 1e-14 {
+            return Err(SimulationError::SolverError {
+                method: "radiosity".to_string(),
+                reason: "Singular radiosity matrix".to_string(),
+            });
+        }
+
+        if pivot != col {
+            for k in 0..n {
+                matrix.swap([col, k], [pivot, k]);
+            }
+            rhs.swap(col, pivot);
+        }
+
+        for row in col + 1..n {
+            let factor = matrix[[row, col]] / matrix[[col, col]];
+            if factor != 0.0 {
+                for k in col..n {
+                    matrix[[row, k]] -= factor * matrix[[col, k]];
+                }
+                rhs[row] -= factor * rhs[col];
+            }
+        }
+    }
+
+    let mut solution = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|k| matrix[[row, k]] * solution[k]).sum();
+        solution[row] = (rhs[row] - sum) / matrix[[row, row]];
+    }
+
+    Ok(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::mesh::CylindricalMesh;
+
+    #[test]
+    fn test_disk_view_factor_limits() {
+        // Very close disks of equal size see each other almost entirely
+        assert!(disk_to_disk_view_factor(1.0, 1.0, 1e-3) > 0.99);
+
+        // Known value: equal disks with R/h = 1 → F ≈ 0.382
+        assert!((disk_to_disk_view_factor(1.0, 1.0, 1.0) - 0.381966).abs() < 1e-5);
+
+        // Distant disks barely see each other
+        assert!(disk_to_disk_view_factor(1.0, 1.0, 100.0) < 1e-3);
+    }
+
+    #[test]
+    fn test_view_factor_summation_and_reciprocity() {
+        let mesh = CylindricalMesh::new(1.0, 2.0, 12, 20).unwrap();
+        let enclosure = EnclosureRadiation::new(&mesh, EnclosureRadiationConfig::default()).unwrap();
+        let n = enclosure.patches.len();
+
+        for i in 0..n {
+            let row_sum: f64 = (0..n).map(|j| enclosure.view_factors[[i, j]]).sum();
+            assert!((row_sum - 1.0).abs() < 1e-9);
+
+            for j in 0..n {
+                let lhs = enclosure.patches[i].area * enclosure.view_factors[[i, j]];
+                let rhs = enclosure.patches[j].area * enclosure.view_factors[[j, i]];
+                assert!((lhs - rhs).abs() < 1e-6 * lhs.abs().max(1.0));
+                assert!(enclosure.view_factors[[i, j]] >= 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_radiosity_energy_conservation() {
+        let mesh = CylindricalMesh::new(1.0, 1.5, 15, 15).unwrap();
+        let enclosure = EnclosureRadiation::new(&mesh, EnclosureRadiationConfig::default()).unwrap();
+
+        // Hot melt at the bottom, cooler walls and roof
+        let temperatures: Vec<f64> = enclosure
+            .patches
+            .iter()
+            .map(|p| if p.surface == PatchSurface::Bottom { 1800.0 } else { 900.0 })
+            .collect();
+        let fluxes = enclosure.solve_net_fluxes(&temperatures, 0.8).unwrap();
+
+        let net: f64 = enclosure.patches.iter().zip(&fluxes).map(|(p, q)| p.area * q).sum();
+        let emitted: f64 = enclosure.patches.iter().zip(&fluxes).map(|(p, q)| p.area * q.abs()).sum();
+        assert!(net.abs() < 1e-6 * emitted);
+
+        // The hot bottom loses heat, the cooler surfaces gain it
+        for (patch, q) in enclosure.patches.iter().zip(&fluxes) {
+            if patch.surface == PatchSurface::Bottom {
+                assert!(*q > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_isothermal_enclosure_has_no_exchange() {
+        let mesh = CylindricalMesh::new(0.5, 1.0, 10, 10).unwrap();
+        let enclosure = EnclosureRadiation::new(&mesh, EnclosureRadiationConfig::default()).unwrap();
+        let temperature = mesh.create_temperature_array(1200.0);
+
+        let flux = enclosure.calculate_surface_flux(&mesh, &temperature, 0.7).unwrap();
+        assert!(flux.iter().all(|q| q.abs() < 1e-6));
+    }
+}
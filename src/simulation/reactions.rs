@@ -0,0 +1,261 @@
+//! Chemical reactions with Arrhenius kinetics
+//!
+//! A material can carry reactions such as pyrolysis, combustion or
+//! gasification. Each reaction has a per-cell conversion α ∈ [0, 1] that
+//! advances with the rate
+//!
+//! dα/dt = A exp(-Ea / (R T)) (1 - α)^n
+//!
+//! and releases (exothermic) or absorbs (endothermic) heat
+//! q = ρ Y ΔH dα/dt, where Y is the reacting mass fraction.
+
+use crate::errors::{Result, SimulationError};
+use ndarray::Array2;
+
+/// Universal gas constant in J/(mol·K)
+const GAS_CONSTANT: f64 = 8.314;
+
+/// Type of chemical reaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ReactionKind {
+    Pyrolysis,
+    Combustion,
+    Gasification,
+}
+
+fn default_order() -> f64 {
+    1.0
+}
+
+fn default_mass_fraction() -> f64 {
+    1.0
+}
+
+/// Reaction with Arrhenius rate parameters
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Reaction {
+    pub name: String,
+    pub kind: ReactionKind,
+    /// Pre-exponential factor A in 1/s
+    pub pre_exponential: f64,
+    /// Activation energy Ea in J/mol
+    pub activation_energy: f64,
+    /// Heat released per kg of reacting mass in J/kg (negative for endothermic)
+    pub heat_of_reaction: f64,
+    /// Reaction order n
+    #[serde(default = "default_order")]
+    pub order: f64,
+    /// Fraction of the material mass taking part in the reaction
+    #[serde(default = "default_mass_fraction")]
+    pub mass_fraction: f64,
+}
+
+impl Reaction {
+    /// Create a first-order reaction involving the whole material mass
+    pub fn new(
+        name: &str,
+        kind: ReactionKind,
+        pre_exponential: f64,
+        activation_energy: f64,
+        heat_of_reaction: f64,
+    ) -> Result<Self> {
+        let reaction = Self {
+            name: name.to_string(),
+            kind,
+            pre_exponential,
+            activation_energy,
+            heat_of_reaction,
+            order: 1.0,
+            mass_fraction: 1.0,
+        };
+        reaction.validate()?;
+        Ok(reaction)
+    }
+
+    /// Set the reacting mass fraction
+    pub fn with_mass_fraction(mut self, mass_fraction: f64) -> Result<Self> {
+        crate::errors::validation::validate_range(mass_fraction, 0.0, 1.0, "reaction mass fraction")?;
+        self.mass_fraction = mass_fraction;
+        Ok(self)
+    }
+
+    /// Validate reaction parameters
+    pub fn validate(&self) -> Result<()> {
+        use crate::errors::validation::{validate_non_empty_string, validate_positive, validate_range};
+
+        validate_non_empty_string(&self.name, "reaction name")?;
+        validate_positive(self.pre_exponential, &format!("{} pre-exponential factor", self.name))?;
+        validate_range(self.activation_energy, 0.0, 1.0e7, &format!("{} activation energy", self.name))?;
+        validate_range(self.order, 0.0, 5.0, &format!("{} reaction order", self.name))?;
+        validate_range(self.mass_fraction, 0.0, 1.0, &format!("{} mass fraction", self.name))?;
+        if !self.heat_of_reaction.is_finite() {
+            return Err(SimulationError::InvalidParameter {
+                parameter: format!("{} heat of reaction", self.name),
+                value: self.heat_of_reaction.to_string(),
+                range: "finite value".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Arrhenius rate constant k(T) = A exp(-Ea / (R T)) in 1/s
+    pub fn rate_constant(&self, temperature: f64) -> f64 {
+        if temperature <= 0.0 {
+            return 0.0;
+        }
+        self.pre_exponential * (-self.activation_energy / (GAS_CONSTANT * temperature)).exp()
+    }
+
+    /// Advance the conversion over a time step
+    ///
+    /// First-order reactions are integrated exactly; other orders use an
+    /// explicit step limited to full conversion.
+    pub fn advance_conversion(&self, conversion: f64, temperature: f64, dt: f64) -> f64 {
+        let k = self.rate_constant(temperature);
+        let remaining = (1.0 - conversion).max(0.0);
+
+        let new_conversion = if (self.order - 1.0).abs() < 1e-12 {
+            1.0 - remaining * (-k * dt).exp()
+        } else {
+            conversion + k * remaining.powf(self.order) * dt
+        };
+
+        new_conversion.clamp(conversion, 1.0)
+    }
+}
+
+/// Reaction progress at one point in time
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ReactionSnapshot {
+    pub time: f64,
+    /// Volume-averaged conversion of each reaction
+    pub mean_conversion: Vec<f64>,
+    /// Total reaction heat release rate in W (negative when endothermic)
+    pub heat_release_rate: f64,
+}
+
+/// Time history of reaction progress
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ReactionHistory {
+    /// Reaction names, matching the order of `mean_conversion`
+    pub names: Vec<String>,
+    pub snapshots: Vec<ReactionSnapshot>,
+}
+
+/// Per-cell reaction state for all reactions of a material
+#[derive(Debug, Clone)]
+pub struct ReactionModel {
+    pub reactions: Vec<Reaction>,
+    /// Conversion field of each reaction
+    pub conversion: Vec<Array2<f64>>,
+    /// Reaction heat source in W/m³
+    pub heat_source: Array2<f64>,
+}
+
+impl ReactionModel {
+    /// Create the model with zero conversion everywhere
+    pub fn new(reactions: Vec<Reaction>, mesh: &super::mesh::CylindricalMesh) -> Result<Self> {
+        for reaction in &reactions {
+            reaction.validate()?;
+        }
+
+        let shape = (mesh.nr, mesh.nz);
+        Ok(Self {
+            conversion: vec![Array2::zeros(shape); reactions.len()],
+            reactions,
+            heat_source: Array2::zeros(shape),
+        })
+    }
+
+    /// Advance all conversions over a time step and update the heat source
+    ///
+    /// `density` gives the charge density in kg/m³ at a temperature.
+    pub fn advance(&mut self, temperature: &Array2<f64>, density: impl Fn(f64) -> f64, dt: f64) {
+        self.heat_source.fill(0.0);
+
+        for (reaction, conversion) in self.reactions.iter().zip(self.conversion.iter_mut()) {
+            let specific_energy = reaction.mass_fraction * reaction.heat_of_reaction;
+
+            for ((i, j), alpha) in conversion.indexed_iter_mut() {
+                let t = temperature[[i, j]];
+                let new_alpha = reaction.advance_conversion(*alpha, t, dt);
+                self.heat_source[[i, j]] += density(t) * specific_energy * (new_alpha - *alpha) / dt;
+                *alpha = new_alpha;
+            }
+        }
+    }
+
+    /// Record the current progress
+    pub fn snapshot(&self, mesh: &super::mesh::CylindricalMesh, time: f64) -> ReactionSnapshot {
+        let mut total_volume = 0.0;
+        let mut heat_release_rate = 0.0;
+        let mut weighted = vec![0.0; self.reactions.len()];
+
+        for i in 0..mesh.nr {
+            for j in 0..mesh.nz {
+                let volume = mesh.get_cell_volume(i, j);
+                total_volume += volume;
+                heat_release_rate += self.heat_source[[i, j]] * volume;
+                for (sum, conversion) in weighted.iter_mut().zip(&self.conversion) {
+                    *sum += conversion[[i, j]] * volume;
+                }
+            }
+        }
+
+        ReactionSnapshot {
+            time,
+            mean_conversion: weighted.into_iter().map(|sum| sum / total_volume).collect(),
+            heat_release_rate,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::mesh::CylindricalMesh;
+
+    fn pyrolysis() -> Reaction {
+        Reaction::new("pyrolysis", ReactionKind::Pyrolysis, 1.0e8, 1.25e5, -2.0e5).unwrap()
+    }
+
+    #[test]
+    fn test_arrhenius_rate_increases_with_temperature() {
+        let reaction = pyrolysis();
+        assert!(reaction.rate_constant(800.0) > reaction.rate_constant(500.0));
+        assert_eq!(reaction.rate_constant(0.0), 0.0);
+
+        let invalid = Reaction::new("bad", ReactionKind::Combustion, -1.0, 1.0e5, 1.0e6);
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_first_order_conversion_is_exact() {
+        let reaction = pyrolysis();
+        let k = reaction.rate_constant(700.0);
+        let alpha = reaction.advance_conversion(0.0, 700.0, 2.0);
+        assert!((alpha - (1.0 - (-2.0 * k).exp())).abs() < 1e-12);
+
+        // Conversion never exceeds one and never decreases
+        assert_eq!(reaction.advance_conversion(0.5, 2000.0, 1.0e3), 1.0);
+        assert!((reaction.advance_conversion(0.5, 300.0, 1.0) - 0.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_endothermic_reaction_absorbs_heat() {
+        let mesh = CylindricalMesh::new(0.5, 1.0, 10, 10).unwrap();
+        let mut model = ReactionModel::new(vec![pyrolysis()], &mesh).unwrap();
+        let mut temperature = mesh.create_temperature_array(300.0);
+        temperature[[2, 2]] = 800.0;
+
+        model.advance(&temperature, |_| 600.0, 0.1);
+
+        assert!(model.conversion[0][[2, 2]] > 0.0);
+        assert!(model.heat_source[[2, 2]] < 0.0);
+        assert!(model.conversion[0][[5, 5]] < 1e-6);
+
+        let snapshot = model.snapshot(&mesh, 0.1);
+        assert!(snapshot.mean_conversion[0] > 0.0);
+        assert!(snapshot.heat_release_rate < 0.0);
+    }
+}
@@ -0,0 +1,374 @@
+//! Material regions and thermal contact between them
+//!
+//! The domain is filled with the base material; rectangular regions in the
+//! (r, z) plane override it with other library materials. Regions are
+//! resolved on the mesh nodes, later regions taking precedence where they
+//! overlap. Faces between nodes of different regions conduct perfectly unless
+//! a contact interface gives them a finite contact conductance h_c, in which
+//! case the face conductance becomes
+//!
+//! G = 1 / (d / (2 k_P) + 1 / h_c + d / (2 k_N))
+//!
+//! and the temperature jumps by q / h_c across the contact.
+
+use super::materials::{Material, MaterialLibrary};
+use super::mesh::CylindricalMesh;
+use crate::errors::{Result, SimulationError};
+use crate::formula::engine::FormulaEngine;
+use ndarray::Array2;
+use std::f64::consts::PI;
+
+/// Name under which interfaces refer to the base material
+pub const BASE_REGION: &str = "base";
+
+/// Rectangular region of a library material
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MaterialRegion {
+    pub name: String,
+    pub material_name: String,
+    /// Radial extent (min, max) in meters
+    pub r_range: (f64, f64),
+    /// Axial extent (min, max) in meters
+    pub z_range: (f64, f64),
+}
+
+impl MaterialRegion {
+    /// Check whether a point lies inside the region (boundaries included)
+    pub fn contains(&self, r: f64, z: f64) -> bool {
+        r >= self.r_range.0 && r <= self.r_range.1 && z >= self.z_range.0 && z <= self.z_range.1
+    }
+}
+
+/// Index of the last region containing (r, z), counting the base material as 0
+fn region_index(regions: &[MaterialRegion], r: f64, z: f64) -> usize {
+    regions.iter().rposition(|region| region.contains(r, z)).map_or(0, |k| k + 1)
+}
+
+/// Contact conductance between two touching regions
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ContactInterface {
+    /// First region name ("base" for the base material)
+    pub region_a: String,
+    /// Second region name ("base" for the base material)
+    pub region_b: String,
+    /// Contact conductance in W/(m²·K)
+    pub conductance: f64,
+}
+
+impl ContactInterface {
+    fn joins(&self, a: &str, b: &str) -> bool {
+        (self.region_a == a && self.region_b == b) || (self.region_a == b && self.region_b == a)
+    }
+}
+
+/// Validate region and interface definitions
+pub fn validate_regions(regions: &[MaterialRegion], interfaces: &[ContactInterface]) -> Result<()> {
+    for (i, region) in regions.iter().enumerate() {
+        crate::errors::validation::validate_non_empty_string(&region.name, &format!("region[{}] name", i))?;
+        if region.name == BASE_REGION || regions[..i].iter().any(|other| other.name == region.name) {
+            return Err(SimulationError::ConfigurationError {
+                component: "material regions".to_string(),
+                issue: format!("Region name '{}' is reserved or used twice", region.name),
+            });
+        }
+        if !MaterialLibrary::is_valid_material(&region.material_name) {
+            return Err(SimulationError::MaterialError {
+                material: region.material_name.clone(),
+                property: format!("region '{}'", region.name),
+                details: "Unknown material name".to_string(),
+            });
+        }
+        for (axis, (min, max)) in [("r", region.r_range), ("z", region.z_range)] {
+            if !(min.is_finite() && max.is_finite()) || max <= min {
+                return Err(SimulationError::InvalidParameter {
+                    parameter: format!("region '{}' {} range", region.name, axis),
+                    value: format!("({}, {})", min, max),
+                    range: "finite min < max".to_string(),
+                });
+            }
+        }
+    }
+
+    let known = |name: &str| name == BASE_REGION || regions.iter().any(|region| region.name == name);
+    for (i, interface) in interfaces.iter().enumerate() {
+        if !known(&interface.region_a) || !known(&interface.region_b) || interface.region_a == interface.region_b {
+            return Err(SimulationError::ConfigurationError {
+                component: format!("contact interface[{}]", i),
+                issue: format!(
+                    "Interface must join two different regions, got '{}' and '{}'",
+                    interface.region_a, interface.region_b
+                ),
+            });
+        }
+        crate::errors::validation::validate_positive(interface.conductance, &format!("interface[{}] contact conductance", i))?;
+    }
+
+    Ok(())
+}
+
+/// Contact conductance on the faces between neighbouring nodes in W/(m²·K)
+///
+/// Faces with perfect contact hold `f64::INFINITY`.
+#[derive(Debug, Clone)]
+pub struct ContactFaces {
+    /// Faces between (i, j) and (i + 1, j), shape (nr - 1, nz)
+    pub radial: Array2<f64>,
+    /// Faces between (i, j) and (i, j + 1), shape (nr, nz - 1)
+    pub axial: Array2<f64>,
+}
+
+impl ContactFaces {
+    /// Conductance per unit area of a face between two nodes at `distance`
+    pub fn face_conductance(k_a: f64, k_b: f64, distance: f64, contact: f64) -> f64 {
+        1.0 / (0.5 * distance / k_a + 0.5 * distance / k_b + 1.0 / contact)
+    }
+}
+
+/// Heat flow and temperature jump across one contact interface
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct InterfaceReport {
+    pub region_a: String,
+    pub region_b: String,
+    /// Contact conductance in W/(m²·K)
+    pub conductance: f64,
+    /// Contact area resolved on the mesh in m²
+    pub area: f64,
+    /// Heat flow from region a to region b in W
+    pub heat_flow: f64,
+    /// Area-weighted mean temperature jump across the contact in K
+    pub mean_temperature_jump: f64,
+    /// Largest temperature jump across the contact in K
+    pub max_temperature_jump: f64,
+}
+
+/// Material regions resolved on the mesh
+pub struct RegionMap {
+    /// Region names, the base material first
+    pub names: Vec<String>,
+    /// Material of each region, the base material first
+    pub materials: Vec<Material>,
+    /// Region index of each node
+    pub index: Array2<usize>,
+    /// Contact conductance on the faces between nodes
+    pub contact_faces: ContactFaces,
+    /// Thermal conductivity of each node in W/(m·K)
+    pub conductivity: Array2<f64>,
+    /// Conductivity of each node relative to the base material
+    pub conductivity_ratio: Array2<f64>,
+    /// Volumetric heat capacity of each node relative to the base material
+    pub capacity_ratio: Array2<f64>,
+    regions: Vec<MaterialRegion>,
+    interfaces: Vec<ContactInterface>,
+    formula_engine: FormulaEngine,
+}
+
+impl RegionMap {
+    /// Resolve regions and contact interfaces on a mesh
+    pub fn new(
+        base: &Material,
+        regions: &[MaterialRegion],
+        interfaces: &[ContactInterface],
+        mesh: &CylindricalMesh,
+    ) -> Result<Self> {
+        validate_regions(regions, interfaces)?;
+
+        let mut names = vec![BASE_REGION.to_string()];
+        let mut materials = vec![base.clone()];
+        for region in regions {
+            names.push(region.name.clone());
+            materials.push(MaterialLibrary::get_material(&region.material_name)?);
+        }
+
+        let shape = (mesh.nr, mesh.nz);
+        let index = Array2::from_shape_fn(shape, |(i, j)| region_index(regions, mesh.r_coords[i], mesh.z_coords[j]));
+
+        let contact = |a: usize, b: usize| {
+            interfaces
+                .iter()
+                .find(|interface| a != b && interface.joins(&names[a], &names[b]))
+                .map_or(f64::INFINITY, |interface| interface.conductance)
+        };
+        let contact_faces = ContactFaces {
+            radial: Array2::from_shape_fn((mesh.nr - 1, mesh.nz), |(i, j)| contact(index[[i, j]], index[[i + 1, j]])),
+            axial: Array2::from_shape_fn((mesh.nr, mesh.nz - 1), |(i, j)| contact(index[[i, j]], index[[i, j + 1]])),
+        };
+
+        Ok(Self {
+            names,
+            materials,
+            index,
+            contact_faces,
+            conductivity: Array2::zeros(shape),
+            conductivity_ratio: Array2::ones(shape),
+            capacity_ratio: Array2::ones(shape),
+            regions: regions.to_vec(),
+            interfaces: interfaces.to_vec(),
+            formula_engine: FormulaEngine::new(),
+        })
+    }
+
+    /// Material at a node
+    pub fn material_at(&self, i: usize, j: usize) -> &Material {
+        &self.materials[self.index[[i, j]]]
+    }
+
+    /// Region index at an arbitrary point (r, z), 0 for the base material
+    pub fn region_at(&self, r: f64, z: f64) -> usize {
+        region_index(&self.regions, r, z)
+    }
+
+    /// Update node properties from the temperature field
+    pub fn update_properties(&mut self, physics: &super::physics::PlasmaPhysics, temperature: &Array2<f64>) -> Result<()> {
+        for ((i, j), &t) in temperature.indexed_iter() {
+            let k_base = physics.get_thermal_conductivity(t);
+
+            let region = self.index[[i, j]];
+            if region == 0 {
+                self.conductivity[[i, j]] = k_base;
+                self.conductivity_ratio[[i, j]] = 1.0;
+                self.capacity_ratio[[i, j]] = 1.0;
+                continue;
+            }
+
+            let material = &self.materials[region];
+            let k = material.get_thermal_conductivity(t, Some(&mut self.formula_engine))?;
+            let capacity = material.get_density(t, Some(&mut self.formula_engine))?
+                * material.get_specific_heat(t, Some(&mut self.formula_engine))?;
+
+            self.conductivity[[i, j]] = k;
+            self.conductivity_ratio[[i, j]] = k / k_base;
+            self.capacity_ratio[[i, j]] = capacity / (physics.get_density(t) * physics.get_specific_heat(t));
+        }
+        Ok(())
+    }
+
+    /// Heat flow and temperature jump across every configured interface
+    pub fn interface_reports(&self, mesh: &CylindricalMesh, temperature: &Array2<f64>) -> Vec<InterfaceReport> {
+        let mut reports: Vec<InterfaceReport> = self
+            .interfaces
+            .iter()
+            .map(|interface| InterfaceReport {
+                region_a: interface.region_a.clone(),
+                region_b: interface.region_b.clone(),
+                conductance: interface.conductance,
+                area: 0.0,
+                heat_flow: 0.0,
+                mean_temperature_jump: 0.0,
+                max_temperature_jump: 0.0,
+            })
+            .collect();
+
+        let radial_faces = self.contact_faces.radial.indexed_iter().map(|((i, j), &h)| {
+            let area = 2.0 * PI * (mesh.r_coords[i] + 0.5 * mesh.dr) * mesh.dz;
+            ((i, j), (i + 1, j), h, mesh.dr, area)
+        });
+        let axial_faces = self
+            .contact_faces
+            .axial
+            .indexed_iter()
+            .map(|((i, j), &h)| ((i, j), (i, j + 1), h, mesh.dz, mesh.get_cell_area_axial(i, j)));
+
+        for (p, n, contact, distance, area) in radial_faces.chain(axial_faces) {
+            if !contact.is_finite() {
+                continue;
+            }
+
+            let g = ContactFaces::face_conductance(self.conductivity[p], self.conductivity[n], distance, contact);
+            let flux = g * (temperature[p] - temperature[n]);
+            let jump = (flux / contact).abs();
+
+            let (name_p, name_n) = (&self.names[self.index[p]], &self.names[self.index[n]]);
+            if let Some(report) = reports.iter_mut().find(|report| {
+                (&report.region_a, &report.region_b) == (name_p, name_n)
+                    || (&report.region_a, &report.region_b) == (name_n, name_p)
+            }) {
+                let sign = if &report.region_a == name_p { 1.0 } else { -1.0 };
+                report.area += area;
+                report.heat_flow += sign * flux * area;
+                report.mean_temperature_jump += jump * area;
+                report.max_temperature_jump = report.max_temperature_jump.max(jump);
+            }
+        }
+
+        for report in &mut reports {
+            if report.area > 0.0 {
+                report.mean_temperature_jump /= report.area;
+            }
+        }
+        reports
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shell_and_lining() -> (Vec<MaterialRegion>, Vec<ContactInterface>) {
+        let regions = vec![MaterialRegion {
+            name: "shell".to_string(),
+            material_name: "Carbon Steel".to_string(),
+            r_range: (0.8, 1.0),
+            z_range: (0.0, 2.0),
+        }];
+        let interfaces = vec![ContactInterface {
+            region_a: "base".to_string(),
+            region_b: "shell".to_string(),
+            conductance: 200.0,
+        }];
+        (regions, interfaces)
+    }
+
+    #[test]
+    fn test_regions_resolved_on_nodes() {
+        let mesh = CylindricalMesh::new(1.0, 2.0, 11, 11).unwrap();
+        let base = MaterialLibrary::get_material("Ceramic").unwrap();
+        let (regions, interfaces) = shell_and_lining();
+        let map = RegionMap::new(&base, &regions, &interfaces, &mesh).unwrap();
+
+        assert_eq!(map.index[[7, 5]], 0);
+        assert_eq!(map.index[[8, 5]], 1);
+        assert_eq!(map.materials[1].name, "Carbon Steel");
+
+        // Only the faces between r = 0.7 and r = 0.8 carry the contact
+        assert_eq!(map.contact_faces.radial[[7, 5]], 200.0);
+        assert!(map.contact_faces.radial[[6, 5]].is_infinite());
+        assert!(map.contact_faces.radial[[8, 5]].is_infinite());
+        assert!(map.contact_faces.axial.iter().all(|h| h.is_infinite()));
+    }
+
+    #[test]
+    fn test_interface_report_temperature_jump() {
+        let mesh = CylindricalMesh::new(1.0, 2.0, 11, 11).unwrap();
+        let base = MaterialLibrary::get_material("Ceramic").unwrap();
+        let (regions, interfaces) = shell_and_lining();
+        let mut map = RegionMap::new(&base, &regions, &interfaces, &mesh).unwrap();
+        map.conductivity.fill(20.0);
+
+        let mut temperature = mesh.create_temperature_array(400.0);
+        for j in 0..mesh.nz {
+            for i in 0..8 {
+                temperature[[i, j]] = 600.0;
+            }
+        }
+        let reports = map.interface_reports(&mesh, &temperature);
+
+        assert_eq!(reports.len(), 1);
+        let g = ContactFaces::face_conductance(20.0, 20.0, mesh.dr, 200.0);
+        let jump = g * 200.0 / 200.0;
+        assert!((reports[0].max_temperature_jump - jump).abs() < 1e-9);
+        assert!((reports[0].mean_temperature_jump - jump).abs() < 1e-9);
+        assert!(reports[0].heat_flow > 0.0);
+    }
+
+    #[test]
+    fn test_region_validation() {
+        let (mut regions, mut interfaces) = shell_and_lining();
+        assert!(validate_regions(&regions, &interfaces).is_ok());
+
+        interfaces[0].region_b = "gap".to_string();
+        assert!(validate_regions(&regions, &interfaces).is_err());
+
+        regions[0].material_name = "Unobtainium".to_string();
+        assert!(validate_regions(&regions, &[]).is_err());
+    }
+}
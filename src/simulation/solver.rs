@@ -5,6 +5,7 @@
 //! Crank-Nicolson methods.
 
 use crate::errors::Result;
+use crate::formula::engine::{FormulaContext, FormulaEngine};
 use ndarray::Array2;
 
 /// Solver method enumeration
@@ -28,6 +29,32 @@ pub struct HeatSolver {
     pub method: SolverMethod,
     pub dt: f64,
     pub cfl_factor: f64,
+    /// Simulation time at the start of the next step (s)
+    pub current_time: f64,
+    /// Additional heat flux into the domain at surface nodes in W/m², e.g. from
+    /// enclosure radiation exchange
+    pub surface_heat_flux: Option<Array2<f64>>,
+    /// Additional volumetric heat source at each node in W/m³, e.g. from
+    /// participating-media radiation
+    pub volumetric_heat_source: Option<Array2<f64>>,
+    /// Effective conductivity multiplier at each node, e.g. from natural
+    /// convection in molten regions
+    pub conductivity_multiplier: Option<Array2<f64>>,
+    /// Volumetric heat capacity multiplier at each node, e.g. from drying
+    /// charge material
+    pub capacity_multiplier: Option<Array2<f64>>,
+    /// Ratio of axial to radial conductivity at each node for anisotropic
+    /// materials (isotropic if not set)
+    pub axial_conductivity_ratio: Option<Array2<f64>>,
+    /// Local coolant temperature at cooled-jacket surface nodes in K
+    pub coolant_temperature: Option<Array2<f64>>,
+    /// Coolant energy balance of every cooled jacket from the last step
+    pub coolant_reports: Vec<super::boundary::CoolantReport>,
+    /// Contact conductances between material regions; when set, interior nodes
+    /// use face conductances so contact resistance is applied on shared faces
+    pub contact_faces: Option<super::regions::ContactFaces>,
+    /// Formula engine for formula-driven boundary conditions
+    formula_engine: FormulaEngine,
 }
 
 impl HeatSolver {
@@ -37,6 +64,16 @@ impl HeatSolver {
             method,
             dt: 0.001, // Default time step
             cfl_factor: 0.5, // Default CFL factor
+            current_time: 0.0,
+            surface_heat_flux: None,
+            volumetric_heat_source: None,
+            conductivity_multiplier: None,
+            capacity_multiplier: None,
+            axial_conductivity_ratio: None,
+            coolant_temperature: None,
+            coolant_reports: Vec::new(),
+            contact_faces: None,
+            formula_engine: FormulaEngine::new(),
         }
     }
     
@@ -54,9 +91,29 @@ impl HeatSolver {
             method,
             dt: 0.001,
             cfl_factor,
+            current_time: 0.0,
+            surface_heat_flux: None,
+            volumetric_heat_source: None,
+            conductivity_multiplier: None,
+            capacity_multiplier: None,
+            axial_conductivity_ratio: None,
+            coolant_temperature: None,
+            coolant_reports: Vec::new(),
+            contact_faces: None,
+            formula_engine: FormulaEngine::new(),
         })
     }
     
+    /// Conductivity multiplier at a node (1 if not set)
+    pub fn conductivity_multiplier_at(&self, i: usize, j: usize) -> f64 {
+        self.conductivity_multiplier.as_ref().map_or(1.0, |multiplier| multiplier[[i, j]])
+    }
+    
+    /// Volumetric heat capacity multiplier at a node (1 if not set)
+    pub fn capacity_multiplier_at(&self, i: usize, j: usize) -> f64 {
+        self.capacity_multiplier.as_ref().map_or(1.0, |multiplier| multiplier[[i, j]])
+    }
+    
     /// Solve one time step using the configured method
     pub fn solve_time_step(
         &mut self,
@@ -90,10 +147,17 @@ impl HeatSolver {
         let reference_temp = 500.0;
         let k = physics.get_thermal_conductivity(reference_temp);
         let cp = physics.get_specific_heat(reference_temp);
-        let rho = physics.get_density();
-        
-        // Calculate thermal diffusivity: α = k/(ρ*cp)
-        let alpha = k / (rho * cp);
+        let rho = physics.get_density(reference_temp);
+        
+        // Calculate thermal diffusivity: α = k/(ρ*cp), including the largest
+        // effective conductivity enhancement
+        let max_multiplier = self.conductivity_multiplier.as_ref()
+            .map_or(1.0, |multiplier| multiplier.iter().cloned().fold(1.0, f64::max));
+        let min_capacity = self.capacity_multiplier.as_ref()
+            .map_or(1.0, |multiplier| multiplier.iter().cloned().fold(1.0, f64::min));
+        let max_axial_ratio = self.axial_conductivity_ratio.as_ref()
+            .map_or(1.0, |ratio| ratio.iter().cloned().fold(1.0, f64::max));
+        let alpha = k * max_multiplier * max_axial_ratio / (rho * cp * min_capacity);
         
         // CFL condition for 2D cylindrical coordinates
         // Δt ≤ min(Δr², Δz²) / (2α)
@@ -126,24 +190,31 @@ impl HeatSolver {
         // Update time step
         self.dt = dt;
         
+        // Resolve boundary specifications once per step
+        let boundaries = physics.boundary_conditions.resolve();
+        self.march_coolant(mesh, &temp_old, &boundaries);
+        
         // Iterate through all interior nodes
         for i in 0..nr {
             for j in 0..nz {
+                if mesh.get_boundary_type(i, j) != super::mesh::BoundaryType::Interior {
+                    continue;
+                }
                 let t_old = temp_old[[i, j]];
                 
                 // Get material properties at current temperature
-                let k = physics.get_thermal_conductivity(t_old);
-                let cp = physics.get_specific_heat(t_old);
-                let rho = physics.get_density();
+                let k = physics.get_thermal_conductivity(t_old) * self.conductivity_multiplier_at(i, j);
+                let cp = physics.get_specific_heat(t_old) * self.capacity_multiplier_at(i, j);
+                let rho = physics.get_density(t_old);
                 let alpha = k / (rho * cp);
                 
                 // Calculate heat source at this position
                 let (r, z) = mesh.get_coordinates(i, j).unwrap();
-                let heat_source = physics.calculate_heat_source(r, z);
+                let heat_source = physics.calculate_heat_source(r, z)
+                    + self.volumetric_heat_source.as_ref().map_or(0.0, |source| source[[i, j]]);
                 
-                // Apply boundary conditions or calculate interior update
-                let new_temp = if mesh.get_boundary_type(i, j) != super::mesh::BoundaryType::Interior {
-                    self.apply_boundary_conditions(i, j, &temp_old, mesh, physics)?
+                let new_temp = if self.contact_faces.is_some() {
+                    self.calculate_interior_update_with_contacts(i, j, &temp_old, mesh, physics, heat_source, rho, cp, dt)?
                 } else {
                     self.calculate_interior_update(i, j, &temp_old, mesh, alpha, heat_source, rho, cp, dt)?
                 };
@@ -152,6 +223,20 @@ impl HeatSolver {
             }
         }
         
+        // Surface nodes follow the updated interior, so the heat conducted to
+        // the surface in this step is exactly what leaves through the boundary;
+        // outer corners follow the already updated edge nodes
+        for i in 0..nr {
+            for j in 0..nz {
+                if mesh.get_boundary_type(i, j) != super::mesh::BoundaryType::Interior {
+                    let surface = self.apply_boundary_conditions(i, j, temperature, mesh, physics, &boundaries)?;
+                    temperature[[i, j]] = surface;
+                }
+            }
+        }
+        
+        self.current_time += dt;
+        
         Ok(())
     }
     
@@ -212,9 +297,10 @@ impl HeatSolver {
             (t_up - 2.0 * t_center + t_down) / (dz * dz)
         };
         
-        // Heat equation: dT/dt = α * (radial_term + axial_term) + Q/(ρ*cp)
+        // Heat equation: dT/dt = α * (radial_term + axial_ratio * axial_term) + Q/(ρ*cp)
+        let axial_ratio = self.axial_conductivity_ratio.as_ref().map_or(1.0, |ratio| ratio[[i, j]]);
         let heat_source_term = heat_source / (rho * cp);
-        let dt_dt = alpha * (d2t_dr2 + d2t_dz2) + heat_source_term;
+        let dt_dt = alpha * (d2t_dr2 + axial_ratio * d2t_dz2) + heat_source_term;
         
         // Forward Euler update: T^(n+1) = T^n + dt * dT/dt
         let new_temp = t_center + dt * dt_dt;
@@ -230,93 +316,197 @@ impl HeatSolver {
         Ok(new_temp)
     }
     
+    /// Calculate temperature update for interior nodes from face heat flows
+    ///
+    /// Each face conducts with the series conductance of the two half cells
+    /// and the contact conductance between them, so regions of different
+    /// materials exchange heat consistently and contacts add their resistance.
+    #[allow(clippy::too_many_arguments)]
+    fn calculate_interior_update_with_contacts(
+        &self,
+        i: usize,
+        j: usize,
+        temperature: &Array2<f64>,
+        mesh: &super::mesh::CylindricalMesh,
+        physics: &super::physics::PlasmaPhysics,
+        heat_source: f64,
+        rho: f64,
+        cp: f64,
+        dt: f64,
+    ) -> Result<f64> {
+        use super::regions::ContactFaces;
+        
+        let contacts = self.contact_faces.as_ref().unwrap();
+        let conductivity = |ni: usize, nj: usize| {
+            physics.get_thermal_conductivity(temperature[[ni, nj]]) * self.conductivity_multiplier_at(ni, nj)
+        };
+        let axial_conductivity = |ni: usize, nj: usize| {
+            conductivity(ni, nj) * self.axial_conductivity_ratio.as_ref().map_or(1.0, |ratio| ratio[[ni, nj]])
+        };
+        
+        let t_center = temperature[[i, j]];
+        let r = mesh.r_coords[i];
+        let (dr, dz) = (mesh.dr, mesh.dz);
+        
+        // Heat flux into the node through a face in W/m², using the radial or
+        // axial conductivity of both nodes
+        let flux = |ni: usize, nj: usize, distance: f64, contact: f64, k: &dyn Fn(usize, usize) -> f64| {
+            ContactFaces::face_conductance(k(i, j), k(ni, nj), distance, contact) * (temperature[[ni, nj]] - t_center)
+        };
+        
+        let radial = ((r + 0.5 * dr) * flux(i + 1, j, dr, contacts.radial[[i, j]], &conductivity)
+            + (r - 0.5 * dr) * flux(i - 1, j, dr, contacts.radial[[i - 1, j]], &conductivity))
+            / (r * dr);
+        let axial = (flux(i, j + 1, dz, contacts.axial[[i, j]], &axial_conductivity)
+            + flux(i, j - 1, dz, contacts.axial[[i, j - 1]], &axial_conductivity))
+            / dz;
+        
+        let new_temp = t_center + dt * (radial + axial + heat_source) / (rho * cp);
+        
+        if !new_temp.is_finite() {
+            return Err(crate::errors::SimulationError::NumericalInstability {
+                step: 0, // Will be set by caller
+                time: 0.0, // Will be set by caller
+            });
+        }
+        
+        Ok(new_temp)
+    }
+    
     /// Apply boundary conditions
     fn apply_boundary_conditions(
-        &self,
+        &mut self,
         i: usize,
         j: usize,
         temperature: &Array2<f64>,
         mesh: &super::mesh::CylindricalMesh,
         physics: &super::physics::PlasmaPhysics,
+        boundaries: &super::boundary::BoundarySet,
     ) -> Result<f64> {
         use super::mesh::BoundaryType;
         
         let boundary_type = mesh.get_boundary_type(i, j);
         let current_temp = temperature[[i, j]];
         
-        match boundary_type {
+        // Select the boundary specification, the interior neighbour and the
+        // coordinate along the boundary used for segment lookup
+        let (spec, (ni, nj), distance, coordinate) = match boundary_type {
             BoundaryType::Axis => {
                 // Axis symmetry: ∂T/∂r = 0 at r = 0
                 // Use temperature from neighboring radial node
                 if i + 1 < mesh.nr {
-                    Ok(temperature[[i + 1, j]])
-                } else {
-                    Ok(current_temp)
-                }
-            }
-            
-            BoundaryType::OuterWall => {
-                // Mixed convection-radiation boundary condition
-                self.apply_convection_radiation_bc(i, j, temperature, mesh, physics)
-            }
-            
-            BoundaryType::Bottom | BoundaryType::Top => {
-                // For now, use adiabatic boundary condition (∂T/∂z = 0)
-                // This can be extended to support specified temperature or heat flux
-                if boundary_type == BoundaryType::Bottom && j + 1 < mesh.nz {
-                    Ok(temperature[[i, j + 1]])
-                } else if boundary_type == BoundaryType::Top && j > 0 {
-                    Ok(temperature[[i, j - 1]])
+                    return Ok(temperature[[i + 1, j]]);
                 } else {
-                    Ok(current_temp)
+                    return Ok(current_temp);
                 }
             }
-            
+            BoundaryType::OuterWall => (&boundaries.outer_wall, (i - 1, j), mesh.dr, mesh.z_coords[j]),
+            BoundaryType::Bottom => (&boundaries.bottom, (i, j + 1), mesh.dz, mesh.r_coords[i]),
+            BoundaryType::Top => (&boundaries.top, (i, j - 1), mesh.dz, mesh.r_coords[i]),
             BoundaryType::Interior => {
                 // This should not happen - interior nodes are handled separately
-                Ok(current_temp)
+                return Ok(current_temp);
             }
-        }
+        };
+        
+        let condition = spec.condition_at(coordinate);
+        let jacket;
+        let condition = match (condition, &self.coolant_temperature) {
+            (super::boundary::BoundaryCondition::CooledJacket { .. }, Some(coolant)) => {
+                jacket = condition.with_coolant_temperature(coolant[[i, j]]);
+                &jacket
+            }
+            _ => condition,
+        };
+        // The default outer wall radiates with the material emissivity at the surface temperature
+        let emissive;
+        let condition = match (&boundary_type, physics.default_wall_emissivity(current_temp)) {
+            (BoundaryType::OuterWall, Some(emissivity)) => {
+                emissive = condition.with_emissivity(emissivity);
+                &emissive
+            }
+            _ => condition,
+        };
+        let t_interior = temperature[[ni, nj]];
+        // Top and bottom conduct heat axially
+        let axial_ratio = match boundary_type {
+            BoundaryType::Top | BoundaryType::Bottom => {
+                self.axial_conductivity_ratio.as_ref().map_or(1.0, |ratio| ratio[[i, j]])
+            }
+            _ => 1.0,
+        };
+        let k = physics.get_thermal_conductivity(current_temp) * self.conductivity_multiplier_at(i, j) * axial_ratio;
+        let external_flux = self.surface_heat_flux.as_ref().map_or(0.0, |flux| flux[[i, j]]);
+        // Only formula conditions need the torch and phase variables
+        let (r, z) = (mesh.r_coords[i], mesh.z_coords[j]);
+        let context = match condition {
+            super::boundary::BoundaryCondition::Formula { .. } => physics.formula_context(r, z, current_temp, self.current_time),
+            _ => FormulaContext::new(current_temp).at(r, z).with_time(self.current_time),
+        };
+        
+        condition.surface_temperature(
+            t_interior,
+            current_temp,
+            k,
+            distance,
+            external_flux,
+            &context,
+            &mut self.formula_engine,
+        )
     }
     
-    /// Apply convection-radiation boundary condition at outer wall
-    /// Heat balance: k * ∂T/∂r = h * (T - T_amb) + ε * σ * (T⁴ - T_amb⁴)
-    fn apply_convection_radiation_bc(
-        &self,
-        i: usize,
-        j: usize,
-        temperature: &Array2<f64>,
+    /// March the coolant of every cooled jacket along its boundary
+    ///
+    /// Uses the surface temperatures of the previous step and records the local
+    /// coolant temperature at each jacketed node together with the energy
+    /// balance of each jacket.
+    fn march_coolant(
+        &mut self,
         mesh: &super::mesh::CylindricalMesh,
-        physics: &super::physics::PlasmaPhysics,
-    ) -> Result<f64> {
-        let current_temp = temperature[[i, j]];
-        let dr = mesh.dr;
-        
-        // Get material properties
-        let k = physics.get_thermal_conductivity(current_temp);
-        let emissivity = physics.material.emissivity;
-        
-        // Get boundary conditions
-        let _h = physics.boundary_conditions.convection_coefficient;
-        let t_amb = physics.boundary_conditions.ambient_temperature;
-        
-        // Calculate heat losses
-        let q_conv = physics.calculate_convection_loss(current_temp);
-        let q_rad = physics.calculate_radiation_loss(current_temp, emissivity);
-        let q_total = q_conv + q_rad;
-        
-        // Apply heat balance using finite difference
-        // k * (T_interior - T_wall) / dr = q_total
-        // Solve for T_wall
-        if i > 0 {
-            let t_interior = temperature[[i - 1, j]];
-            let new_temp = (k * t_interior / dr + q_total) / (k / dr);
+        temperature: &Array2<f64>,
+        boundaries: &super::boundary::BoundarySet,
+    ) {
+        use super::boundary::CoolantMarch;
+        
+        let (nr, nz) = (mesh.nr, mesh.nz);
+        
+        // Surface nodes of each boundary in flow direction: (i, j, coordinate, area)
+        let wall: Vec<_> = (0..nz).map(|j| (nr - 1, j, mesh.z_coords[j], mesh.get_cell_area_radial(nr - 1, j))).collect();
+        let bottom: Vec<_> = (1..nr - 1).map(|i| (i, 0, mesh.r_coords[i], mesh.get_cell_area_axial(i, 0))).collect();
+        let top: Vec<_> = (1..nr - 1).map(|i| (i, nz - 1, mesh.r_coords[i], mesh.get_cell_area_axial(i, nz - 1))).collect();
+        
+        let mut coolant_temperature: Option<Array2<f64>> = None;
+        let mut reports = Vec::new();
+        
+        for (name, spec, nodes) in [
+            ("outer wall", &boundaries.outer_wall, wall),
+            ("bottom", &boundaries.bottom, bottom),
+            ("top", &boundaries.top, top),
+        ] {
+            let mut circuits: Vec<CoolantMarch> = Vec::new();
             
-            // Ensure temperature doesn't go below ambient
-            Ok(new_temp.max(t_amb))
-        } else {
-            Ok(current_temp)
+            for (i, j, coordinate, area) in nodes {
+                let segment = spec.segment_index_at(coordinate);
+                let index = match circuits.iter().position(|circuit| circuit.report.segment == segment) {
+                    Some(index) => index,
+                    None => match CoolantMarch::start(spec.condition_at(coordinate), name, segment) {
+                        Some(circuit) => {
+                            circuits.push(circuit);
+                            circuits.len() - 1
+                        }
+                        None => continue,
+                    },
+                };
+                
+                let local = circuits[index].advance(temperature[[i, j]], area);
+                coolant_temperature.get_or_insert_with(|| Array2::zeros((nr, nz)))[[i, j]] = local;
+            }
+            
+            reports.extend(circuits.into_iter().map(|circuit| circuit.report));
         }
+        
+        self.coolant_temperature = coolant_temperature;
+        self.coolant_reports = reports;
     }
     
     /// Check CFL stability condition
@@ -461,18 +651,195 @@ mod tests {
         let bc = BoundaryConditions::default();
         let physics = PlasmaPhysics::new(vec![torch], material, bc).unwrap();
         
-        let solver = HeatSolver::new(SolverMethod::ForwardEuler);
+        let mut solver = HeatSolver::new(SolverMethod::ForwardEuler);
         let temperature = mesh.create_temperature_array(400.0);
+        let boundaries = physics.boundary_conditions.resolve();
         
         // Test axis boundary condition
-        let axis_temp = solver.apply_boundary_conditions(0, 5, &temperature, &mesh, &physics).unwrap();
+        let axis_temp = solver.apply_boundary_conditions(0, 5, &temperature, &mesh, &physics, &boundaries).unwrap();
         assert!(axis_temp > 0.0);
         
         // Test outer wall boundary condition
-        let wall_temp = solver.apply_boundary_conditions(9, 5, &temperature, &mesh, &physics).unwrap();
+        let wall_temp = solver.apply_boundary_conditions(9, 5, &temperature, &mesh, &physics, &boundaries).unwrap();
         assert!(wall_temp > 0.0);
     }
     
+    #[test]
+    fn test_temperature_dependent_wall_emissivity() {
+        use crate::simulation::materials::Property;
+        
+        let mesh = CylindricalMesh::new(1.0, 2.0, 10, 10).unwrap();
+        let temperature = mesh.create_temperature_array(1200.0);
+        let wall_temperature = |emissivity: Property| {
+            let material = MaterialLibrary::get_material("Carbon Steel").unwrap().with_emissivity(emissivity).unwrap();
+            let torch = PlasmaTorch::new((0.5, 1.0), 100.0, 0.8, 0.1).unwrap();
+            let physics = PlasmaPhysics::new(vec![torch], material, BoundaryConditions::default()).unwrap();
+            let boundaries = physics.boundary_conditions.resolve();
+            let mut solver = HeatSolver::new(SolverMethod::ForwardEuler);
+            (
+                physics.default_wall_emissivity(1200.0),
+                solver.apply_boundary_conditions(9, 5, &temperature, &mesh, &physics, &boundaries).unwrap(),
+            )
+        };
+        
+        // The default wall radiates with the emissivity at the surface temperature
+        let (emissivity, rising) = wall_temperature(Property::Table(vec![(300.0, 0.1), (1500.0, 0.9)]));
+        assert!((emissivity.unwrap() - 0.7).abs() < 1e-12);
+        let (_, dull) = wall_temperature(Property::Constant(0.1));
+        let (_, matched) = wall_temperature(Property::Constant(0.7));
+        assert!(rising < dull);
+        assert!((rising - matched).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_segmented_boundary_conditions() {
+        use crate::simulation::boundary::{BoundaryCondition, BoundarySpec};
+        
+        let mesh = CylindricalMesh::new(1.0, 2.0, 11, 11).unwrap();
+        let torch = PlasmaTorch::new((0.5, 1.0), 100.0, 0.8, 0.1).unwrap();
+        let material = MaterialLibrary::get_material("Carbon Steel").unwrap();
+        
+        // Water-cooled roof, insulated hearth with an open tap hole near the axis
+        let bc = BoundaryConditions {
+            top: Some(BoundarySpec::uniform(BoundaryCondition::FixedTemperature { temperature: 320.0 })),
+            bottom: Some(
+                BoundarySpec::uniform(BoundaryCondition::Adiabatic)
+                    .with_segment(0.0, 0.25, BoundaryCondition::Convection {
+                        heat_transfer_coefficient: 50.0,
+                        ambient_temperature: 300.0,
+                    }),
+            ),
+            ..BoundaryConditions::default()
+        };
+        let physics = PlasmaPhysics::new(vec![torch], material, bc).unwrap();
+        
+        let mut solver = HeatSolver::new(SolverMethod::ForwardEuler);
+        let temperature = mesh.create_temperature_array(500.0);
+        let boundaries = physics.boundary_conditions.resolve();
+        
+        let roof = solver.apply_boundary_conditions(5, 10, &temperature, &mesh, &physics, &boundaries).unwrap();
+        assert_eq!(roof, 320.0);
+        
+        let hearth = solver.apply_boundary_conditions(5, 0, &temperature, &mesh, &physics, &boundaries).unwrap();
+        assert_eq!(hearth, 500.0);
+        
+        let tap_hole = solver.apply_boundary_conditions(2, 0, &temperature, &mesh, &physics, &boundaries).unwrap();
+        assert!(tap_hole < 500.0 && tap_hole > 300.0);
+    }
+    
+    #[test]
+    fn test_contact_resistance_slows_heat_flow() {
+        use crate::simulation::regions::ContactFaces;
+        
+        let mesh = CylindricalMesh::new(1.0, 2.0, 11, 11).unwrap();
+        let torch = PlasmaTorch::new((0.5, 1.0), 100.0, 0.8, 0.1).unwrap();
+        let material = MaterialLibrary::get_material("Carbon Steel").unwrap();
+        let physics = PlasmaPhysics::new(vec![torch], material, BoundaryConditions::default()).unwrap();
+        
+        let mut temperature = mesh.create_temperature_array(300.0);
+        for i in 0..6 {
+            for j in 0..mesh.nz {
+                temperature[[i, j]] = 600.0;
+            }
+        }
+        
+        let step = |contact: f64| {
+            let mut solver = HeatSolver::new(SolverMethod::ForwardEuler);
+            solver.contact_faces = Some(ContactFaces {
+                radial: Array2::from_shape_fn((mesh.nr - 1, mesh.nz), |(i, _)| if i == 5 { contact } else { f64::INFINITY }),
+                axial: Array2::from_elem((mesh.nr, mesh.nz - 1), f64::INFINITY),
+            });
+            solver.calculate_interior_update_with_contacts(6, 5, &temperature, &mesh, &physics, 0.0, 7850.0, 500.0, 1.0)
+                .unwrap()
+        };
+        
+        // The cold side of the contact warms, less so through a poor contact
+        let perfect = step(f64::INFINITY);
+        let resistive = step(100.0);
+        assert!(perfect > 300.0);
+        assert!(resistive > 300.0 && resistive < perfect);
+        
+        // A uniform region stays uniform
+        let uniform = mesh.create_temperature_array(450.0);
+        let mut solver = HeatSolver::new(SolverMethod::ForwardEuler);
+        solver.contact_faces = Some(ContactFaces {
+            radial: Array2::from_elem((mesh.nr - 1, mesh.nz), 100.0),
+            axial: Array2::from_elem((mesh.nr, mesh.nz - 1), 100.0),
+        });
+        let t = solver.calculate_interior_update_with_contacts(4, 4, &uniform, &mesh, &physics, 0.0, 7850.0, 500.0, 1.0).unwrap();
+        assert!((t - 450.0).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_axial_conductivity_ratio() {
+        let mesh = CylindricalMesh::new(1.0, 2.0, 11, 11).unwrap();
+        let torch = PlasmaTorch::new((0.5, 1.0), 100.0, 0.8, 0.1).unwrap();
+        let material = MaterialLibrary::get_material("Carbon Steel").unwrap();
+        let physics = PlasmaPhysics::new(vec![torch], material, BoundaryConditions::default()).unwrap();
+        
+        // Temperature varies along z only, so only axial conduction acts
+        let mut temperature = mesh.create_temperature_array(300.0);
+        for i in 0..mesh.nr {
+            temperature[[i, 5]] = 600.0;
+        }
+        
+        let update = |ratio: f64| {
+            let mut solver = HeatSolver::new(SolverMethod::ForwardEuler);
+            solver.axial_conductivity_ratio = Some(Array2::from_elem((mesh.nr, mesh.nz), ratio));
+            let alpha = 50.0 / (7850.0 * 500.0);
+            solver.calculate_interior_update(5, 5, &temperature, &mesh, alpha, 0.0, 7850.0, 500.0, 1.0).unwrap() - 600.0
+        };
+        
+        assert!(update(1.0) < 0.0);
+        assert!((update(2.0) - 2.0 * update(1.0)).abs() < 1e-12);
+        assert_eq!(update(0.0), 0.0);
+        
+        // Higher axial conductivity shortens the stable time step
+        let fine_mesh = CylindricalMesh::new(0.05, 0.1, 11, 11).unwrap();
+        let mut solver = HeatSolver::new(SolverMethod::ForwardEuler);
+        let isotropic = solver.calculate_stable_timestep(&fine_mesh, &physics);
+        solver.axial_conductivity_ratio = Some(Array2::from_elem((fine_mesh.nr, fine_mesh.nz), 4.0));
+        assert!(solver.calculate_stable_timestep(&fine_mesh, &physics) < isotropic);
+    }
+    
+    #[test]
+    fn test_phase_change_properties_reach_solver() {
+        use crate::simulation::materials::{Material, PhaseProperties, PhaseSet, Property};
+        
+        let mesh = CylindricalMesh::new(1.0, 2.0, 11, 11).unwrap();
+        let phases = PhaseProperties {
+            solidus: 1750.0,
+            liquidus: 1800.0,
+            liquid: PhaseSet { thermal_conductivity: Property::Constant(30.0), specific_heat: Property::Constant(820.0) },
+            vaporization_range: None,
+            gas: None,
+        };
+        let material = Material::new("Phased Steel".to_string(), 7850.0, 0.8).unwrap().with_phase_properties(phases).unwrap();
+        // A narrow torch in the corner leaves the middle of the charge unheated
+        let torch = PlasmaTorch::new((0.0, 0.0), 100.0, 0.8, 0.01).unwrap();
+        let physics = PlasmaPhysics::new(vec![torch], material, BoundaryConditions::default()).unwrap();
+        
+        // Effective properties blend across the melting range
+        assert_eq!(physics.get_thermal_conductivity(1500.0), 50.0);
+        assert!((physics.get_thermal_conductivity(1775.0) - 40.0).abs() < 1e-12);
+        assert_eq!(physics.get_specific_heat(2000.0), 820.0);
+        
+        // The same hot slab relaxes at the solid or the liquid diffusivity
+        let relaxation = |base: f64| {
+            let mut temperature = mesh.create_temperature_array(base);
+            for i in 0..mesh.nr {
+                temperature[[i, 5]] = base + 20.0;
+            }
+            let mut solver = HeatSolver::new(SolverMethod::ForwardEuler);
+            solver.solve_time_step(&mut temperature, &mesh, &physics, 1.0).unwrap();
+            base + 20.0 - temperature[[5, 5]]
+        };
+        let (solid, liquid) = (relaxation(1500.0), relaxation(2000.0));
+        assert!(solid > 0.0);
+        assert!((liquid / solid - (30.0 / 820.0) / (50.0 / 500.0)).abs() < 1e-9);
+        assert!(physics.take_property_error().is_none());
+    }
+    
     #[test]
     fn test_solver_info() {
         let solver = HeatSolver::with_cfl_factor(SolverMethod::ForwardEuler, 0.3).unwrap();
@@ -126,7 +126,7 @@ mod integration_tests {
                 let j = idx % mesh.nz;
                 let volume = mesh.get_cell_volume(i, j);
                 let cp = physics.get_specific_heat(temp);
-                let rho = physics.get_density();
+                let rho = physics.get_density(temp);
                 volume * rho * cp * temp
             })
             .sum();
@@ -144,7 +144,7 @@ mod integration_tests {
                 let j = idx % mesh.nz;
                 let volume = mesh.get_cell_volume(i, j);
                 let cp = physics.get_specific_heat(temp);
-                let rho = physics.get_density();
+                let rho = physics.get_density(temp);
                 volume * rho * cp * temp
             })
             .sum();
@@ -0,0 +1,655 @@
+//! Thermoelastic stress post-processing
+//!
+//! Solves axisymmetric linear thermoelasticity on the simulation mesh for a
+//! given temperature field. Bilinear quadrilateral elements connect the mesh
+//! nodes, and the thermal strain α (T - T_ref) [1, 1, 1, 0] acts as the only
+//! load. The lining rests on the bottom (axial displacement fixed) and is
+//! otherwise free to expand; the radial displacement on the axis is zero by
+//! symmetry.
+//!
+//! The displacement system is solved with Jacobi-preconditioned conjugate
+//! gradients. Stresses are evaluated at element centroids and averaged to the
+//! nodes, giving radial, axial, hoop, shear and von Mises stress fields.
+//! Elastic constants are taken as temperature independent. With material
+//! regions, each element uses the constants of the region containing its
+//! centroid.
+
+use super::materials::{ElasticProperties, Material};
+use super::mesh::CylindricalMesh;
+use super::regions::RegionMap;
+use crate::errors::{Result, SimulationError};
+use ndarray::Array2;
+use std::f64::consts::PI;
+
+/// Gauss point coordinate of the 2×2 rule
+const GAUSS_POINT: f64 = 0.577_350_269_189_625_8;
+
+/// Natural coordinates of the element nodes, counter-clockwise from (i, j)
+const NODE_XI: [f64; 4] = [-1.0, 1.0, 1.0, -1.0];
+const NODE_ETA: [f64; 4] = [-1.0, -1.0, 1.0, 1.0];
+
+/// Thermal stress configuration
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ThermalStressConfig {
+    /// Stress-free temperature in K (simulation initial temperature if not set)
+    pub reference_temperature: Option<f64>,
+    /// Relative residual tolerance of the conjugate gradient solve
+    pub tolerance: f64,
+    /// Maximum conjugate gradient iterations
+    pub max_iterations: usize,
+}
+
+impl Default for ThermalStressConfig {
+    fn default() -> Self {
+        Self {
+            reference_temperature: None,
+            tolerance: 1e-10,
+            max_iterations: 20_000,
+        }
+    }
+}
+
+impl ThermalStressConfig {
+    /// Validate configuration parameters
+    pub fn validate(&self) -> Result<()> {
+        use crate::errors::validation::{validate_positive, validate_range};
+
+        if let Some(reference) = self.reference_temperature {
+            validate_positive(reference, "stress-free reference temperature")?;
+        }
+        validate_range(self.tolerance, 1e-15, 1e-2, "stress solver tolerance")?;
+        if self.max_iterations == 0 {
+            return Err(SimulationError::InvalidParameter {
+                parameter: "stress solver max iterations".to_string(),
+                value: "0".to_string(),
+                range: "≥ 1".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Nodal stress and displacement fields in Pa and m
+#[derive(Debug, Clone)]
+pub struct StressField {
+    pub radial: Array2<f64>,
+    pub axial: Array2<f64>,
+    pub hoop: Array2<f64>,
+    pub shear: Array2<f64>,
+    pub von_mises: Array2<f64>,
+    pub radial_displacement: Array2<f64>,
+    pub axial_displacement: Array2<f64>,
+    /// Conjugate gradient iterations used
+    pub iterations: usize,
+    /// Whether the solve reached the tolerance
+    pub converged: bool,
+}
+
+impl StressField {
+    /// Peak stresses of the field
+    pub fn summary(&self, mesh: &CylindricalMesh, time: f64) -> StressSummary {
+        let ((i, j), &max_von_mises) = self
+            .von_mises
+            .indexed_iter()
+            .fold(((0, 0), &f64::NEG_INFINITY), |best, item| if item.1 > best.1 { item } else { best });
+
+        let max = |field: &Array2<f64>| field.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+
+        StressSummary {
+            time,
+            max_von_mises,
+            max_von_mises_position: (mesh.r_coords[i], mesh.z_coords[j]),
+            max_hoop_stress: max(&self.hoop),
+            max_axial_stress: max(&self.axial),
+        }
+    }
+}
+
+/// Peak stresses at one point in time
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StressSummary {
+    pub time: f64,
+    /// Largest von Mises stress in Pa
+    pub max_von_mises: f64,
+    /// Position (r, z) of the largest von Mises stress in meters
+    pub max_von_mises_position: (f64, f64),
+    /// Largest hoop stress in Pa (positive in tension)
+    pub max_hoop_stress: f64,
+    /// Largest axial stress in Pa (positive in tension)
+    pub max_axial_stress: f64,
+}
+
+/// Axisymmetric thermoelastic analysis on a cylindrical mesh
+#[derive(Debug, Clone)]
+pub struct ThermalStressAnalysis {
+    pub config: ThermalStressConfig,
+    pub mesh: CylindricalMesh,
+    /// Elastic constants of each region, the base material first
+    pub elastic: Vec<ElasticProperties>,
+    /// Stress-free temperature in K
+    pub reference_temperature: f64,
+    /// Region index of each element
+    element_region: Array2<usize>,
+    /// Element stiffness matrices per region, one per radial element column
+    element_stiffness: Vec<Vec<[[f64; 8]; 8]>>,
+    /// Degrees of freedom held at zero displacement
+    fixed: Vec<bool>,
+    /// Diagonal of the global stiffness matrix (Jacobi preconditioner)
+    diagonal: Vec<f64>,
+}
+
+impl ThermalStressAnalysis {
+    /// Set up the analysis for a mesh and material
+    ///
+    /// `initial_temperature` is the stress-free temperature unless the
+    /// configuration overrides it.
+    pub fn new(
+        config: ThermalStressConfig,
+        mesh: CylindricalMesh,
+        material: &Material,
+        initial_temperature: f64,
+    ) -> Result<Self> {
+        let element_region = Array2::zeros((mesh.nr - 1, mesh.nz - 1));
+        Self::build(config, mesh, std::slice::from_ref(material), element_region, initial_temperature)
+    }
+
+    /// Set up the analysis for the materials of a region map
+    ///
+    /// Each element takes the material of the region containing its centroid.
+    pub fn with_regions(
+        config: ThermalStressConfig,
+        mesh: CylindricalMesh,
+        regions: &RegionMap,
+        initial_temperature: f64,
+    ) -> Result<Self> {
+        let element_region = Array2::from_shape_fn((mesh.nr - 1, mesh.nz - 1), |(i, j)| {
+            regions.region_at(mesh.r_coords[i] + 0.5 * mesh.dr, mesh.z_coords[j] + 0.5 * mesh.dz)
+        });
+        Self::build(config, mesh, &regions.materials, element_region, initial_temperature)
+    }
+
+    fn build(
+        config: ThermalStressConfig,
+        mesh: CylindricalMesh,
+        materials: &[Material],
+        element_region: Array2<usize>,
+        initial_temperature: f64,
+    ) -> Result<Self> {
+        config.validate()?;
+
+        let elastic = materials
+            .iter()
+            .map(|material| {
+                let elastic = material.elastic.ok_or_else(|| SimulationError::MaterialError {
+                    material: material.name.clone(),
+                    property: "elastic".to_string(),
+                    details: "Elastic properties not defined".to_string(),
+                })?;
+                elastic.validate()?;
+                Ok(elastic)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let reference_temperature = config.reference_temperature.unwrap_or(initial_temperature);
+
+        // Element stiffness depends on the radius only, so one matrix per column and region
+        let element_stiffness: Vec<Vec<[[f64; 8]; 8]>> = elastic
+            .iter()
+            .map(|elastic| {
+                let d = elasticity_matrix(elastic);
+                (0..mesh.nr - 1)
+                    .map(|i| {
+                        let mut ke = [[0.0; 8]; 8];
+                        for (xi, eta) in gauss_points() {
+                            let (b, weight) = strain_displacement(&mesh, i, xi, eta);
+                            let db = mat_mul_4x8(&d, &b);
+                            for a in 0..8 {
+                                for c in 0..8 {
+                                    ke[a][c] += weight * (0..4).map(|k| b[k][a] * db[k][c]).sum::<f64>();
+                                }
+                            }
+                        }
+                        ke
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // Radial displacement fixed on the axis, axial displacement on the bottom
+        let dofs = 2 * mesh.nr * mesh.nz;
+        let mut fixed = vec![false; dofs];
+        for j in 0..mesh.nz {
+            fixed[2 * node_index(&mesh, 0, j)] = true;
+        }
+        for i in 0..mesh.nr {
+            fixed[2 * node_index(&mesh, i, 0) + 1] = true;
+        }
+
+        let mut diagonal = vec![0.0; dofs];
+        for ((i, j), &region) in element_region.indexed_iter() {
+            let ke = &element_stiffness[region][i];
+            for (a, &dof) in element_dofs(&mesh, i, j).iter().enumerate() {
+                diagonal[dof] += ke[a][a];
+            }
+        }
+        for (value, &is_fixed) in diagonal.iter_mut().zip(&fixed) {
+            if is_fixed {
+                *value = 1.0;
+            }
+        }
+
+        Ok(Self {
+            config,
+            mesh,
+            elastic,
+            reference_temperature,
+            element_region,
+            element_stiffness,
+            fixed,
+            diagonal,
+        })
+    }
+
+    /// Set up the analysis for the mesh and material regions of a completed simulation
+    pub fn for_results(config: ThermalStressConfig, results: &super::SimulationResults) -> Result<Self> {
+        let (nr, nz) = results.config.mesh.resolution();
+        let mesh = CylindricalMesh::new(results.config.geometry.radius, results.config.geometry.height, nr, nz)?;
+        let material_config = &results.config.material;
+        let base = super::materials::MaterialLibrary::get_material(&material_config.material_name)?;
+        let regions = RegionMap::new(&base, &material_config.regions, &material_config.interfaces, &mesh)?;
+        Self::with_regions(config, mesh, &regions, results.config.physics.initial_temperature)
+    }
+
+    /// Solve for the stresses caused by a temperature field
+    pub fn solve(&self, temperature: &Array2<f64>) -> Result<StressField> {
+        let (nr, nz) = (self.mesh.nr, self.mesh.nz);
+        if temperature.dim() != (nr, nz) {
+            return Err(SimulationError::PhysicsError {
+                operation: "thermal stress analysis".to_string(),
+                details: format!("Temperature field is {:?}, mesh is {}x{}", temperature.dim(), nr, nz),
+            });
+        }
+
+        let load = self.thermal_load(temperature);
+        let mut displacement = vec![0.0; load.len()];
+        let (iterations, converged) = self.conjugate_gradient(&load, &mut displacement);
+
+        if !converged {
+            log::warn!("Thermal stress solve did not converge in {} iterations", iterations);
+        }
+
+        Ok(self.recover_stresses(temperature, &displacement, iterations, converged))
+    }
+
+    /// Solve for a temperature grid as stored in `SimulationResults` or `TimeStepData`
+    pub fn solve_grid(&self, grid: &[Vec<f64>]) -> Result<StressField> {
+        let nz = grid.first().map_or(0, |row| row.len());
+        let values: Vec<f64> = grid.iter().flatten().cloned().collect();
+        let temperature = Array2::from_shape_vec((grid.len(), nz), values).map_err(|e| SimulationError::PhysicsError {
+            operation: "thermal stress analysis".to_string(),
+            details: format!("Irregular temperature grid: {}", e),
+        })?;
+        self.solve(&temperature)
+    }
+
+    /// Peak stresses over a stored time series, for screening heat-up ramps
+    pub fn screen_time_series(&self, time_series: &[super::TimeStepData]) -> Result<Vec<StressSummary>> {
+        time_series
+            .iter()
+            .map(|step| Ok(self.solve_grid(&step.temperature_grid)?.summary(&self.mesh, step.time)))
+            .collect()
+    }
+
+    /// Equivalent nodal forces of the thermal strain
+    fn thermal_load(&self, temperature: &Array2<f64>) -> Vec<f64> {
+        let d: Vec<_> = self.elastic.iter().map(elasticity_matrix).collect();
+        let mut load = vec![0.0; 2 * self.mesh.nr * self.mesh.nz];
+
+        for ((i, j), &region) in self.element_region.indexed_iter() {
+            let alpha = self.elastic[region].thermal_expansion;
+            let nodal_t = element_temperatures(temperature, i, j);
+            let dofs = element_dofs(&self.mesh, i, j);
+
+            for (xi, eta) in gauss_points() {
+                let t = interpolate(&nodal_t, xi, eta);
+                let strain = alpha * (t - self.reference_temperature);
+                let stress = thermal_stress(&d[region], strain);
+                let (b, weight) = strain_displacement(&self.mesh, i, xi, eta);
+
+                for (a, &dof) in dofs.iter().enumerate() {
+                    load[dof] += weight * (0..4).map(|k| b[k][a] * stress[k]).sum::<f64>();
+                }
+            }
+        }
+
+        for (value, &is_fixed) in load.iter_mut().zip(&self.fixed) {
+            if is_fixed {
+                *value = 0.0;
+            }
+        }
+        load
+    }
+
+    /// Global stiffness product, with identity rows for the fixed degrees of freedom
+    fn apply_stiffness(&self, u: &[f64], out: &mut [f64]) {
+        out.fill(0.0);
+
+        for ((i, j), &region) in self.element_region.indexed_iter() {
+            let ke = &self.element_stiffness[region][i];
+            let dofs = element_dofs(&self.mesh, i, j);
+            let local: Vec<f64> = dofs.iter().map(|&dof| if self.fixed[dof] { 0.0 } else { u[dof] }).collect();
+
+            for (a, &dof) in dofs.iter().enumerate() {
+                if !self.fixed[dof] {
+                    out[dof] += (0..8).map(|c| ke[a][c] * local[c]).sum::<f64>();
+                }
+            }
+        }
+
+        for (dof, &is_fixed) in self.fixed.iter().enumerate() {
+            if is_fixed {
+                out[dof] = u[dof];
+            }
+        }
+    }
+
+    /// Jacobi-preconditioned conjugate gradient solve, returning (iterations, converged)
+    fn conjugate_gradient(&self, b: &[f64], x: &mut [f64]) -> (usize, bool) {
+        let dot = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(x, y)| x * y).sum::<f64>();
+
+        let b_norm = dot(b, b).sqrt();
+        if b_norm == 0.0 {
+            x.fill(0.0);
+            return (0, true);
+        }
+
+        let n = b.len();
+        let mut r = vec![0.0; n];
+        self.apply_stiffness(x, &mut r);
+        for (r, b) in r.iter_mut().zip(b) {
+            *r = b - *r;
+        }
+        let mut z: Vec<f64> = r.iter().zip(&self.diagonal).map(|(r, d)| r / d).collect();
+        let mut p = z.clone();
+        let mut kp = vec![0.0; n];
+        let mut rz = dot(&r, &z);
+
+        for iteration in 1..=self.config.max_iterations {
+            self.apply_stiffness(&p, &mut kp);
+            let step = rz / dot(&p, &kp);
+
+            for k in 0..n {
+                x[k] += step * p[k];
+                r[k] -= step * kp[k];
+            }
+
+            if dot(&r, &r).sqrt() <= self.config.tolerance * b_norm {
+                return (iteration, true);
+            }
+
+            for k in 0..n {
+                z[k] = r[k] / self.diagonal[k];
+            }
+            let rz_new = dot(&r, &z);
+            let beta = rz_new / rz;
+            rz = rz_new;
+            for k in 0..n {
+                p[k] = z[k] + beta * p[k];
+            }
+        }
+
+        (self.config.max_iterations, false)
+    }
+
+    /// Evaluate stresses at element centroids and average them to the nodes
+    fn recover_stresses(&self, temperature: &Array2<f64>, u: &[f64], iterations: usize, converged: bool) -> StressField {
+        let (nr, nz) = (self.mesh.nr, self.mesh.nz);
+        let d: Vec<_> = self.elastic.iter().map(elasticity_matrix).collect();
+
+        // Accumulated radial, axial, hoop and shear stress with element counts
+        let mut sums = vec![Array2::<f64>::zeros((nr, nz)); 4];
+        let mut counts = Array2::<f64>::zeros((nr, nz));
+
+        for ((i, j), &region) in self.element_region.indexed_iter() {
+            let dofs = element_dofs(&self.mesh, i, j);
+            let (b, _) = strain_displacement(&self.mesh, i, 0.0, 0.0);
+            let thermal = self.elastic[region].thermal_expansion
+                * (interpolate(&element_temperatures(temperature, i, j), 0.0, 0.0) - self.reference_temperature);
+
+            let mut strain = [0.0; 4];
+            for (k, value) in strain.iter_mut().enumerate() {
+                *value = (0..8).map(|a| b[k][a] * u[dofs[a]]).sum::<f64>();
+                if k < 3 {
+                    *value -= thermal;
+                }
+            }
+
+            for (k, sum) in sums.iter_mut().enumerate() {
+                let stress: f64 = (0..4).map(|m| d[region][k][m] * strain[m]).sum();
+                for (ni, nj) in element_nodes(i, j) {
+                    sum[[ni, nj]] += stress;
+                }
+            }
+            for (ni, nj) in element_nodes(i, j) {
+                counts[[ni, nj]] += 1.0;
+            }
+        }
+
+        let [radial, axial, hoop, shear]: [Array2<f64>; 4] = sums
+            .into_iter()
+            .map(|sum| sum / &counts)
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+
+        let mut von_mises = Array2::zeros((nr, nz));
+        for ((i, j), value) in von_mises.indexed_iter_mut() {
+            let (sr, sz, st, tau) = (radial[[i, j]], axial[[i, j]], hoop[[i, j]], shear[[i, j]]);
+            *value = (0.5 * ((sr - sz).powi(2) + (sz - st).powi(2) + (st - sr).powi(2)) + 3.0 * tau * tau).sqrt();
+        }
+
+        let mut radial_displacement = Array2::zeros((nr, nz));
+        let mut axial_displacement = Array2::zeros((nr, nz));
+        for i in 0..nr {
+            for j in 0..nz {
+                let node = node_index(&self.mesh, i, j);
+                radial_displacement[[i, j]] = u[2 * node];
+                axial_displacement[[i, j]] = u[2 * node + 1];
+            }
+        }
+
+        StressField {
+            radial,
+            axial,
+            hoop,
+            shear,
+            von_mises,
+            radial_displacement,
+            axial_displacement,
+            iterations,
+            converged,
+        }
+    }
+}
+
+/// Isotropic elasticity matrix for the strain ordering (rr, zz, θθ, rz)
+fn elasticity_matrix(elastic: &ElasticProperties) -> [[f64; 4]; 4] {
+    let (e, nu) = (elastic.youngs_modulus, elastic.poisson_ratio);
+    let c = e / ((1.0 + nu) * (1.0 - 2.0 * nu));
+    [
+        [c * (1.0 - nu), c * nu, c * nu, 0.0],
+        [c * nu, c * (1.0 - nu), c * nu, 0.0],
+        [c * nu, c * nu, c * (1.0 - nu), 0.0],
+        [0.0, 0.0, 0.0, c * (1.0 - 2.0 * nu) / 2.0],
+    ]
+}
+
+/// Stress of a free thermal strain, D · ε_th
+fn thermal_stress(d: &[[f64; 4]; 4], strain: f64) -> [f64; 4] {
+    let mut stress = [0.0; 4];
+    for (k, value) in stress.iter_mut().enumerate() {
+        *value = strain * (d[k][0] + d[k][1] + d[k][2]);
+    }
+    stress
+}
+
+fn gauss_points() -> [(f64, f64); 4] {
+    [
+        (-GAUSS_POINT, -GAUSS_POINT),
+        (GAUSS_POINT, -GAUSS_POINT),
+        (GAUSS_POINT, GAUSS_POINT),
+        (-GAUSS_POINT, GAUSS_POINT),
+    ]
+}
+
+/// Strain-displacement matrix at a point of an element in column `i`,
+/// with the integration weight 2π r |J|
+fn strain_displacement(mesh: &CylindricalMesh, i: usize, xi: f64, eta: f64) -> ([[f64; 8]; 4], f64) {
+    let r = mesh.r_coords[i] + 0.5 * (1.0 + xi) * mesh.dr;
+    let mut b = [[0.0; 8]; 4];
+
+    for a in 0..4 {
+        let shape = 0.25 * (1.0 + NODE_XI[a] * xi) * (1.0 + NODE_ETA[a] * eta);
+        let dn_dr = 0.25 * NODE_XI[a] * (1.0 + NODE_ETA[a] * eta) * 2.0 / mesh.dr;
+        let dn_dz = 0.25 * NODE_ETA[a] * (1.0 + NODE_XI[a] * xi) * 2.0 / mesh.dz;
+
+        b[0][2 * a] = dn_dr;
+        b[1][2 * a + 1] = dn_dz;
+        b[2][2 * a] = shape / r;
+        b[3][2 * a] = dn_dz;
+        b[3][2 * a + 1] = dn_dr;
+    }
+
+    (b, 2.0 * PI * r * 0.25 * mesh.dr * mesh.dz)
+}
+
+fn mat_mul_4x8(d: &[[f64; 4]; 4], b: &[[f64; 8]; 4]) -> [[f64; 8]; 4] {
+    let mut result = [[0.0; 8]; 4];
+    for k in 0..4 {
+        for c in 0..8 {
+            result[k][c] = (0..4).map(|m| d[k][m] * b[m][c]).sum();
+        }
+    }
+    result
+}
+
+fn node_index(mesh: &CylindricalMesh, i: usize, j: usize) -> usize {
+    i * mesh.nz + j
+}
+
+fn element_nodes(i: usize, j: usize) -> [(usize, usize); 4] {
+    [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)]
+}
+
+fn element_dofs(mesh: &CylindricalMesh, i: usize, j: usize) -> [usize; 8] {
+    let mut dofs = [0; 8];
+    for (a, (ni, nj)) in element_nodes(i, j).into_iter().enumerate() {
+        let node = node_index(mesh, ni, nj);
+        dofs[2 * a] = 2 * node;
+        dofs[2 * a + 1] = 2 * node + 1;
+    }
+    dofs
+}
+
+fn element_temperatures(temperature: &Array2<f64>, i: usize, j: usize) -> [f64; 4] {
+    element_nodes(i, j).map(|node| temperature[node])
+}
+
+fn interpolate(nodal: &[f64; 4], xi: f64, eta: f64) -> f64 {
+    (0..4)
+        .map(|a| 0.25 * (1.0 + NODE_XI[a] * xi) * (1.0 + NODE_ETA[a] * eta) * nodal[a])
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::materials::MaterialLibrary;
+
+    fn analysis(material: &str) -> Result<ThermalStressAnalysis> {
+        let mesh = CylindricalMesh::new(0.5, 1.0, 11, 11).unwrap();
+        let material = MaterialLibrary::get_material(material).unwrap();
+        ThermalStressAnalysis::new(ThermalStressConfig::default(), mesh, &material, 300.0)
+    }
+
+    #[test]
+    fn test_uniform_heating_is_stress_free() {
+        let analysis = analysis("Ceramic").unwrap();
+        let temperature = analysis.mesh.create_temperature_array(800.0);
+        let field = analysis.solve(&temperature).unwrap();
+        assert!(field.converged);
+
+        // Free expansion: u_r = α ΔT r, u_z = α ΔT z and no stress
+        let strain = analysis.elastic[0].thermal_expansion * 500.0;
+        let (nr, nz) = (analysis.mesh.nr, analysis.mesh.nz);
+        assert!((field.radial_displacement[[nr - 1, 5]] - strain * 0.5).abs() < 1e-9);
+        assert!((field.axial_displacement[[5, nz - 1]] - strain * 1.0).abs() < 1e-9);
+
+        let scale = analysis.elastic[0].youngs_modulus * strain;
+        assert!(field.von_mises.iter().all(|s| s.abs() < 1e-6 * scale));
+    }
+
+    #[test]
+    fn test_hot_core_puts_surface_in_hoop_tension() {
+        let analysis = analysis("Ceramic").unwrap();
+        let mut temperature = analysis.mesh.create_temperature_array(300.0);
+        for ((i, _), t) in temperature.indexed_iter_mut() {
+            let r = analysis.mesh.r_coords[i];
+            *t = 1300.0 - 4000.0 * r * r;
+        }
+
+        let field = analysis.solve(&temperature).unwrap();
+        let nr = analysis.mesh.nr;
+
+        // Hot core is held in compression by the cooler outer shell
+        assert!(field.hoop[[0, 5]] < 0.0);
+        assert!(field.hoop[[nr - 1, 5]] > 0.0);
+
+        let summary = field.summary(&analysis.mesh, 1.0);
+        assert!(summary.max_hoop_stress > 0.0);
+        assert!(summary.max_von_mises > 0.0);
+    }
+
+    #[test]
+    fn test_regions_use_their_own_elastic_constants() {
+        use crate::simulation::regions::MaterialRegion;
+
+        let mesh = CylindricalMesh::new(0.5, 1.0, 11, 11).unwrap();
+        let ceramic = MaterialLibrary::get_material("Ceramic").unwrap();
+        let shell = |r_min: f64| MaterialRegion {
+            name: "shell".to_string(),
+            material_name: "Carbon Steel".to_string(),
+            r_range: (r_min, 0.5),
+            z_range: (0.0, 1.0),
+        };
+        let analysis = |r_min: f64| {
+            let regions = RegionMap::new(&ceramic, &[shell(r_min)], &[], &mesh).unwrap();
+            ThermalStressAnalysis::with_regions(ThermalStressConfig::default(), mesh.clone(), &regions, 300.0).unwrap()
+        };
+
+        // A region over the whole charge expands freely with its own α
+        let steel = analysis(0.0);
+        let temperature = mesh.create_temperature_array(800.0);
+        let field = steel.solve(&temperature).unwrap();
+        let strain = steel.elastic[1].thermal_expansion * 500.0;
+        assert!((field.radial_displacement[[10, 5]] - strain * 0.5).abs() < 1e-9);
+
+        // Mismatched expansion of a steel shell on a ceramic core is no longer stress free
+        let lined = analysis(0.4);
+        let field = lined.solve(&temperature).unwrap();
+        assert!(field.converged);
+        assert!(field.von_mises[[10, 5]] > 1e-3 * lined.elastic[1].youngs_modulus * strain);
+
+        let wood = MaterialRegion { material_name: "Wood".to_string(), ..shell(0.4) };
+        let regions = RegionMap::new(&ceramic, &[wood], &[], &mesh).unwrap();
+        assert!(ThermalStressAnalysis::with_regions(ThermalStressConfig::default(), mesh, &regions, 300.0).is_err());
+    }
+
+    #[test]
+    fn test_requires_elastic_properties() {
+        assert!(analysis("Wood").is_err());
+
+        let analysis = analysis("Carbon Steel").unwrap();
+        assert!(analysis.solve_grid(&vec![vec![300.0; 5]; 5]).is_err());
+    }
+}
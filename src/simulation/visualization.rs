@@ -180,6 +180,15 @@ mod tests {
             min_temperature: 300.0,
             avg_temperature: 350.0,
             time_series_data: None,
+            joule_heating_power: None,
+            coolant_circuits: Vec::new(),
+            interface_reports: Vec::new(),
+            reaction_history: Default::default(),
+            drying_history: Default::default(),
+            probe_histories: Vec::new(),
+            controller_history: Default::default(),
+            energy_ledger: Default::default(),
+            property_warnings: Vec::new(),
         };
         let mesh = crate::simulation::CylindricalMesh::new(1.0, 2.0, 10, 20).unwrap();
         let data = manager.prepare_3d_data(&results, &mesh).unwrap();
@@ -0,0 +1,596 @@
+//! Physical units for configuration quantities and formula results
+//!
+//! Quantities are written as a number followed by a unit, e.g. `"25 degC"`,
+//! `"150 kW"`, `"2 m"` or `"15 W/(m^2*K)"`, and converted to the unit a
+//! field is stored in. Dimensions are checked on conversion, so a power
+//! given where a temperature is expected is rejected instead of silently
+//! misread.
+//!
+//! # Unit syntax
+//!
+//! Units are products of known symbols (see [`UNITS`]) joined by `*`, `·`
+//! or spaces, with integer exponents written `m^2`, `m2` or `m²`. Everything
+//! after a `/` is in the denominator, so `W/m·K` and `W/(m·K)` are the same
+//! unit. Celsius and Fahrenheit (`degC`, `°C`, `degF`, `°F`) convert with
+//! their offset when they stand alone and as temperature differences inside
+//! compound units such as `J/(kg·degC)`.
+//!
+//! # Formula annotations
+//!
+//! A formula may end with its result unit in square brackets, e.g.
+//! `"0.5 + 1.2e-3 * TC [W/(m*K)]"`. The formula engine converts the result
+//! to SI and [`check_formula_dimension`] compares the annotation with the
+//! quantity the formula defines. Unannotated formulas are taken to be in SI.
+
+use crate::errors::{Result, SimulationError};
+
+/// Exponents of mass, length, time, temperature and electric current
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Dimension {
+    pub mass: i8,
+    pub length: i8,
+    pub time: i8,
+    pub temperature: i8,
+    pub current: i8,
+}
+
+impl Dimension {
+    const fn new(mass: i8, length: i8, time: i8, temperature: i8, current: i8) -> Self {
+        Self { mass, length, time, temperature, current }
+    }
+
+    pub const DIMENSIONLESS: Self = Self::new(0, 0, 0, 0, 0);
+    pub const MASS: Self = Self::new(1, 0, 0, 0, 0);
+    pub const LENGTH: Self = Self::new(0, 1, 0, 0, 0);
+    pub const TIME: Self = Self::new(0, 0, 1, 0, 0);
+    pub const TEMPERATURE: Self = Self::new(0, 0, 0, 1, 0);
+    pub const CURRENT: Self = Self::new(0, 0, 0, 0, 1);
+    /// J = kg·m²/s²
+    pub const ENERGY: Self = Self::new(1, 2, -2, 0, 0);
+    /// W = kg·m²/s³
+    pub const POWER: Self = Self::new(1, 2, -3, 0, 0);
+    /// kg/m³
+    pub const DENSITY: Self = Self::new(1, -3, 0, 0, 0);
+    /// W/(m·K)
+    pub const THERMAL_CONDUCTIVITY: Self = Self::new(1, 1, -3, -1, 0);
+    /// J/(kg·K)
+    pub const SPECIFIC_HEAT: Self = Self::new(0, 2, -2, -1, 0);
+    /// J/kg
+    pub const SPECIFIC_ENERGY: Self = Self::new(0, 2, -2, 0, 0);
+    /// W/m²
+    pub const HEAT_FLUX: Self = Self::new(1, 0, -3, 0, 0);
+    /// W/m³
+    pub const HEAT_SOURCE: Self = Self::new(1, -1, -3, 0, 0);
+    /// W/(m²·K)
+    pub const HEAT_TRANSFER_COEFFICIENT: Self = Self::new(1, 0, -3, -1, 0);
+    /// S/m = A²·s³/(kg·m³)
+    pub const ELECTRICAL_CONDUCTIVITY: Self = Self::new(-1, -3, 3, 0, 2);
+
+    /// Dimension raised to a power, `None` if an exponent overflows
+    fn scaled(self, power: i8) -> Option<Self> {
+        Some(Self::new(
+            self.mass.checked_mul(power)?,
+            self.length.checked_mul(power)?,
+            self.time.checked_mul(power)?,
+            self.temperature.checked_mul(power)?,
+            self.current.checked_mul(power)?,
+        ))
+    }
+
+    /// Product of two dimensions, `None` if an exponent overflows
+    fn combine(self, other: Self) -> Option<Self> {
+        Some(Self::new(
+            self.mass.checked_add(other.mass)?,
+            self.length.checked_add(other.length)?,
+            self.time.checked_add(other.time)?,
+            self.temperature.checked_add(other.temperature)?,
+            self.current.checked_add(other.current)?,
+        ))
+    }
+}
+
+impl std::fmt::Display for Dimension {
+    /// SI base units, e.g. `kg·m^2·s^-3`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let factors: Vec<String> = [
+            ("kg", self.mass),
+            ("m", self.length),
+            ("s", self.time),
+            ("K", self.temperature),
+            ("A", self.current),
+        ]
+        .iter()
+        .filter(|(_, power)| *power != 0)
+        .map(|(symbol, power)| if *power == 1 { symbol.to_string() } else { format!("{}^{}", symbol, power) })
+        .collect();
+        if factors.is_empty() {
+            write!(f, "1")
+        } else {
+            write!(f, "{}", factors.join("·"))
+        }
+    }
+}
+
+/// Known unit symbol: SI value = value * factor + offset
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitSymbol {
+    pub symbol: &'static str,
+    pub factor: f64,
+    /// Offset of affine temperature scales, applied only to a lone unit
+    pub offset: f64,
+    pub dimension: Dimension,
+}
+
+const fn unit(symbol: &'static str, factor: f64, dimension: Dimension) -> UnitSymbol {
+    UnitSymbol { symbol, factor, offset: 0.0, dimension }
+}
+
+/// Unit symbols understood by the parser
+pub const UNITS: &[UnitSymbol] = &[
+    unit("1", 1.0, Dimension::DIMENSIONLESS),
+    unit("%", 0.01, Dimension::DIMENSIONLESS),
+    unit("m", 1.0, Dimension::LENGTH),
+    unit("km", 1.0e3, Dimension::LENGTH),
+    unit("cm", 1.0e-2, Dimension::LENGTH),
+    unit("mm", 1.0e-3, Dimension::LENGTH),
+    unit("um", 1.0e-6, Dimension::LENGTH),
+    unit("µm", 1.0e-6, Dimension::LENGTH),
+    unit("in", 0.0254, Dimension::LENGTH),
+    unit("ft", 0.3048, Dimension::LENGTH),
+    unit("kg", 1.0, Dimension::MASS),
+    unit("g", 1.0e-3, Dimension::MASS),
+    unit("t", 1.0e3, Dimension::MASS),
+    unit("lb", 0.45359237, Dimension::MASS),
+    unit("s", 1.0, Dimension::TIME),
+    unit("ms", 1.0e-3, Dimension::TIME),
+    unit("min", 60.0, Dimension::TIME),
+    unit("h", 3600.0, Dimension::TIME),
+    unit("K", 1.0, Dimension::TEMPERATURE),
+    UnitSymbol { symbol: "degC", factor: 1.0, offset: 273.15, dimension: Dimension::TEMPERATURE },
+    UnitSymbol { symbol: "°C", factor: 1.0, offset: 273.15, dimension: Dimension::TEMPERATURE },
+    UnitSymbol { symbol: "degF", factor: 5.0 / 9.0, offset: 273.15 - 32.0 * 5.0 / 9.0, dimension: Dimension::TEMPERATURE },
+    UnitSymbol { symbol: "°F", factor: 5.0 / 9.0, offset: 273.15 - 32.0 * 5.0 / 9.0, dimension: Dimension::TEMPERATURE },
+    unit("J", 1.0, Dimension::ENERGY),
+    unit("kJ", 1.0e3, Dimension::ENERGY),
+    unit("MJ", 1.0e6, Dimension::ENERGY),
+    unit("kWh", 3.6e6, Dimension::ENERGY),
+    unit("cal", 4.184, Dimension::ENERGY),
+    unit("kcal", 4184.0, Dimension::ENERGY),
+    unit("W", 1.0, Dimension::POWER),
+    unit("mW", 1.0e-3, Dimension::POWER),
+    unit("kW", 1.0e3, Dimension::POWER),
+    unit("MW", 1.0e6, Dimension::POWER),
+    unit("A", 1.0, Dimension::CURRENT),
+    unit("kA", 1.0e3, Dimension::CURRENT),
+    unit("V", 1.0, Dimension::new(1, 2, -3, 0, -1)),
+    unit("kV", 1.0e3, Dimension::new(1, 2, -3, 0, -1)),
+    unit("S", 1.0, Dimension::new(-1, -2, 3, 0, 2)),
+    unit("Ohm", 1.0, Dimension::new(1, 2, -3, 0, -2)),
+    unit("Ω", 1.0, Dimension::new(1, 2, -3, 0, -2)),
+    unit("Pa", 1.0, Dimension::new(1, -1, -2, 0, 0)),
+    unit("kPa", 1.0e3, Dimension::new(1, -1, -2, 0, 0)),
+    unit("MPa", 1.0e6, Dimension::new(1, -1, -2, 0, 0)),
+    unit("GPa", 1.0e9, Dimension::new(1, -1, -2, 0, 0)),
+    unit("bar", 1.0e5, Dimension::new(1, -1, -2, 0, 0)),
+];
+
+/// Parsed unit: SI value = value * factor + offset
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Unit {
+    pub factor: f64,
+    pub offset: f64,
+    pub dimension: Dimension,
+}
+
+impl Unit {
+    /// Parse a unit expression such as `kW`, `W/(m^2*K)` or `degC`
+    pub fn parse(text: &str) -> Result<Self> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(unit_error(text, "empty unit"));
+        }
+        if let Some(symbol) = UNITS.iter().find(|symbol| symbol.symbol == text) {
+            return Ok(Self { factor: symbol.factor, offset: symbol.offset, dimension: symbol.dimension });
+        }
+
+        let mut parser = UnitParser { text, chars: text.char_indices().peekable() };
+        let (factor, dimension) = parser.product()?;
+        if let Some((_, c)) = parser.chars.next() {
+            return Err(unit_error(text, &format!("unexpected '{}'", c)));
+        }
+        Ok(Self { factor, offset: 0.0, dimension })
+    }
+
+    /// Convert a value in this unit to SI
+    pub fn to_si(&self, value: f64) -> f64 {
+        value * self.factor + self.offset
+    }
+
+    /// Convert an SI value to this unit
+    pub fn from_si(&self, value: f64) -> f64 {
+        (value - self.offset) / self.factor
+    }
+}
+
+/// Recursive-descent parser for compound units
+struct UnitParser<'a> {
+    text: &'a str,
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+}
+
+impl UnitParser<'_> {
+    /// Factors up to a closing parenthesis or the end; factors after `/` divide
+    fn product(&mut self) -> Result<(f64, Dimension)> {
+        let (mut factor, mut dimension) = (1.0, Dimension::DIMENSIONLESS);
+        let mut denominator = false;
+        let mut expect_factor = true;
+        while let Some(&(_, c)) = self.chars.peek() {
+            match c {
+                ')' => break,
+                '*' | '·' | '.' | ' ' => {
+                    self.chars.next();
+                }
+                '/' => {
+                    self.chars.next();
+                    denominator = true;
+                    expect_factor = true;
+                    continue;
+                }
+                _ => {
+                    let (f, d) = self.power()?;
+                    let sign = if denominator { -1 } else { 1 };
+                    factor *= f.powi(sign as i32);
+                    dimension = d
+                        .scaled(sign)
+                        .and_then(|d| dimension.combine(d))
+                        .ok_or_else(|| unit_error(self.text, "exponent out of range"))?;
+                    expect_factor = false;
+                    continue;
+                }
+            }
+        }
+        if expect_factor {
+            return Err(unit_error(self.text, "missing unit after operator"));
+        }
+        Ok((factor, dimension))
+    }
+
+    /// Symbol or parenthesized product with an optional integer exponent
+    fn power(&mut self) -> Result<(f64, Dimension)> {
+        let (factor, dimension) = if self.chars.peek().map(|&(_, c)| c) == Some('(') {
+            self.chars.next();
+            let inner = self.product()?;
+            if self.chars.next().map(|(_, c)| c) != Some(')') {
+                return Err(unit_error(self.text, "unbalanced parenthesis"));
+            }
+            inner
+        } else {
+            let start = self.chars.peek().map_or(self.text.len(), |&(i, _)| i);
+            let mut end = start;
+            while let Some(&(i, c)) = self.chars.peek() {
+                if c.is_alphabetic() || c == '°' || c == '%' || c == 'µ' || c == 'Ω' {
+                    end = i + c.len_utf8();
+                    self.chars.next();
+                } else {
+                    break;
+                }
+            }
+            let symbol = &self.text[start..end];
+            let unit = UNITS
+                .iter()
+                .find(|unit| unit.symbol == symbol && !symbol.is_empty())
+                .ok_or_else(|| unit_error(self.text, &format!("unknown unit '{}'", symbol)))?;
+            // Affine scales count as temperature differences inside compound units
+            (unit.factor, unit.dimension)
+        };
+
+        let exponent = self.exponent()?;
+        let dimension = dimension.scaled(exponent).ok_or_else(|| unit_error(self.text, "exponent out of range"))?;
+        Ok((factor.powi(exponent as i32), dimension))
+    }
+
+    /// Exponent written `^n`, `n` or with superscript digits; 1 if absent
+    fn exponent(&mut self) -> Result<i8> {
+        if self.chars.peek().map(|&(_, c)| c) == Some('^') {
+            self.chars.next();
+        }
+        let mut digits = String::new();
+        while let Some(&(_, c)) = self.chars.peek() {
+            let digit = match c {
+                '0'..='9' | '-' => c,
+                '⁻' => '-',
+                '⁰' => '0',
+                '¹' => '1',
+                '²' => '2',
+                '³' => '3',
+                '⁴' => '4',
+                _ => break,
+            };
+            digits.push(digit);
+            self.chars.next();
+        }
+        if digits.is_empty() {
+            return Ok(1);
+        }
+        digits.parse().map_err(|_| unit_error(self.text, &format!("invalid exponent '{}'", digits)))
+    }
+}
+
+fn unit_error(text: &str, issue: &str) -> SimulationError {
+    SimulationError::InvalidParameter {
+        parameter: "unit".to_string(),
+        value: text.to_string(),
+        range: issue.to_string(),
+    }
+}
+
+/// Value in SI units with its dimension
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity {
+    pub value: f64,
+    pub dimension: Dimension,
+}
+
+impl Quantity {
+    /// Parse a number followed by a unit, e.g. `"25 degC"` or `"1.5e3 W/m^2"`
+    ///
+    /// A bare number is dimensionless.
+    pub fn parse(text: &str) -> Result<Self> {
+        let text = text.trim();
+        let (number, unit) = split_number(text)
+            .ok_or_else(|| quantity_error(text, "a number followed by a unit".to_string()))?;
+        let value: f64 = number.parse().map_err(|_| quantity_error(text, "a number followed by a unit".to_string()))?;
+        let unit = if unit.trim().is_empty() { Unit::parse("1")? } else { Unit::parse(unit)? };
+        let value = finite(text, unit.to_si(value))?;
+        Ok(Self { value, dimension: unit.dimension })
+    }
+
+    /// Value in a unit of the same dimension
+    pub fn in_unit(&self, unit: &str) -> Result<f64> {
+        let target = Unit::parse(unit)?;
+        if target.dimension != self.dimension {
+            return Err(SimulationError::InvalidParameter {
+                parameter: "quantity".to_string(),
+                value: format!("{} {}", self.value, self.dimension),
+                range: format!("dimension of {} ({})", unit, target.dimension),
+            });
+        }
+        Ok(target.from_si(self.value))
+    }
+}
+
+/// Split the longest leading floating-point literal from the unit
+fn split_number(text: &str) -> Option<(&str, &str)> {
+    let bytes = text.as_bytes();
+    let mut end = 0;
+    let digits = |mut i: usize| {
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        i
+    };
+    if end < bytes.len() && (bytes[end] == b'+' || bytes[end] == b'-') {
+        end += 1;
+    }
+    let integer_end = digits(end);
+    end = integer_end;
+    if end < bytes.len() && bytes[end] == b'.' {
+        end = digits(end + 1);
+    }
+    if end == 0 || !bytes[..end].iter().any(u8::is_ascii_digit) {
+        return None;
+    }
+    // Exponent only if digits follow, so "2 m" and "3e" stay unambiguous
+    if end < bytes.len() && (bytes[end] == b'e' || bytes[end] == b'E') {
+        let mut i = end + 1;
+        if i < bytes.len() && (bytes[i] == b'+' || bytes[i] == b'-') {
+            i += 1;
+        }
+        let exponent_end = digits(i);
+        if exponent_end > i {
+            end = exponent_end;
+        }
+    }
+    Some((&text[..end], &text[end..]))
+}
+
+fn quantity_error(text: &str, expected: String) -> SimulationError {
+    SimulationError::InvalidParameter {
+        parameter: "quantity".to_string(),
+        value: text.to_string(),
+        range: expected,
+    }
+}
+
+/// Reject NaN and infinite values, which `f64` parsing accepts
+fn finite(text: &str, value: f64) -> Result<f64> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(quantity_error(text, "a finite number".to_string()))
+    }
+}
+
+/// Parse a quantity into a unit; bare numbers are taken to be in that unit
+pub fn parse_in(text: &str, unit: &str) -> Result<f64> {
+    let value = match text.trim().parse::<f64>() {
+        Ok(value) => value,
+        Err(_) => Quantity::parse(text)?.in_unit(unit)?,
+    };
+    finite(text, value)
+}
+
+/// Convert a value between two units of the same dimension
+pub fn convert(value: f64, from: &str, to: &str) -> Result<f64> {
+    let from = Unit::parse(from)?;
+    Quantity { value: from.to_si(value), dimension: from.dimension }.in_unit(to)
+}
+
+/// Split a trailing `[unit]` annotation from a formula
+///
+/// Returns the expression and the annotation text, or `None` if the
+/// formula does not end with a bracketed unit preceded by whitespace.
+pub fn split_formula_unit(formula: &str) -> (&str, Option<&str>) {
+    let trimmed = formula.trim_end();
+    if let Some(body) = trimmed.strip_suffix(']') {
+        if let Some(open) = body.rfind('[') {
+            let expression = &body[..open];
+            if expression.ends_with(char::is_whitespace) && !body[open + 1..].contains('[') {
+                return (expression.trim_end(), Some(&body[open + 1..]));
+            }
+        }
+    }
+    (formula, None)
+}
+
+/// Check the unit annotation of a formula against the dimension it must have
+///
+/// Formulas without an annotation pass; they are assumed to be in SI.
+pub fn check_formula_dimension(formula: &str, expected: Dimension, quantity: &str) -> Result<()> {
+    if let (_, Some(annotation)) = split_formula_unit(formula) {
+        let unit = Unit::parse(annotation)?;
+        if unit.dimension != expected {
+            return Err(SimulationError::FormulaError {
+                formula: formula.to_string(),
+                error: format!(
+                    "Result unit [{}] ({}) does not match {} ({})",
+                    annotation.trim(), unit.dimension, quantity, expected
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Deserialize a number or a quantity string into a unit
+fn deserialize_in<'de, D>(deserializer: D, unit: &str) -> std::result::Result<f64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrQuantity {
+        Number(f64),
+        Quantity(String),
+    }
+
+    match serde::Deserialize::deserialize(deserializer)? {
+        NumberOrQuantity::Number(value) => finite(&value.to_string(), value).map_err(serde::de::Error::custom),
+        NumberOrQuantity::Quantity(text) => parse_in(&text, unit).map_err(serde::de::Error::custom),
+    }
+}
+
+/// Deserialize a temperature in K (`#[serde(deserialize_with = "units::kelvin")]`)
+pub fn kelvin<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<f64, D::Error> {
+    deserialize_in(deserializer, "K")
+}
+
+/// Deserialize an optional temperature in K
+/// (`#[serde(default, deserialize_with = "units::optional_kelvin")]`)
+pub fn optional_kelvin<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<f64>, D::Error> {
+    #[derive(serde::Deserialize)]
+    struct Kelvin(#[serde(deserialize_with = "kelvin")] f64);
+
+    let temperature: Option<Kelvin> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(temperature.map(|Kelvin(value)| value))
+}
+
+/// Deserialize a length in m
+pub fn meters<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<f64, D::Error> {
+    deserialize_in(deserializer, "m")
+}
+
+/// Deserialize a duration in s
+pub fn seconds<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<f64, D::Error> {
+    deserialize_in(deserializer, "s")
+}
+
+/// Deserialize a power in kW
+pub fn kilowatts<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<f64, D::Error> {
+    deserialize_in(deserializer, "kW")
+}
+
+/// Deserialize a heat transfer coefficient in W/(m²·K)
+pub fn heat_transfer_coefficient<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<f64, D::Error> {
+    deserialize_in(deserializer, "W/(m^2*K)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantity_parsing_and_conversion() {
+        assert!((parse_in("25 degC", "K").unwrap() - 298.15).abs() < 1e-12);
+        assert!((parse_in("77 °F", "degC").unwrap() - 25.0).abs() < 1e-12);
+        assert_eq!(parse_in("150 kW", "kW").unwrap(), 150.0);
+        assert_eq!(parse_in("0.2 MW", "kW").unwrap(), 200.0);
+        assert_eq!(parse_in("250mm", "m").unwrap(), 0.25);
+        assert_eq!(parse_in("2", "m").unwrap(), 2.0);
+        assert_eq!(parse_in("1.5e3 W/(m^2*K)", "W/(m²·K)").unwrap(), 1500.0);
+        assert_eq!(parse_in("2 min", "s").unwrap(), 120.0);
+
+        // Dimension mismatches and unknown units are rejected
+        assert!(parse_in("150 kW", "K").is_err());
+        assert!(parse_in("3 parsecs", "m").is_err());
+        assert!(parse_in("kW", "kW").is_err());
+
+        // Non-finite numbers and overflowing exponents are errors, not panics
+        assert!(parse_in("NaN m", "m").is_err());
+        assert!(parse_in("NaN", "m").is_err());
+        assert!(parse_in("inf", "K").is_err());
+        assert!(parse_in("-inf s", "s").is_err());
+        assert!(parse_in("1e400 m", "m").is_err());
+        assert!(Unit::parse("(m^100)^2").is_err());
+        assert!(Unit::parse("m^100 m^100").is_err());
+
+        // Compound units and temperature differences
+        let conductivity = Unit::parse("W/m·K").unwrap();
+        assert_eq!(conductivity.dimension, Dimension::THERMAL_CONDUCTIVITY);
+        assert_eq!(Unit::parse("J/(kg·degC)").unwrap().offset, 0.0);
+        assert_eq!(Unit::parse("kJ/kg/K").unwrap().factor, 1000.0);
+        assert_eq!(Unit::parse("kg m-3").unwrap().dimension, Dimension::DENSITY);
+        assert_eq!(Unit::parse("S/m").unwrap().dimension, Dimension::ELECTRICAL_CONDUCTIVITY);
+        assert!(Unit::parse("W/(m").is_err());
+        assert!((convert(1.0, "kWh", "MJ").unwrap() - 3.6).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_formula_unit_annotation() {
+        assert_eq!(split_formula_unit("0.5 + 1e-3 * TC [W/(m*K)]"), ("0.5 + 1e-3 * TC", Some("W/(m*K)")));
+        assert_eq!(split_formula_unit("T * 2.0"), ("T * 2.0", None));
+        assert_eq!(split_formula_unit("values[2]"), ("values[2]", None));
+
+        assert!(check_formula_dimension("20.0 [W/(m*K)]", Dimension::THERMAL_CONDUCTIVITY, "thermal conductivity").is_ok());
+        assert!(check_formula_dimension("20.0", Dimension::THERMAL_CONDUCTIVITY, "thermal conductivity").is_ok());
+        let error = check_formula_dimension("20.0 [W/m^2]", Dimension::THERMAL_CONDUCTIVITY, "thermal conductivity");
+        assert!(error.unwrap_err().to_string().contains("thermal conductivity"));
+    }
+
+    #[test]
+    fn test_deserialize_quantities() {
+        #[derive(serde::Deserialize)]
+        struct Config {
+            #[serde(deserialize_with = "kelvin")]
+            temperature: f64,
+            #[serde(deserialize_with = "kilowatts")]
+            power: f64,
+            #[serde(default, deserialize_with = "optional_kelvin")]
+            wall_temperature: Option<f64>,
+        }
+
+        let config: Config = serde_json::from_str(r#"{"temperature": "1200 degC", "power": 150}"#).unwrap();
+        assert!((config.temperature - 1473.15).abs() < 1e-9);
+        assert_eq!(config.power, 150.0);
+        assert!(serde_json::from_str::<Config>(r#"{"temperature": "5 kW", "power": 1}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{"temperature": "NaN", "power": 1}"#).is_err());
+
+        // Optional temperatures may be missing, null or a quantity
+        assert_eq!(config.wall_temperature, None);
+        let config: Config = serde_json::from_str(r#"{"temperature": 300, "power": 1, "wall_temperature": null}"#).unwrap();
+        assert_eq!(config.wall_temperature, None);
+        let config: Config = serde_json::from_str(r#"{"temperature": 300, "power": 1, "wall_temperature": "27 degC"}"#).unwrap();
+        assert!((config.wall_temperature.unwrap() - 300.15).abs() < 1e-9);
+        assert!(serde_json::from_str::<Config>(r#"{"temperature": 300, "power": 1, "wall_temperature": "1 m"}"#).is_err());
+    }
+}